@@ -8,9 +8,9 @@ use crate::{
 };
 use anyhow::anyhow;
 use core::{fmt, ops::Range, str::FromStr};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::{Path, PathBuf},
 };
 use triple_accel::levenshtein_search as text_search;
@@ -20,9 +20,14 @@ pub type AnnotationSet = BTreeSet<Annotation>;
 pub type AnnotationReferenceMap<'a> =
     HashMap<(Target, Option<&'a str>), Vec<(usize, &'a Annotation)>>;
 
+/// Annotations grouped by the target document and section they cite
+pub type AnnotationSectionMap<'a> = BTreeMap<(Target, Option<&'a str>), Vec<&'a Annotation>>;
+
 pub trait AnnotationSetExt {
     fn targets(&self) -> Result<TargetSet, Error>;
     fn reference_map(&self) -> Result<AnnotationReferenceMap, Error>;
+    fn by_section(&self) -> Result<AnnotationSectionMap, Error>;
+    fn to_json(&self) -> Result<String, Error>;
 }
 
 impl AnnotationSetExt for AnnotationSet {
@@ -43,9 +48,23 @@ impl AnnotationSetExt for AnnotationSet {
         }
         Ok(map)
     }
+
+    fn by_section(&self) -> Result<AnnotationSectionMap, Error> {
+        let mut map = AnnotationSectionMap::new();
+        for anno in self.iter() {
+            let target = anno.target()?;
+            let section = anno.target_section();
+            map.entry((target, section)).or_default().push(anno);
+        }
+        Ok(map)
+    }
+
+    fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Debug, Default, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub struct Annotation {
     pub source: PathBuf,
     pub anno_line: u32,
@@ -53,6 +72,7 @@ pub struct Annotation {
     pub item_line: u32,
     pub item_column: u32,
     pub path: String,
+    #[serde(rename = "type")]
     pub anno: AnnotationType,
     pub target: String,
     pub quote: String,
@@ -132,7 +152,7 @@ impl Annotation {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum AnnotationType {
     Spec,
     Test,
@@ -166,7 +186,9 @@ impl FromStr for AnnotationType {
         match v {
             "SPEC" | "spec" => Ok(Self::Spec),
             "TEST" | "test" => Ok(Self::Test),
-            "CITATION" | "citation" => Ok(Self::Citation),
+            // An implementation annotation links a requirement to implementing code, which
+            // is what a citation already does
+            "CITATION" | "citation" | "IMPLEMENTATION" | "implementation" => Ok(Self::Citation),
             "EXCEPTION" | "exception" => Ok(Self::Exception),
             "TODO" | "todo" => Ok(Self::Todo),
             _ => Err(anyhow!(format!("Invalid annotation type {:?}", v))),
@@ -175,7 +197,7 @@ impl FromStr for AnnotationType {
 }
 
 // The order is in terms of priority from least to greatest
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash, Serialize, Deserialize)]
 pub enum AnnotationLevel {
     Auto,
     May,
@@ -217,3 +239,108 @@ impl FromStr for AnnotationLevel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotation_set_json_round_trip() {
+        let mut annotations = AnnotationSet::new();
+        annotations.insert(Annotation {
+            source: "src/lib.rs".into(),
+            anno_line: 12,
+            anno_column: 4,
+            item_line: 16,
+            target: "https://www.rfc-editor.org/rfc/rfc9002#section-6.2".into(),
+            quote: "A PTO timer expiration event does not indicate packet loss".into(),
+            level: AnnotationLevel::Must,
+            ..Default::default()
+        });
+        annotations.insert(Annotation {
+            source: "src/tests.rs".into(),
+            anno_line: 3,
+            anno: AnnotationType::Test,
+            target: "https://www.rfc-editor.org/rfc/rfc9002#section-7.2".into(),
+            quote: "The RECOMMENDED value is 2 * max_datagram_size.".into(),
+            tags: ["cc".to_string()].iter().cloned().collect(),
+            ..Default::default()
+        });
+
+        let json = annotations.to_json().unwrap();
+        assert!(json.contains(r#""type": "Test""#));
+
+        let decoded: AnnotationSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, annotations);
+    }
+
+    #[test]
+    fn annotation_set_by_section() {
+        let mut annotations = AnnotationSet::new();
+        annotations.insert(Annotation {
+            source: "src/prr.rs".into(),
+            anno_line: 10,
+            target: "https://www.rfc-editor.org/rfc/rfc6937#section-3.1".into(),
+            quote: "prr_delivered".into(),
+            ..Default::default()
+        });
+        annotations.insert(Annotation {
+            source: "src/prr.rs".into(),
+            anno_line: 20,
+            target: "https://www.rfc-editor.org/rfc/rfc6937#3.1".into(),
+            quote: "prr_out".into(),
+            ..Default::default()
+        });
+        annotations.insert(Annotation {
+            source: "src/prr.rs".into(),
+            anno_line: 30,
+            target: "https://www.rfc-editor.org/rfc/rfc6937#section-4".into(),
+            quote: "Proportional Rate Reduction".into(),
+            ..Default::default()
+        });
+
+        let target: Target = "https://www.rfc-editor.org/rfc/rfc6937".parse().unwrap();
+        let sections = annotations.by_section().unwrap();
+        assert_eq!(sections.len(), 2);
+
+        // `#section-3.1` and `#3.1` refer to the same section
+        let section_3_1 = &sections[&(target.clone(), Some("3.1"))];
+        assert_eq!(section_3_1.len(), 2);
+        assert!(section_3_1
+            .iter()
+            .all(|anno| anno.target_section() == Some("3.1")));
+
+        let section_4 = &sections[&(target, Some("4"))];
+        assert_eq!(section_4.len(), 1);
+        assert_eq!(section_4[0].anno_line, 30);
+    }
+
+    #[test]
+    fn annotation_type_round_trip() {
+        for anno in [
+            AnnotationType::Spec,
+            AnnotationType::Test,
+            AnnotationType::Citation,
+            AnnotationType::Exception,
+            AnnotationType::Todo,
+        ] {
+            let upper = anno.to_string();
+            assert_eq!(upper.parse::<AnnotationType>().unwrap(), anno);
+            assert_eq!(
+                upper.to_lowercase().parse::<AnnotationType>().unwrap(),
+                anno
+            );
+        }
+    }
+
+    #[test]
+    fn annotation_type_semantics() {
+        let parse = |v: &str| v.parse::<AnnotationType>().unwrap();
+
+        assert_eq!(parse("test"), AnnotationType::Test);
+        assert_eq!(parse("implementation"), AnnotationType::Citation);
+        assert_eq!(parse("IMPLEMENTATION"), AnnotationType::Citation);
+        assert_eq!(parse("exception"), AnnotationType::Exception);
+        assert!("invalid".parse::<AnnotationType>().is_err());
+    }
+}
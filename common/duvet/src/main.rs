@@ -13,6 +13,7 @@ mod source;
 mod sourcemap;
 mod specification;
 mod target;
+mod validate;
 
 pub use anyhow::Error;
 
@@ -27,6 +28,7 @@ fn main() {
 enum Arguments {
     Extract(extract::Extract),
     Report(report::Report),
+    Validate(validate::Validate),
 }
 
 impl Arguments {
@@ -34,6 +36,7 @@ impl Arguments {
         match self {
             Self::Extract(args) => args.exec(),
             Self::Report(args) => args.exec(),
+            Self::Validate(args) => args.exec(),
         }
     }
 }
@@ -96,9 +96,46 @@ impl<'a> ParsedAnnotation<'a> {
     }
 }
 
-impl<'a> From<ParsedAnnotation<'a>> for Annotation {
-    fn from(a: ParsedAnnotation<'a>) -> Self {
-        Annotation {
+impl<'a> TryFrom<ParsedAnnotation<'a>> for Annotation {
+    type Error = Error;
+
+    fn try_from(a: ParsedAnnotation<'a>) -> Result<Self, Self::Error> {
+        if a.target.is_empty() {
+            return Err(anyhow!("missing source information"));
+        }
+
+        if !a.comment.is_empty() && a.anno != AnnotationType::Exception {
+            return Err(anyhow!(format!(
+                "reason is only valid for exception annotations, not {}",
+                a.anno
+            )));
+        }
+
+        if !a.feature.is_empty() && a.anno != AnnotationType::Todo {
+            return Err(anyhow!(format!(
+                "feature is only valid for todo annotations, not {}",
+                a.anno
+            )));
+        }
+
+        if !a.tracking_issue.is_empty() && a.anno != AnnotationType::Todo {
+            return Err(anyhow!(format!(
+                "tracking-issue is only valid for todo annotations, not {}",
+                a.anno
+            )));
+        }
+
+        // exceptions and todos refer to a requirement rather than state one
+        if a.level != AnnotationLevel::Auto
+            && matches!(a.anno, AnnotationType::Exception | AnnotationType::Todo)
+        {
+            return Err(anyhow!(format!(
+                "level {} cannot be specified for {} annotations",
+                a.level, a.anno
+            )));
+        }
+
+        Ok(Annotation {
             target: a.target.to_string(),
             quote: a.quote.to_string(),
             anno: a.anno,
@@ -115,7 +152,7 @@ impl<'a> From<ParsedAnnotation<'a>> for Annotation {
             feature: a.feature.to_string(),
             tags: Default::default(),
             tracking_issue: a.tracking_issue.to_string(),
-        }
+        })
     }
 }
 
@@ -131,9 +168,133 @@ impl<'a> Iterator for Parser<'a> {
         match ParsedAnnotation::parse(data) {
             Ok((annotation, data)) => {
                 self.0 = data;
-                Some(Ok(annotation.into()))
+                Some(annotation.try_into())
             }
             Err(err) => Some(Err(err)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed() -> ParsedAnnotation<'static> {
+        ParsedAnnotation {
+            target: "https://www.rfc-editor.org/rfc/rfc9000#section-4",
+            quote: "A receiver MUST NOT renege on an advertisement",
+            anno_line: 3,
+            item_line: 5,
+            ..Default::default()
+        }
+    }
+
+    fn error(parsed: ParsedAnnotation) -> String {
+        Annotation::try_from(parsed).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn valid_conversion() {
+        let annotation = Annotation::try_from(ParsedAnnotation {
+            level: AnnotationLevel::Must,
+            ..parsed()
+        })
+        .unwrap();
+        assert_eq!(
+            annotation.target,
+            "https://www.rfc-editor.org/rfc/rfc9000#section-4"
+        );
+        assert_eq!(
+            annotation.quote,
+            "A receiver MUST NOT renege on an advertisement"
+        );
+        assert_eq!(annotation.anno, AnnotationType::Citation);
+        assert_eq!(annotation.level, AnnotationLevel::Must);
+        assert_eq!(annotation.anno_line, 3);
+        assert_eq!(annotation.item_line, 5);
+
+        let annotation = Annotation::try_from(ParsedAnnotation {
+            anno: AnnotationType::Exception,
+            comment: "not applicable to this implementation",
+            ..parsed()
+        })
+        .unwrap();
+        assert_eq!(annotation.comment, "not applicable to this implementation");
+
+        let annotation = Annotation::try_from(ParsedAnnotation {
+            anno: AnnotationType::Todo,
+            feature: "flow control",
+            tracking_issue: "123",
+            ..parsed()
+        })
+        .unwrap();
+        assert_eq!(annotation.feature, "flow control");
+        assert_eq!(annotation.tracking_issue, "123");
+    }
+
+    #[test]
+    fn missing_target() {
+        assert_eq!(
+            error(ParsedAnnotation {
+                target: "",
+                ..parsed()
+            }),
+            "missing source information"
+        );
+    }
+
+    #[test]
+    fn reason_without_exception() {
+        assert_eq!(
+            error(ParsedAnnotation {
+                comment: "not applicable",
+                ..parsed()
+            }),
+            "reason is only valid for exception annotations, not CITATION"
+        );
+    }
+
+    #[test]
+    fn feature_without_todo() {
+        assert_eq!(
+            error(ParsedAnnotation {
+                anno: AnnotationType::Test,
+                feature: "flow control",
+                ..parsed()
+            }),
+            "feature is only valid for todo annotations, not TEST"
+        );
+    }
+
+    #[test]
+    fn tracking_issue_without_todo() {
+        assert_eq!(
+            error(ParsedAnnotation {
+                anno: AnnotationType::Exception,
+                tracking_issue: "123",
+                ..parsed()
+            }),
+            "tracking-issue is only valid for todo annotations, not EXCEPTION"
+        );
+    }
+
+    #[test]
+    fn level_on_exception() {
+        assert_eq!(
+            error(ParsedAnnotation {
+                anno: AnnotationType::Exception,
+                level: AnnotationLevel::Should,
+                ..parsed()
+            }),
+            "level SHOULD cannot be specified for EXCEPTION annotations"
+        );
+        assert_eq!(
+            error(ParsedAnnotation {
+                anno: AnnotationType::Todo,
+                level: AnnotationLevel::Must,
+                ..parsed()
+            }),
+            "level MUST cannot be specified for TODO annotations"
+        );
+    }
+}
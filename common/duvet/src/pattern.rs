@@ -8,34 +8,87 @@ use crate::{
     Error,
 };
 use anyhow::anyhow;
-use std::path::Path;
+use core::fmt;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Pattern<'a> {
     meta: &'a str,
     content: &'a str,
+    tab_width: usize,
+    exception_attribute: Option<&'a str>,
 }
 
+/// The number of columns a tab character advances to when computing indentation
+const DEFAULT_TAB_WIDTH: usize = 4;
+
 impl<'a> Default for Pattern<'a> {
     fn default() -> Self {
         Self {
             meta: "//=",
             content: "//#",
+            tab_width: DEFAULT_TAB_WIDTH,
+            exception_attribute: None,
         }
     }
 }
 
 impl<'a> Pattern<'a> {
-    pub fn from_arg(arg: &'a str) -> Result<Self, Error> {
-        let mut parts = arg.split(',').filter(|p| !p.is_empty());
-        let meta = parts.next().expect("should have at least one pattern");
-        if meta.is_empty() {
-            return Err(anyhow!("compliance pattern cannot be empty"));
+    /// Pattern for languages using `#` comments, e.g. Python and shell scripts
+    pub fn hash() -> Self {
+        Self {
+            meta: "#=",
+            content: "##",
+            tab_width: DEFAULT_TAB_WIDTH,
+            exception_attribute: None,
         }
+    }
 
-        let content = parts.next().unwrap();
+    /// Sets the tab width used for computing the visual column of annotations
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// Recognizes exceptions written as an attribute on the annotated item
+    ///
+    /// With an attribute of `compliance::exception`, a line such as
+    /// `#[compliance::exception("https://example.com/spec#section-1", reason = "...")]`
+    /// is parsed as an exception annotation for the item that follows it.
+    pub fn with_exception_attribute(mut self, attribute: &'a str) -> Self {
+        self.exception_attribute = Some(attribute);
+        self
+    }
 
-        Ok(Self { meta, content })
+    /// Selects the default pattern for a file based on its extension
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("py" | "sh") => Self::hash(),
+            _ => Self::default(),
+        }
+    }
+
+    pub fn from_arg(arg: &'a str) -> Result<Self, Error> {
+        let mut parts = arg.split(',').map(|p| p.trim()).filter(|p| !p.is_empty());
+        let meta = parts
+            .next()
+            .ok_or_else(|| anyhow!("compliance pattern cannot be empty"))?;
+        let content = parts
+            .next()
+            .ok_or_else(|| anyhow!("compliance pattern is missing a content prefix"))?;
+
+        if meta == content {
+            return Err(anyhow!(
+                "compliance meta and content prefixes must be different"
+            ));
+        }
+
+        Ok(Self {
+            meta,
+            content,
+            tab_width: DEFAULT_TAB_WIDTH,
+            exception_attribute: None,
+        })
     }
 
     pub fn extract(
@@ -44,7 +97,52 @@ impl<'a> Pattern<'a> {
         path: &Path,
         annotations: &mut AnnotationSet,
     ) -> Result<(), Error> {
+        self.parse(source, path, |result| {
+            annotations.insert(result.map_err(|issue| anyhow!(issue.message))?);
+            Ok(())
+        })
+    }
+
+    /// Checks the annotations in `source` without extracting them
+    ///
+    /// Rather than stopping at the first error, every issue in the file is collected
+    /// and the parser recovers as best it can: invalid metadata fields are ignored and
+    /// metadata found while parsing content is skipped. The target of each annotation
+    /// is also checked to be a well-formed path or URL.
+    pub fn validate(&self, source: &str, path: &Path) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+
+        let result = self.parse(source, path, |result| {
+            let issue = match result {
+                Ok(annotation) => match annotation.target() {
+                    Ok(_) => return Ok(()),
+                    Err(error) => ValidationIssue::new(path, annotation.anno_line as _, error),
+                },
+                Err(issue) => issue,
+            };
+            issues.push(issue);
+            Ok(())
+        });
+
+        debug_assert!(result.is_ok(), "issues should never abort validation");
+
+        issues
+    }
+
+    fn parse<F>(&self, source: &str, path: &Path, mut on_result: F) -> Result<(), Error>
+    where
+        F: FnMut(Result<Annotation, ValidationIssue>) -> Result<(), Error>,
+    {
         let mut state = ParserState::Search;
+        let mut last_line_no = 0;
+
+        macro_rules! check {
+            ($line:expr, $result:expr) => {
+                if let Err(error) = $result {
+                    on_result(Err(ValidationIssue::new(path, $line, error)))?;
+                }
+            };
+        }
 
         for Str {
             value: line,
@@ -52,10 +150,19 @@ impl<'a> Pattern<'a> {
             ..
         } in LinesIter::new(source)
         {
+            last_line_no = line_no;
             let content = line.trim_start();
 
             match core::mem::replace(&mut state, ParserState::Search) {
                 ParserState::Search => {
+                    if let Some(args) = self.exception_attribute_args(content) {
+                        let mut capture = Capture::new(line_no, self.column(line));
+                        check!(line_no, capture.push_attribute(args));
+
+                        state = ParserState::CapturingMeta(capture);
+                        continue;
+                    }
+
                     let content = if let Some(content) = content.strip_prefix(self.meta) {
                         content
                     } else {
@@ -66,38 +173,97 @@ impl<'a> Pattern<'a> {
                         continue;
                     }
 
-                    let indent = line.len() - content.len();
-                    let mut capture = Capture::new(line_no, indent);
-                    capture.push_meta(content)?;
+                    let mut capture = Capture::new(line_no, self.column(line));
+                    check!(line_no, capture.push_meta(content));
 
                     state = ParserState::CapturingMeta(capture);
                 }
                 ParserState::CapturingMeta(mut capture) => {
                     if let Some(meta) = content.strip_prefix(self.meta) {
-                        capture.push_meta(meta)?;
+                        check!(line_no, capture.push_meta(meta));
                         state = ParserState::CapturingMeta(capture);
                     } else if let Some(content) = content.strip_prefix(self.content) {
                         capture.push_content(content);
                         state = ParserState::CapturingContent(capture);
                     } else {
-                        annotations.insert(capture.done(line_no, path)?);
+                        on_result(capture.done(line_no, self.column(line), path))?;
                     }
                 }
                 ParserState::CapturingContent(mut capture) => {
                     if content.starts_with(self.meta) {
-                        return Err(anyhow!("cannot set metadata while parsing content"));
+                        let error = anyhow!("cannot set metadata while parsing content");
+                        on_result(Err(ValidationIssue::new(path, line_no, error)))?;
+                        state = ParserState::CapturingContent(capture);
                     } else if let Some(content) = content.strip_prefix(self.content) {
                         capture.push_content(content);
                         state = ParserState::CapturingContent(capture);
                     } else {
-                        annotations.insert(capture.done(line_no, path)?);
+                        on_result(capture.done(line_no, self.column(line), path))?;
                     }
                 }
             }
         }
 
+        // flush any annotation that was still being captured when the file ended
+        match state {
+            ParserState::Search => {}
+            ParserState::CapturingMeta(capture) | ParserState::CapturingContent(capture) => {
+                on_result(capture.done(last_line_no + 1, 0, path))?;
+            }
+        }
+
         Ok(())
     }
+
+    /// Returns the arguments of `content` if it is the configured exception attribute
+    fn exception_attribute_args<'b>(&self, content: &'b str) -> Option<&'b str> {
+        let attribute = self.exception_attribute?;
+        content
+            .trim_end()
+            .strip_prefix("#[")?
+            .strip_prefix(attribute)?
+            .strip_prefix('(')?
+            .strip_suffix(")]")
+    }
+
+    /// Computes the visual column of the first non-whitespace character in `line`
+    ///
+    /// Tabs advance the column to the next multiple of the configured tab width.
+    fn column(&self, line: &str) -> usize {
+        let mut column = 0;
+        for c in line.chars() {
+            match c {
+                '\t' => column += self.tab_width - column % self.tab_width,
+                c if c.is_whitespace() => column += 1,
+                _ => break,
+            }
+        }
+        column
+    }
+}
+
+/// A problem with an annotation, found while validating a source file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(path: &Path, line: usize, error: Error) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            line,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+    }
 }
 
 enum ParserState<'a> {
@@ -132,6 +298,35 @@ impl<'a> Capture<'a> {
         let key = parts.next().unwrap();
         let value = parts.next();
 
+        self.push_field(key, value)
+    }
+
+    /// Pushes the comma-separated arguments of an exception attribute
+    ///
+    /// Arguments use the same keys as the metadata lines, with an optional
+    /// positional target. Values may be quoted.
+    fn push_attribute(&mut self, args: &'a str) -> Result<(), Error> {
+        self.annotation.anno = AnnotationType::Exception;
+
+        for arg in split_args(args) {
+            let arg = arg.trim();
+
+            if arg.is_empty() {
+                continue;
+            }
+
+            match arg.split_once('=') {
+                Some((key, value)) if !arg.starts_with('"') => {
+                    self.push_field(key.trim(), Some(unquote(value.trim())))?
+                }
+                _ => self.push_field(unquote(arg), None)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_field(&mut self, key: &'a str, value: Option<&'a str>) -> Result<(), Error> {
         match (key, value) {
             ("source", Some(value)) => self.annotation.target = value,
             ("level", Some(value)) => self.annotation.level = value.parse()?,
@@ -162,24 +357,285 @@ impl<'a> Capture<'a> {
         }
     }
 
-    fn done(self, item_line: usize, path: &Path) -> Result<Annotation, Error> {
-        let mut annotation = Annotation {
-            item_line: item_line as _,
-            item_column: 0,
-            source: path.into(),
-            quote: self.contents,
-            manifest_dir: std::env::current_dir()?,
-            ..self.annotation.into()
-        };
+    fn done(
+        mut self,
+        item_line: usize,
+        item_column: usize,
+        path: &Path,
+    ) -> Result<Annotation, ValidationIssue> {
+        let anno_line = self.annotation.anno_line as usize;
+        self.annotation.item_line = item_line as _;
+        self.annotation.item_column = item_column as _;
+
+        let issue = |error| ValidationIssue::new(path, anno_line, error);
+
+        let mut annotation = Annotation::try_from(self.annotation).map_err(issue)?;
+        annotation.source = path.into();
+        annotation.quote = self.contents;
+        annotation.manifest_dir = std::env::current_dir().map_err(|error| issue(error.into()))?;
 
         while annotation.quote.ends_with(' ') {
             annotation.quote.pop();
         }
 
-        if annotation.target.is_empty() {
-            return Err(anyhow!("missing source information"));
+        Ok(annotation)
+    }
+}
+
+/// Splits attribute arguments on commas that are not inside a quoted value
+fn split_args(args: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut in_quotes = false;
+
+    for (idx, c) in args.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(&args[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
         }
+    }
 
-        Ok(annotation)
+    parts.push(&args[start..]);
+    parts
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotation::AnnotationLevel;
+
+    fn extract(pattern: Pattern, source: &str) -> Vec<Annotation> {
+        let mut annotations = AnnotationSet::new();
+        pattern
+            .extract(source, Path::new("test.py"), &mut annotations)
+            .unwrap();
+        annotations.into_iter().collect()
+    }
+
+    #[test]
+    fn hash_pattern_from_arg() {
+        assert_eq!(Pattern::from_arg("#=,##").unwrap(), Pattern::hash());
+        assert_eq!(Pattern::from_arg("#=, ##").unwrap(), Pattern::hash());
+        assert!(Pattern::from_arg("#=").is_err());
+        assert!(Pattern::from_arg("##,##").is_err());
+        assert!(Pattern::from_arg("").is_err());
+    }
+
+    #[test]
+    fn pattern_for_path() {
+        assert_eq!(Pattern::for_path(Path::new("build.py")), Pattern::hash());
+        assert_eq!(Pattern::for_path(Path::new("ci/run.sh")), Pattern::hash());
+        assert_eq!(
+            Pattern::for_path(Path::new("src/lib.rs")),
+            Pattern::default()
+        );
+    }
+
+    #[test]
+    fn python_annotation() {
+        let source = r#"
+def on_packet_sent():
+    #= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
+    #= type=test
+    ## A sender SHOULD restart its PTO timer every time an ack-eliciting
+    ## packet is sent or acknowledged
+    restart_pto_timer()
+"#;
+
+        let annotations = extract(Pattern::hash(), source);
+        assert_eq!(annotations.len(), 1);
+
+        let annotation = &annotations[0];
+        assert_eq!(
+            annotation.target,
+            "https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1"
+        );
+        assert_eq!(annotation.anno, AnnotationType::Test);
+        assert_eq!(
+            annotation.quote,
+            "A sender SHOULD restart its PTO timer every time an ack-eliciting packet is sent or acknowledged"
+        );
+        assert_eq!(annotation.anno_line, 3);
+        assert_eq!(annotation.item_line, 7);
+    }
+
+    #[test]
+    fn tab_indentation() {
+        let source = "fn main() {\n\t//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n\t//# quote\n\t\tfoo();\n}\n";
+
+        let annotations = extract(Pattern::default(), source);
+        assert_eq!(annotations[0].anno_column, 4);
+        assert_eq!(annotations[0].item_column, 8);
+
+        let annotations = extract(Pattern::default().with_tab_width(8), source);
+        assert_eq!(annotations[0].anno_column, 8);
+        assert_eq!(annotations[0].item_column, 16);
+    }
+
+    #[test]
+    fn mixed_indentation() {
+        let pattern = Pattern::default();
+
+        // a tab after spaces only advances to the next tab stop
+        assert_eq!(pattern.column("  \t//="), 4);
+        assert_eq!(pattern.column("\t  //="), 6);
+        assert_eq!(pattern.column("    \t//="), 8);
+        assert_eq!(pattern.column("//="), 0);
+        assert_eq!(pattern.column(""), 0);
+
+        let source =
+            "  \t//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n  \t//# quote\n\t  foo();\n";
+        let annotations = extract(pattern, source);
+        assert_eq!(annotations[0].anno_column, 4);
+        assert_eq!(annotations[0].item_column, 6);
+    }
+
+    #[test]
+    fn annotation_at_eof() {
+        let target = "https://www.rfc-editor.org/rfc/rfc9000#section-4";
+
+        for source in [
+            "fn main() {}\n//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n//# quote",
+            "fn main() {}\n//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n//# quote\n",
+        ] {
+            let annotations = extract(Pattern::default(), source);
+            assert_eq!(annotations.len(), 1, "{:?}", source);
+            assert_eq!(annotations[0].target, target);
+            assert_eq!(annotations[0].quote, "quote");
+            assert_eq!(annotations[0].anno_line, 2);
+            assert_eq!(annotations[0].item_line, 4);
+        }
+
+        // a file ending while capturing metadata is also flushed
+        let annotations = extract(
+            Pattern::default(),
+            "//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n//= type=exception",
+        );
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].anno, AnnotationType::Exception);
+        assert!(annotations[0].quote.is_empty());
+    }
+
+    #[test]
+    fn python_annotation_ignores_comments() {
+        let source = r#"
+# a regular comment
+#=https://www.rfc-editor.org/rfc/rfc9000#section-4
+#= level=MUST
+## A receiver MUST NOT renege on an advertisement
+# another comment
+
+## not captured
+"#;
+
+        let annotations = extract(Pattern::hash(), source);
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(
+            annotations[0].target,
+            "https://www.rfc-editor.org/rfc/rfc9000#section-4"
+        );
+        assert_eq!(annotations[0].level, AnnotationLevel::Must);
+        assert_eq!(
+            annotations[0].quote,
+            "A receiver MUST NOT renege on an advertisement"
+        );
+    }
+
+    #[test]
+    fn exception_attribute() {
+        let source = r#"
+#[compliance::exception("https://www.rfc-editor.org/rfc/rfc9000#section-4", reason = "flow control is enforced by the application, not the transport")]
+fn on_stream_data() {}
+"#;
+
+        // attributes are only recognized when configured
+        assert!(extract(Pattern::default(), source).is_empty());
+
+        let annotations = extract(
+            Pattern::default().with_exception_attribute("compliance::exception"),
+            source,
+        );
+        assert_eq!(annotations.len(), 1);
+
+        let annotation = &annotations[0];
+        assert_eq!(
+            annotation.target,
+            "https://www.rfc-editor.org/rfc/rfc9000#section-4"
+        );
+        assert_eq!(annotation.anno, AnnotationType::Exception);
+        assert_eq!(
+            annotation.comment,
+            "flow control is enforced by the application, not the transport"
+        );
+        assert!(annotation.quote.is_empty());
+        assert_eq!(annotation.anno_line, 2);
+        assert_eq!(annotation.item_line, 3);
+    }
+
+    #[test]
+    fn validate() {
+        let source = r#"
+//= https://www.rfc-editor.org/rfc/rfc9000#section-4
+//# A receiver MUST NOT renege on an advertisement
+fn valid() {}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-4
+//= priority=high
+//# quote
+fn invalid_field() {}
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-4
+//# quote
+//= type=test
+//# more quote
+fn meta_after_content() {}
+
+//= type=test
+//# quote
+fn missing_target() {}
+
+//= https://[invalid
+//# quote
+fn invalid_target() {}
+"#;
+        let path = Path::new("src/lib.rs");
+
+        let issues = Pattern::default().validate(source, path);
+        let lines: Vec<_> = issues.iter().map(|issue| issue.line).collect();
+        assert_eq!(lines, [7, 13, 17, 21], "{:#?}", issues);
+        assert!(issues.iter().all(|issue| issue.path == path));
+        assert_eq!(issues[0].message, "invalid metadata field priority");
+        assert_eq!(
+            issues[1].to_string(),
+            "src/lib.rs:13: cannot set metadata while parsing content"
+        );
+        assert_eq!(issues[2].message, "missing source information");
+
+        // extraction still fails on the first issue
+        let mut annotations = AnnotationSet::new();
+        let error = Pattern::default()
+            .extract(source, path, &mut annotations)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "invalid metadata field priority");
+    }
+
+    #[test]
+    fn validate_clean_file() {
+        let source =
+            "//= https://www.rfc-editor.org/rfc/rfc9000#section-4\n//# quote\nfn main() {}\n";
+        assert!(Pattern::default()
+            .validate(source, Path::new("src/lib.rs"))
+            .is_empty());
     }
 }
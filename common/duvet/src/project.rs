@@ -55,6 +55,14 @@ pub struct Project {
     /// Glob patterns for spec files
     #[structopt(long = "spec-pattern")]
     spec_patterns: Vec<String>,
+
+    /// Number of columns a tab advances when computing annotation columns
+    #[structopt(long = "tab-width", default_value = "4")]
+    tab_width: usize,
+
+    /// Attribute path recognized as an exception on the following item, e.g. `compliance::exception`
+    #[structopt(long = "exception-attribute")]
+    exception_attribute: Option<String>,
 }
 
 impl Project {
@@ -86,7 +94,7 @@ impl Project {
     }
 
     fn source_file<'a>(
-        &self,
+        &'a self,
         pattern: &'a str,
         files: &mut HashSet<SourceFile<'a>>,
     ) -> Result<(), Error> {
@@ -97,13 +105,21 @@ impl Project {
 
             let pattern = Pattern::from_arg(pattern)?;
 
-            (pattern, file_pattern)
+            (Some(pattern), file_pattern)
         } else {
-            (Pattern::default(), pattern)
+            (None, pattern)
         };
 
         for entry in glob(file_pattern)? {
-            files.insert(SourceFile::Text(compliance_pattern, entry?));
+            let entry = entry?;
+            // fall back to a pattern matching the file's comment syntax
+            let mut compliance_pattern = compliance_pattern
+                .unwrap_or_else(|| Pattern::for_path(&entry))
+                .with_tab_width(self.tab_width);
+            if let Some(attribute) = self.exception_attribute.as_deref() {
+                compliance_pattern = compliance_pattern.with_exception_attribute(attribute);
+            }
+            files.insert(SourceFile::Text(compliance_pattern, entry));
         }
 
         Ok(())
@@ -40,6 +40,9 @@ pub struct Report {
     #[structopt(long)]
     html: Option<PathBuf>,
 
+    #[structopt(long)]
+    annotations_json: Option<PathBuf>,
+
     #[structopt(long)]
     require_citations: Option<Option<bool>>,
 
@@ -110,6 +113,10 @@ impl Report {
             })
             .collect();
 
+        if let Some(annotations_json) = &self.annotations_json {
+            std::fs::write(annotations_json, annotations.to_json()?)?;
+        }
+
         let targets = annotations.targets()?;
 
         let contents: HashMap<_, _> = targets
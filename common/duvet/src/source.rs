@@ -3,7 +3,7 @@
 
 use crate::{
     annotation::{Annotation, AnnotationLevel, AnnotationSet, AnnotationType},
-    pattern::Pattern,
+    pattern::{Pattern, ValidationIssue},
     specification::Format,
     Error,
 };
@@ -44,6 +44,20 @@ impl<'a> SourceFile<'a> {
             }
         }
     }
+
+    /// Returns every annotation issue found in the file
+    ///
+    /// Spec files are deserialized as a whole, so they are only checked by
+    /// [`Self::annotations`].
+    pub fn validate(&self) -> Result<Vec<ValidationIssue>, Error> {
+        match self {
+            Self::Text(pattern, file) => {
+                let text = std::fs::read_to_string(file)?;
+                Ok(pattern.validate(&text, file))
+            }
+            Self::Spec(_) => Ok(vec![]),
+        }
+    }
 }
 
 #[derive(Deserialize)]
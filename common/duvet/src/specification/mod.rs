@@ -9,6 +9,7 @@ use core::{
     ops::{Deref, Range},
     str::FromStr,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 pub mod ietf;
@@ -41,7 +42,7 @@ impl<'a> Specification<'a> {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Ord, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Ord, Eq, Hash, Serialize, Deserialize)]
 pub enum Format {
     Auto,
     Ietf,
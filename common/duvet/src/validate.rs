@@ -0,0 +1,35 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{project::Project, Error};
+use anyhow::anyhow;
+use structopt::StructOpt;
+
+/// Checks the annotations in the project sources without producing a report
+#[derive(Debug, StructOpt)]
+pub struct Validate {
+    #[structopt(flatten)]
+    project: Project,
+}
+
+impl Validate {
+    pub fn exec(&self) -> Result<(), Error> {
+        let mut issues = vec![];
+
+        for source in self.project.sources()? {
+            issues.extend(source.validate()?);
+        }
+
+        issues.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+
+        for issue in &issues {
+            eprintln!("{}", issue);
+        }
+
+        if !issues.is_empty() {
+            return Err(anyhow!(format!("found {} annotation issues", issues.len())));
+        }
+
+        Ok(())
+    }
+}
@@ -4,12 +4,18 @@
 use crate::{
     ack,
     event::{api::SocketAddress, IntoEvent},
-    inet, stream,
+    inet,
+    path::MINIMUM_MTU,
+    recovery::{
+        resumption::ResumptionState, DEFAULT_INITIAL_RTT, K_GRANULARITY,
+        K_PERSISTENT_CONGESTION_THRESHOLD, MAX_INITIAL_RTT,
+    },
+    stream,
     transport::parameters::{
         AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
         InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
         InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, TransportParameters,
+        MaxDatagramFrameSize, MaxIdleTimeout, MinAckDelay, TransportParameters,
     },
 };
 use core::{convert::TryInto, time::Duration};
@@ -28,6 +34,12 @@ const MAX_HANDSHAKE_DURATION_DEFAULT: Duration = Duration::from_secs(10);
 //# middleboxes from losing state for UDP flows [GATEWAY].
 const MAX_KEEP_ALIVE_PERIOD_DEFAULT: Duration = Duration::from_secs(30);
 
+// The smallest initial congestion window, which applies to the minimum maximum datagram size
+const MIN_REMEMBERED_CONGESTION_WINDOW: u32 = 10 * MINIMUM_MTU as u32;
+
+// The congestion controller limits a remembered window to 1000 datagrams
+const MAX_REMEMBERED_CONGESTION_WINDOW: u32 = 1000 * u16::MAX as u32;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct ConnectionInfo<'a> {
@@ -56,6 +68,7 @@ pub struct Limits {
     pub(crate) max_open_remote_bidirectional_streams: InitialMaxStreamsBidi,
     pub(crate) max_open_remote_unidirectional_streams: InitialMaxStreamsUni,
     pub(crate) max_ack_delay: MaxAckDelay,
+    pub(crate) min_ack_delay: Option<MinAckDelay>,
     pub(crate) ack_delay_exponent: AckDelayExponent,
     pub(crate) max_active_connection_ids: ActiveConnectionIdLimit,
     pub(crate) ack_elicitation_interval: u8,
@@ -64,6 +77,15 @@ pub struct Limits {
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) initial_rtt: Duration,
+    pub(crate) remembered_congestion_window: Option<u32>,
+    pub(crate) remembered_slow_start_threshold: Option<u32>,
+    pub(crate) initial_rttvar: Option<Duration>,
+    pub(crate) persistent_congestion_threshold: u32,
+    pub(crate) timer_granularity: Duration,
+    pub(crate) max_tracked_packets: Option<usize>,
+    pub(crate) time_threshold: Option<(u32, u32)>,
+    pub(crate) min_rtt_loss_floor: Option<u32>,
 }
 
 impl Default for Limits {
@@ -94,6 +116,7 @@ impl Limits {
             max_open_remote_bidirectional_streams: InitialMaxStreamsBidi::RECOMMENDED,
             max_open_remote_unidirectional_streams: InitialMaxStreamsUni::RECOMMENDED,
             max_ack_delay: MaxAckDelay::RECOMMENDED,
+            min_ack_delay: None,
             ack_delay_exponent: AckDelayExponent::RECOMMENDED,
             max_active_connection_ids: ActiveConnectionIdLimit::RECOMMENDED,
             ack_elicitation_interval: ack::Settings::RECOMMENDED.ack_elicitation_interval,
@@ -102,6 +125,15 @@ impl Limits {
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            initial_rtt: DEFAULT_INITIAL_RTT,
+            remembered_congestion_window: None,
+            remembered_slow_start_threshold: None,
+            initial_rttvar: None,
+            persistent_congestion_threshold: K_PERSISTENT_CONGESTION_THRESHOLD,
+            timer_granularity: K_GRANULARITY,
+            max_tracked_packets: None,
+            time_threshold: None,
+            min_rtt_loss_floor: None,
         }
     }
 
@@ -168,7 +200,31 @@ impl Limits {
         max_open_remote_unidirectional_streams,
         u64
     );
-    setter!(with_max_ack_delay, max_ack_delay, Duration);
+
+    /// Sets the maximum amount of time by which the endpoint will delay sending
+    /// acknowledgements
+    ///
+    /// The value must not be less than the min ack delay, if one is set.
+    pub fn with_max_ack_delay(mut self, value: Duration) -> Result<Self, ValidationError> {
+        let max_ack_delay: MaxAckDelay = value.try_into()?;
+        if let Some(min_ack_delay) = self.min_ack_delay {
+            min_ack_delay.validate_with_max_ack_delay(max_ack_delay)?;
+        }
+        self.max_ack_delay = max_ack_delay;
+        Ok(self)
+    }
+
+    /// Sets the minimum amount of time by which the endpoint can delay sending
+    /// acknowledgements
+    ///
+    /// Setting this value advertises support for ACK_FREQUENCY frames, which allow the peer
+    /// to control how often acknowledgements are sent. The value must not be greater than
+    /// the max ack delay.
+    pub fn with_min_ack_delay(mut self, value: Duration) -> Result<Self, ValidationError> {
+        let min_ack_delay: MinAckDelay = value.try_into()?;
+        self.min_ack_delay = Some(min_ack_delay.validate_with_max_ack_delay(self.max_ack_delay)?);
+        Ok(self)
+    }
     setter!(
         with_max_active_connection_ids,
         max_active_connection_ids,
@@ -184,6 +240,135 @@ impl Limits {
     );
     setter!(with_max_keep_alive_period, max_keep_alive_period, Duration);
 
+    /// Sets the initial RTT estimate used before any RTT samples are available
+    ///
+    /// A resumed connection may use the final smoothed RTT of a prior connection to the
+    /// same peer. The value must be between 1ms and 1s.
+    pub fn with_initial_rtt(mut self, value: Duration) -> Result<Self, ValidationError> {
+        if !(K_GRANULARITY..=MAX_INITIAL_RTT).contains(&value) {
+            return Err(ValidationError("initial RTT must be between 1ms and 1s"));
+        }
+        self.initial_rtt = value;
+        Ok(self)
+    }
+
+    /// Sets the congestion window, in bytes, remembered from a prior connection to the
+    /// same peer
+    ///
+    /// The congestion controller starts with this window rather than its default initial
+    /// window. The value must be at least the initial window for the minimum datagram size
+    /// and at most 1000 datagrams of the maximum datagram size. The congestion controller
+    /// further clamps the window to the bounds for the datagram size of the path.
+    pub fn with_remembered_cwnd(mut self, value: u32) -> Result<Self, ValidationError> {
+        if !(MIN_REMEMBERED_CONGESTION_WINDOW..=MAX_REMEMBERED_CONGESTION_WINDOW).contains(&value) {
+            return Err(ValidationError(
+                "remembered congestion window is outside the supported bounds",
+            ));
+        }
+        self.remembered_congestion_window = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the initial RTT, RTT variance, congestion window and slow start threshold from
+    /// the state exported by a prior connection to the same peer
+    ///
+    /// The smoothed RTT and congestion window are validated with the same bounds as
+    /// [`Self::with_initial_rtt`] and [`Self::with_remembered_cwnd`].
+    pub fn with_resumption_state(self, value: ResumptionState) -> Result<Self, ValidationError> {
+        let mut limits = self
+            .with_initial_rtt(value.smoothed_rtt)?
+            .with_remembered_cwnd(value.congestion_window)?;
+        limits.initial_rttvar = Some(value.rttvar);
+        limits.remembered_slow_start_threshold = value.remembered_slow_start_threshold();
+        Ok(limits)
+    }
+
+    /// Sets the multiplier applied to the PTO period when determining if losses
+    /// represent persistent congestion
+    ///
+    /// Defaults to 3. Larger values may be desirable on links with high random loss. The
+    /// value must be greater than zero.
+    pub fn with_persistent_congestion_threshold(
+        mut self,
+        value: u32,
+    ) -> Result<Self, ValidationError> {
+        if value == 0 {
+            return Err(ValidationError(
+                "persistent congestion threshold must be greater than zero",
+            ));
+        }
+        self.persistent_congestion_threshold = value;
+        Ok(self)
+    }
+
+    /// Sets the granularity of the local timer, used as the floor in loss delay and PTO
+    /// computations
+    ///
+    /// Defaults to 1ms. Platforms with coarser timers should set a larger value to
+    /// avoid spurious timeouts. The value must be greater than zero.
+    pub fn with_timer_granularity(mut self, value: Duration) -> Result<Self, ValidationError> {
+        if value.is_zero() {
+            return Err(ValidationError(
+                "timer granularity must be greater than zero",
+            ));
+        }
+        self.timer_granularity = value;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of unacknowledged packets tracked in each packet number space
+    ///
+    /// Once more than this number of packets are tracked, the oldest packets are declared
+    /// lost so their frames are retransmitted. By default, every packet is tracked until it
+    /// is acknowledged or declared lost. The value must be greater than zero.
+    pub fn with_max_tracked_packets(mut self, value: usize) -> Result<Self, ValidationError> {
+        if value == 0 {
+            return Err(ValidationError(
+                "max tracked packets must be greater than zero",
+            ));
+        }
+        self.max_tracked_packets = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the RTT multiplier used for time threshold loss detection to
+    /// `numerator / denominator`
+    ///
+    /// Defaults to 9/8. Larger values tolerate more reordering on the path before packets
+    /// are declared lost. The denominator must be greater than zero and the multiplier must
+    /// be at least 1.
+    pub fn with_time_threshold(
+        mut self,
+        numerator: u32,
+        denominator: u32,
+    ) -> Result<Self, ValidationError> {
+        if denominator == 0 {
+            return Err(ValidationError(
+                "time threshold denominator must be greater than zero",
+            ));
+        }
+        if numerator < denominator {
+            return Err(ValidationError("time threshold must be at least 1"));
+        }
+        self.time_threshold = Some((numerator, denominator));
+        Ok(self)
+    }
+
+    /// Sets a floor on the loss time threshold of `multiplier` times the minimum RTT
+    ///
+    /// Packets delayed by a transient RTT spike are given at least this many minimum RTTs to
+    /// be acknowledged before they are declared lost. By default, no floor is applied. The
+    /// value must be greater than zero.
+    pub fn with_min_rtt_loss_floor(mut self, multiplier: u32) -> Result<Self, ValidationError> {
+        if multiplier == 0 {
+            return Err(ValidationError(
+                "min RTT loss floor must be greater than zero",
+            ));
+        }
+        self.min_rtt_loss_floor = Some(multiplier);
+        Ok(self)
+    }
+
     // internal APIs
 
     #[doc(hidden)]
@@ -239,6 +424,11 @@ impl Limits {
         self.max_idle_timeout.as_duration()
     }
 
+    #[doc(hidden)]
+    pub fn min_ack_delay(&self) -> Option<Duration> {
+        self.min_ack_delay.map(MinAckDelay::as_duration)
+    }
+
     #[doc(hidden)]
     pub fn max_handshake_duration(&self) -> Duration {
         self.max_handshake_duration
@@ -248,6 +438,51 @@ impl Limits {
     pub fn max_keep_alive_period(&self) -> Duration {
         self.max_keep_alive_period
     }
+
+    #[doc(hidden)]
+    pub fn initial_rtt(&self) -> Duration {
+        self.initial_rtt
+    }
+
+    #[doc(hidden)]
+    pub fn remembered_congestion_window(&self) -> Option<u32> {
+        self.remembered_congestion_window
+    }
+
+    #[doc(hidden)]
+    pub fn remembered_slow_start_threshold(&self) -> Option<u32> {
+        self.remembered_slow_start_threshold
+    }
+
+    #[doc(hidden)]
+    pub fn initial_rttvar(&self) -> Option<Duration> {
+        self.initial_rttvar
+    }
+
+    #[doc(hidden)]
+    pub fn persistent_congestion_threshold(&self) -> u32 {
+        self.persistent_congestion_threshold
+    }
+
+    #[doc(hidden)]
+    pub fn timer_granularity(&self) -> Duration {
+        self.timer_granularity
+    }
+
+    #[doc(hidden)]
+    pub fn max_tracked_packets(&self) -> Option<usize> {
+        self.max_tracked_packets
+    }
+
+    #[doc(hidden)]
+    pub fn time_threshold(&self) -> Option<(u32, u32)> {
+        self.time_threshold
+    }
+
+    #[doc(hidden)]
+    pub fn min_rtt_loss_floor(&self) -> Option<u32> {
+        self.min_rtt_loss_floor
+    }
 }
 
 /// Creates limits for a given connection
@@ -261,3 +496,177 @@ impl Limiter for Limits {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_rtt_bounds() {
+        let limits = Limits::new();
+        assert!(limits.with_initial_rtt(Duration::ZERO).is_err());
+        assert!(limits.with_initial_rtt(Duration::from_micros(999)).is_err());
+        assert!(limits
+            .with_initial_rtt(Duration::from_millis(1001))
+            .is_err());
+
+        for value in [K_GRANULARITY, Duration::from_millis(50), MAX_INITIAL_RTT] {
+            assert_eq!(limits.with_initial_rtt(value).unwrap().initial_rtt(), value);
+        }
+    }
+
+    #[test]
+    fn remembered_cwnd_bounds() {
+        let limits = Limits::new();
+        assert!(limits.with_remembered_cwnd(0).is_err());
+        assert!(limits
+            .with_remembered_cwnd(MIN_REMEMBERED_CONGESTION_WINDOW - 1)
+            .is_err());
+        assert!(limits
+            .with_remembered_cwnd(MAX_REMEMBERED_CONGESTION_WINDOW + 1)
+            .is_err());
+
+        for value in [
+            MIN_REMEMBERED_CONGESTION_WINDOW,
+            60_000,
+            MAX_REMEMBERED_CONGESTION_WINDOW,
+        ] {
+            assert_eq!(
+                limits
+                    .with_remembered_cwnd(value)
+                    .unwrap()
+                    .remembered_congestion_window(),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn resumption_state_bounds() {
+        let limits = Limits::new();
+        let state = ResumptionState {
+            congestion_window: 60_000,
+            slow_start_threshold: 80_000,
+            smoothed_rtt: Duration::from_millis(50),
+            rttvar: Duration::from_millis(10),
+        };
+
+        let resumed = limits.with_resumption_state(state).unwrap();
+        assert_eq!(resumed.initial_rtt(), state.smoothed_rtt);
+        assert_eq!(
+            resumed.remembered_congestion_window(),
+            Some(state.congestion_window)
+        );
+
+        assert!(limits
+            .with_resumption_state(ResumptionState {
+                smoothed_rtt: Duration::ZERO,
+                ..state
+            })
+            .is_err());
+        assert!(limits
+            .with_resumption_state(ResumptionState {
+                smoothed_rtt: MAX_INITIAL_RTT + Duration::from_millis(1),
+                ..state
+            })
+            .is_err());
+        assert!(limits
+            .with_resumption_state(ResumptionState {
+                congestion_window: MIN_REMEMBERED_CONGESTION_WINDOW - 1,
+                ..state
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn persistent_congestion_threshold_bounds() {
+        let limits = Limits::new();
+        assert!(limits.with_persistent_congestion_threshold(0).is_err());
+        assert_eq!(
+            limits
+                .with_persistent_congestion_threshold(5)
+                .unwrap()
+                .persistent_congestion_threshold(),
+            5
+        );
+    }
+
+    #[test]
+    fn timer_granularity_bounds() {
+        let limits = Limits::new();
+        assert!(limits.with_timer_granularity(Duration::ZERO).is_err());
+
+        let value = Duration::from_millis(5);
+        assert_eq!(
+            limits
+                .with_timer_granularity(value)
+                .unwrap()
+                .timer_granularity(),
+            value
+        );
+    }
+
+    #[test]
+    fn max_tracked_packets_bounds() {
+        let limits = Limits::new();
+        assert_eq!(limits.max_tracked_packets(), None);
+        assert!(limits.with_max_tracked_packets(0).is_err());
+        assert_eq!(
+            limits
+                .with_max_tracked_packets(1000)
+                .unwrap()
+                .max_tracked_packets(),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn time_threshold_bounds() {
+        let limits = Limits::new();
+        assert_eq!(limits.time_threshold(), None);
+        assert!(limits.with_time_threshold(9, 0).is_err());
+        assert!(limits.with_time_threshold(7, 8).is_err());
+
+        for (numerator, denominator) in [(1, 1), (9, 8), (5, 4)] {
+            assert_eq!(
+                limits
+                    .with_time_threshold(numerator, denominator)
+                    .unwrap()
+                    .time_threshold(),
+                Some((numerator, denominator))
+            );
+        }
+    }
+
+    #[test]
+    fn min_rtt_loss_floor_bounds() {
+        let limits = Limits::new();
+        assert_eq!(limits.min_rtt_loss_floor(), None);
+        assert!(limits.with_min_rtt_loss_floor(0).is_err());
+        assert_eq!(
+            limits
+                .with_min_rtt_loss_floor(2)
+                .unwrap()
+                .min_rtt_loss_floor(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn min_ack_delay_bounds() {
+        let limits = Limits::new();
+        assert_eq!(limits.min_ack_delay(), None);
+        assert!(limits
+            .with_min_ack_delay(Duration::from_millis(26))
+            .is_err());
+        assert!(limits.with_min_ack_delay(Duration::from_secs(17)).is_err());
+
+        let value = Duration::from_millis(5);
+        let limits = limits.with_min_ack_delay(value).unwrap();
+        assert_eq!(limits.min_ack_delay(), Some(value));
+
+        // the max ack delay can't be lowered below the min ack delay
+        assert!(limits.with_max_ack_delay(Duration::from_millis(4)).is_err());
+        assert!(limits.with_max_ack_delay(value).is_ok());
+    }
+}
@@ -168,6 +168,13 @@ pub mod api {
         HandshakeDone {},
         #[non_exhaustive]
         Datagram { len: u16 },
+        #[non_exhaustive]
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
@@ -437,6 +444,37 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The loss detection threshold that caused a packet to be declared lost"]
+    pub enum PacketLossReason {
+        #[non_exhaustive]
+        #[doc = " The packet was sent kPacketThreshold packets before an acknowledged packet"]
+        PacketThreshold {},
+        #[non_exhaustive]
+        #[doc = " The packet was sent long enough in the past, without exceeding the packet threshold"]
+        TimeThreshold {},
+        #[non_exhaustive]
+        #[doc = " The packet was the oldest tracked packet when the limit on tracked packets was exceeded"]
+        TrackedPacketLimit {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The reason a connection stopped transmitting on a path"]
+    pub enum TransmissionLimitCause {
+        #[non_exhaustive]
+        #[doc = " The congestion window does not have space for another packet"]
+        CongestionLimited {},
+        #[non_exhaustive]
+        #[doc = " The application does not have enough data to fill the congestion window"]
+        AppLimited {},
+        #[non_exhaustive]
+        #[doc = " The pacer has delayed transmission until a future departure time"]
+        PacingLimited {},
+        #[non_exhaustive]
+        #[doc = " The transmission queue does not have capacity for another packet"]
+        QueueFull {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[allow(non_camel_case_types)]
     pub enum CipherSuite {
         #[non_exhaustive]
@@ -479,6 +517,17 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The congestion controller phase responsible for an increase in the congestion window"]
+    pub enum CongestionWindowIncreaseCause {
+        #[non_exhaustive]
+        #[doc = " The window was increased by Slow Start"]
+        SlowStart {},
+        #[non_exhaustive]
+        #[doc = " The window was increased by Congestion Avoidance"]
+        CongestionAvoidance {},
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
         #[non_exhaustive]
@@ -577,6 +626,7 @@ pub mod api {
         pub path: Path<'a>,
         pub bytes_lost: u16,
         pub is_mtu_probe: bool,
+        pub reason: PacketLossReason,
     }
     impl<'a> Event for PacketLost<'a> {
         const NAME: &'static str = "recovery:packet_lost";
@@ -611,6 +661,58 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " Transmission on a path has stopped due to a limit"]
+    pub struct TransmissionLimited<'a> {
+        pub path: Path<'a>,
+        pub cause: TransmissionLimitCause,
+    }
+    impl<'a> Event for TransmissionLimited<'a> {
+        const NAME: &'static str = "recovery:transmission_limited";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The probe timeout (PTO) timer has been armed for a packet number space"]
+    pub struct PtoArmed<'a> {
+        pub path: Path<'a>,
+        pub space: KeySpace,
+        #[doc = " The PTO period the timer was armed with"]
+        #[doc = ""]
+        #[doc = " The timer expires once this much time has passed since the last ack-eliciting packet was"]
+        #[doc = " sent, or since the timer was armed if there are no ack-eliciting packets in flight."]
+        pub pto_period: Duration,
+    }
+    impl<'a> Event for PtoArmed<'a> {
+        const NAME: &'static str = "recovery:pto_armed";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The probe timeout (PTO) timer has expired for a packet number space"]
+    pub struct PtoExpired<'a> {
+        pub path: Path<'a>,
+        pub space: KeySpace,
+        #[doc = " The number of ack-eliciting probe packets that will be sent in response"]
+        pub probe_count: u8,
+    }
+    impl<'a> Event for PtoExpired<'a> {
+        const NAME: &'static str = "recovery:pto_expired";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " The congestion window changed significantly, and the receive buffer should be resized"]
+    pub struct ReceiveBufferAdvice<'a> {
+        pub path: Path<'a>,
+        #[doc = " The congestion window in bytes"]
+        pub congestion_window: u32,
+        #[doc = " The suggested receive buffer size in bytes"]
+        #[doc = ""]
+        #[doc = " This is twice the estimated bandwidth-delay product of the path."]
+        pub suggested_receive_buffer_size: u64,
+    }
+    impl<'a> Event for ReceiveBufferAdvice<'a> {
+        const NAME: &'static str = "recovery:receive_buffer_advice";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " Events related to ACK processing"]
     #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
     #[allow(deprecated)]
@@ -868,6 +970,17 @@ pub mod api {
     }
     #[derive(Clone, Debug)]
     #[non_exhaustive]
+    #[doc = " The congestion window has been increased in response to an acknowledgement"]
+    pub struct CongestionWindowIncreased<'a> {
+        pub path: Path<'a>,
+        pub cause: CongestionWindowIncreaseCause,
+        pub congestion_window: u32,
+    }
+    impl<'a> Event for CongestionWindowIncreased<'a> {
+        const NAME: &'static str = "recovery:congestion_window_increased";
+    }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -1364,6 +1477,16 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::AckFrequency {
+                sequence_number: self.sequence_number.as_u64(),
+                ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+                request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+                reordering_threshold: self.reordering_threshold.as_u64(),
+            }
+        }
+    }
     impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
         fn into_event(self) -> builder::StreamType {
             match self {
@@ -1372,6 +1495,15 @@ pub mod api {
             }
         }
     }
+    impl IntoEvent<builder::KeySpace> for crate::packet::number::PacketNumberSpace {
+        fn into_event(self) -> builder::KeySpace {
+            match self {
+                Self::Initial => builder::KeySpace::Initial {},
+                Self::Handshake => builder::KeySpace::Handshake {},
+                Self::ApplicationData => builder::KeySpace::OneRtt {},
+            }
+        }
+    }
     impl builder::PacketHeader {
         pub fn new(
             packet_number: crate::packet::number::PacketNumber,
@@ -1586,8 +1718,9 @@ pub mod tracing {
                 path,
                 bytes_lost,
                 is_mtu_probe,
+                reason,
             } = event;
-            tracing :: event ! (target : "packet_lost" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , bytes_lost = tracing :: field :: debug (bytes_lost) , is_mtu_probe = tracing :: field :: debug (is_mtu_probe));
+            tracing :: event ! (target : "packet_lost" , parent : id , tracing :: Level :: DEBUG , packet_header = tracing :: field :: debug (packet_header) , path = tracing :: field :: debug (path) , bytes_lost = tracing :: field :: debug (bytes_lost) , is_mtu_probe = tracing :: field :: debug (is_mtu_probe) , reason = tracing :: field :: debug (reason));
         }
         #[inline]
         fn on_recovery_metrics(
@@ -1623,6 +1756,62 @@ pub mod tracing {
             tracing :: event ! (target : "congestion" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , source = tracing :: field :: debug (source));
         }
         #[inline]
+        fn on_transmission_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::TransmissionLimited,
+        ) {
+            let id = context.id();
+            let api::TransmissionLimited { path, cause } = event;
+            tracing :: event ! (target : "transmission_limited" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , cause = tracing :: field :: debug (cause));
+        }
+        #[inline]
+        fn on_pto_armed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PtoArmed,
+        ) {
+            let id = context.id();
+            let api::PtoArmed {
+                path,
+                space,
+                pto_period,
+            } = event;
+            tracing :: event ! (target : "pto_armed" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , space = tracing :: field :: debug (space) , pto_period = tracing :: field :: debug (pto_period));
+        }
+        #[inline]
+        fn on_pto_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PtoExpired,
+        ) {
+            let id = context.id();
+            let api::PtoExpired {
+                path,
+                space,
+                probe_count,
+            } = event;
+            tracing :: event ! (target : "pto_expired" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , space = tracing :: field :: debug (space) , probe_count = tracing :: field :: debug (probe_count));
+        }
+        #[inline]
+        fn on_receive_buffer_advice(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ReceiveBufferAdvice,
+        ) {
+            let id = context.id();
+            let api::ReceiveBufferAdvice {
+                path,
+                congestion_window,
+                suggested_receive_buffer_size,
+            } = event;
+            tracing :: event ! (target : "receive_buffer_advice" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , congestion_window = tracing :: field :: debug (congestion_window) , suggested_receive_buffer_size = tracing :: field :: debug (suggested_receive_buffer_size));
+        }
+        #[inline]
         #[allow(deprecated)]
         fn on_ack_processed(
             &mut self,
@@ -1934,6 +2123,21 @@ pub mod tracing {
             tracing :: event ! (target : "slow_start_exited" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window));
         }
         #[inline]
+        fn on_congestion_window_increased(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::CongestionWindowIncreased,
+        ) {
+            let id = context.id();
+            let api::CongestionWindowIncreased {
+                path,
+                cause,
+                congestion_window,
+            } = event;
+            tracing :: event ! (target : "congestion_window_increased" , parent : id , tracing :: Level :: DEBUG , path = tracing :: field :: debug (path) , cause = tracing :: field :: debug (cause) , congestion_window = tracing :: field :: debug (congestion_window));
+        }
+        #[inline]
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -2419,6 +2623,12 @@ pub mod builder {
         Datagram {
             len: u16,
         },
+        AckFrequency {
+            sequence_number: u64,
+            ack_eliciting_threshold: u64,
+            request_max_ack_delay: u64,
+            reordering_threshold: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -2495,6 +2705,17 @@ pub mod builder {
                 Self::Datagram { len } => Datagram {
                     len: len.into_event(),
                 },
+                Self::AckFrequency {
+                    sequence_number,
+                    ack_eliciting_threshold,
+                    request_max_ack_delay,
+                    reordering_threshold,
+                } => AckFrequency {
+                    sequence_number: sequence_number.into_event(),
+                    ack_eliciting_threshold: ack_eliciting_threshold.into_event(),
+                    request_max_ack_delay: request_max_ack_delay.into_event(),
+                    reordering_threshold: reordering_threshold.into_event(),
+                },
             }
         }
     }
@@ -2919,6 +3140,51 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The loss detection threshold that caused a packet to be declared lost"]
+    pub enum PacketLossReason {
+        #[doc = " The packet was sent kPacketThreshold packets before an acknowledged packet"]
+        PacketThreshold,
+        #[doc = " The packet was sent long enough in the past, without exceeding the packet threshold"]
+        TimeThreshold,
+        #[doc = " The packet was the oldest tracked packet when the limit on tracked packets was exceeded"]
+        TrackedPacketLimit,
+    }
+    impl IntoEvent<api::PacketLossReason> for PacketLossReason {
+        #[inline]
+        fn into_event(self) -> api::PacketLossReason {
+            use api::PacketLossReason::*;
+            match self {
+                Self::PacketThreshold => PacketThreshold {},
+                Self::TimeThreshold => TimeThreshold {},
+                Self::TrackedPacketLimit => TrackedPacketLimit {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The reason a connection stopped transmitting on a path"]
+    pub enum TransmissionLimitCause {
+        #[doc = " The congestion window does not have space for another packet"]
+        CongestionLimited,
+        #[doc = " The application does not have enough data to fill the congestion window"]
+        AppLimited,
+        #[doc = " The pacer has delayed transmission until a future departure time"]
+        PacingLimited,
+        #[doc = " The transmission queue does not have capacity for another packet"]
+        QueueFull,
+    }
+    impl IntoEvent<api::TransmissionLimitCause> for TransmissionLimitCause {
+        #[inline]
+        fn into_event(self) -> api::TransmissionLimitCause {
+            use api::TransmissionLimitCause::*;
+            match self {
+                Self::CongestionLimited => CongestionLimited {},
+                Self::AppLimited => AppLimited {},
+                Self::PacingLimited => PacingLimited {},
+                Self::QueueFull => QueueFull {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[allow(non_camel_case_types)]
     pub enum CipherSuite {
         TLS_AES_128_GCM_SHA256,
@@ -2982,6 +3248,24 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The congestion controller phase responsible for an increase in the congestion window"]
+    pub enum CongestionWindowIncreaseCause {
+        #[doc = " The window was increased by Slow Start"]
+        SlowStart,
+        #[doc = " The window was increased by Congestion Avoidance"]
+        CongestionAvoidance,
+    }
+    impl IntoEvent<api::CongestionWindowIncreaseCause> for CongestionWindowIncreaseCause {
+        #[inline]
+        fn into_event(self) -> api::CongestionWindowIncreaseCause {
+            use api::CongestionWindowIncreaseCause::*;
+            match self {
+                Self::SlowStart => SlowStart {},
+                Self::CongestionAvoidance => CongestionAvoidance {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
         #[doc = " The MTU was initialized with the default value"]
@@ -3143,6 +3427,7 @@ pub mod builder {
         pub path: Path<'a>,
         pub bytes_lost: u16,
         pub is_mtu_probe: bool,
+        pub reason: PacketLossReason,
     }
     impl<'a> IntoEvent<api::PacketLost<'a>> for PacketLost<'a> {
         #[inline]
@@ -3152,12 +3437,14 @@ pub mod builder {
                 path,
                 bytes_lost,
                 is_mtu_probe,
+                reason,
             } = self;
             api::PacketLost {
                 packet_header: packet_header.into_event(),
                 path: path.into_event(),
                 bytes_lost: bytes_lost.into_event(),
                 is_mtu_probe: is_mtu_probe.into_event(),
+                reason: reason.into_event(),
             }
         }
     }
@@ -3221,6 +3508,97 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Transmission on a path has stopped due to a limit"]
+    pub struct TransmissionLimited<'a> {
+        pub path: Path<'a>,
+        pub cause: TransmissionLimitCause,
+    }
+    impl<'a> IntoEvent<api::TransmissionLimited<'a>> for TransmissionLimited<'a> {
+        #[inline]
+        fn into_event(self) -> api::TransmissionLimited<'a> {
+            let TransmissionLimited { path, cause } = self;
+            api::TransmissionLimited {
+                path: path.into_event(),
+                cause: cause.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The probe timeout (PTO) timer has been armed for a packet number space"]
+    pub struct PtoArmed<'a> {
+        pub path: Path<'a>,
+        pub space: KeySpace,
+        #[doc = " The PTO period the timer was armed with"]
+        #[doc = ""]
+        #[doc = " The timer expires once this much time has passed since the last ack-eliciting packet was"]
+        #[doc = " sent, or since the timer was armed if there are no ack-eliciting packets in flight."]
+        pub pto_period: Duration,
+    }
+    impl<'a> IntoEvent<api::PtoArmed<'a>> for PtoArmed<'a> {
+        #[inline]
+        fn into_event(self) -> api::PtoArmed<'a> {
+            let PtoArmed {
+                path,
+                space,
+                pto_period,
+            } = self;
+            api::PtoArmed {
+                path: path.into_event(),
+                space: space.into_event(),
+                pto_period: pto_period.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The probe timeout (PTO) timer has expired for a packet number space"]
+    pub struct PtoExpired<'a> {
+        pub path: Path<'a>,
+        pub space: KeySpace,
+        #[doc = " The number of ack-eliciting probe packets that will be sent in response"]
+        pub probe_count: u8,
+    }
+    impl<'a> IntoEvent<api::PtoExpired<'a>> for PtoExpired<'a> {
+        #[inline]
+        fn into_event(self) -> api::PtoExpired<'a> {
+            let PtoExpired {
+                path,
+                space,
+                probe_count,
+            } = self;
+            api::PtoExpired {
+                path: path.into_event(),
+                space: space.into_event(),
+                probe_count: probe_count.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The congestion window changed significantly, and the receive buffer should be resized"]
+    pub struct ReceiveBufferAdvice<'a> {
+        pub path: Path<'a>,
+        #[doc = " The congestion window in bytes"]
+        pub congestion_window: u32,
+        #[doc = " The suggested receive buffer size in bytes"]
+        #[doc = ""]
+        #[doc = " This is twice the estimated bandwidth-delay product of the path."]
+        pub suggested_receive_buffer_size: u64,
+    }
+    impl<'a> IntoEvent<api::ReceiveBufferAdvice<'a>> for ReceiveBufferAdvice<'a> {
+        #[inline]
+        fn into_event(self) -> api::ReceiveBufferAdvice<'a> {
+            let ReceiveBufferAdvice {
+                path,
+                congestion_window,
+                suggested_receive_buffer_size,
+            } = self;
+            api::ReceiveBufferAdvice {
+                path: path.into_event(),
+                congestion_window: congestion_window.into_event(),
+                suggested_receive_buffer_size: suggested_receive_buffer_size.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Events related to ACK processing"]
     pub struct AckProcessed<'a> {
         pub action: AckAction,
@@ -3657,6 +4035,28 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The congestion window has been increased in response to an acknowledgement"]
+    pub struct CongestionWindowIncreased<'a> {
+        pub path: Path<'a>,
+        pub cause: CongestionWindowIncreaseCause,
+        pub congestion_window: u32,
+    }
+    impl<'a> IntoEvent<api::CongestionWindowIncreased<'a>> for CongestionWindowIncreased<'a> {
+        #[inline]
+        fn into_event(self) -> api::CongestionWindowIncreased<'a> {
+            let CongestionWindowIncreased {
+                path,
+                cause,
+                congestion_window,
+            } = self;
+            api::CongestionWindowIncreased {
+                path: path.into_event(),
+                cause: cause.into_event(),
+                congestion_window: congestion_window.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
         pub server_versions: &'a [u32],
@@ -4228,6 +4628,54 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `TransmissionLimited` event is triggered"]
+        #[inline]
+        fn on_transmission_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TransmissionLimited,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PtoArmed` event is triggered"]
+        #[inline]
+        fn on_pto_armed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoArmed,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `PtoExpired` event is triggered"]
+        #[inline]
+        fn on_pto_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoExpired,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ReceiveBufferAdvice` event is triggered"]
+        #[inline]
+        fn on_receive_buffer_advice(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ReceiveBufferAdvice,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `AckProcessed` event is triggered"]
         #[inline]
         #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
@@ -4530,6 +4978,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `CongestionWindowIncreased` event is triggered"]
+        #[inline]
+        fn on_congestion_window_increased(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionWindowIncreased,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `VersionInformation` event is triggered"]
         #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
@@ -4851,6 +5311,46 @@ mod traits {
             (self.1).on_congestion(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_transmission_limited(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &TransmissionLimited,
+        ) {
+            (self.0).on_transmission_limited(&mut context.0, meta, event);
+            (self.1).on_transmission_limited(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_pto_armed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoArmed,
+        ) {
+            (self.0).on_pto_armed(&mut context.0, meta, event);
+            (self.1).on_pto_armed(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_pto_expired(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &PtoExpired,
+        ) {
+            (self.0).on_pto_expired(&mut context.0, meta, event);
+            (self.1).on_pto_expired(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_receive_buffer_advice(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &ReceiveBufferAdvice,
+        ) {
+            (self.0).on_receive_buffer_advice(&mut context.0, meta, event);
+            (self.1).on_receive_buffer_advice(&mut context.1, meta, event);
+        }
+        #[inline]
         #[allow(deprecated)]
         fn on_ack_processed(
             &mut self,
@@ -5102,6 +5602,16 @@ mod traits {
             (self.1).on_slow_start_exited(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_congestion_window_increased(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &ConnectionMeta,
+            event: &CongestionWindowIncreased,
+        ) {
+            (self.0).on_congestion_window_increased(&mut context.0, meta, event);
+            (self.1).on_congestion_window_increased(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_version_information(&mut self, meta: &EndpointMeta, event: &VersionInformation) {
             (self.0).on_version_information(meta, event);
             (self.1).on_version_information(meta, event);
@@ -5424,6 +5934,14 @@ mod traits {
         fn on_recovery_metrics(&mut self, event: builder::RecoveryMetrics);
         #[doc = "Publishes a `Congestion` event to the publisher's subscriber"]
         fn on_congestion(&mut self, event: builder::Congestion);
+        #[doc = "Publishes a `TransmissionLimited` event to the publisher's subscriber"]
+        fn on_transmission_limited(&mut self, event: builder::TransmissionLimited);
+        #[doc = "Publishes a `PtoArmed` event to the publisher's subscriber"]
+        fn on_pto_armed(&mut self, event: builder::PtoArmed);
+        #[doc = "Publishes a `PtoExpired` event to the publisher's subscriber"]
+        fn on_pto_expired(&mut self, event: builder::PtoExpired);
+        #[doc = "Publishes a `ReceiveBufferAdvice` event to the publisher's subscriber"]
+        fn on_receive_buffer_advice(&mut self, event: builder::ReceiveBufferAdvice);
         #[doc = "Publishes a `AckProcessed` event to the publisher's subscriber"]
         fn on_ack_processed(&mut self, event: builder::AckProcessed);
         #[doc = "Publishes a `RxAckRangeDropped` event to the publisher's subscriber"]
@@ -5474,6 +5992,8 @@ mod traits {
         fn on_mtu_updated(&mut self, event: builder::MtuUpdated);
         #[doc = "Publishes a `SlowStartExited` event to the publisher's subscriber"]
         fn on_slow_start_exited(&mut self, event: builder::SlowStartExited);
+        #[doc = "Publishes a `CongestionWindowIncreased` event to the publisher's subscriber"]
+        fn on_congestion_window_increased(&mut self, event: builder::CongestionWindowIncreased);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
         fn quic_version(&self) -> u32;
         #[doc = r" Returns the [`Subject`] for the current publisher"]
@@ -5613,6 +6133,42 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_transmission_limited(&mut self, event: builder::TransmissionLimited) {
+            let event = event.into_event();
+            self.subscriber
+                .on_transmission_limited(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_pto_armed(&mut self, event: builder::PtoArmed) {
+            let event = event.into_event();
+            self.subscriber
+                .on_pto_armed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_pto_expired(&mut self, event: builder::PtoExpired) {
+            let event = event.into_event();
+            self.subscriber
+                .on_pto_expired(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_receive_buffer_advice(&mut self, event: builder::ReceiveBufferAdvice) {
+            let event = event.into_event();
+            self.subscriber
+                .on_receive_buffer_advice(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         #[allow(deprecated)]
         fn on_ack_processed(&mut self, event: builder::AckProcessed) {
             let event = event.into_event();
@@ -5842,6 +6398,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_congestion_window_increased(&mut self, event: builder::CongestionWindowIncreased) {
+            let event = event.into_event();
+            self.subscriber
+                .on_congestion_window_increased(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> u32 {
             self.quic_version
         }
@@ -5869,6 +6434,10 @@ pub mod testing {
         pub packet_lost: u32,
         pub recovery_metrics: u32,
         pub congestion: u32,
+        pub transmission_limited: u32,
+        pub pto_armed: u32,
+        pub pto_expired: u32,
+        pub receive_buffer_advice: u32,
         pub ack_processed: u32,
         pub rx_ack_range_dropped: u32,
         pub ack_range_received: u32,
@@ -5894,6 +6463,7 @@ pub mod testing {
         pub keep_alive_timer_expired: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
+        pub congestion_window_increased: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -5943,6 +6513,10 @@ pub mod testing {
                 packet_lost: 0,
                 recovery_metrics: 0,
                 congestion: 0,
+                transmission_limited: 0,
+                pto_armed: 0,
+                pto_expired: 0,
+                receive_buffer_advice: 0,
                 ack_processed: 0,
                 rx_ack_range_dropped: 0,
                 ack_range_received: 0,
@@ -5968,6 +6542,7 @@ pub mod testing {
                 keep_alive_timer_expired: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
+                congestion_window_increased: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6114,6 +6689,50 @@ pub mod testing {
                 self.output.push(format!("{:?} {:?}", meta, event));
             }
         }
+        fn on_transmission_limited(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TransmissionLimited,
+        ) {
+            self.transmission_limited += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{:?} {:?}", meta, event));
+            }
+        }
+        fn on_pto_armed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PtoArmed,
+        ) {
+            self.pto_armed += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{:?} {:?}", meta, event));
+            }
+        }
+        fn on_pto_expired(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PtoExpired,
+        ) {
+            self.pto_expired += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{:?} {:?}", meta, event));
+            }
+        }
+        fn on_receive_buffer_advice(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ReceiveBufferAdvice,
+        ) {
+            self.receive_buffer_advice += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{:?} {:?}", meta, event));
+            }
+        }
         #[allow(deprecated)]
         fn on_ack_processed(
             &mut self,
@@ -6390,6 +7009,17 @@ pub mod testing {
                 self.output.push(format!("{:?} {:?}", meta, event));
             }
         }
+        fn on_congestion_window_increased(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CongestionWindowIncreased,
+        ) {
+            self.congestion_window_increased += 1;
+            if self.location.is_some() {
+                self.output.push(format!("{:?} {:?}", meta, event));
+            }
+        }
         fn on_version_information(
             &mut self,
             meta: &api::EndpointMeta,
@@ -6502,6 +7132,10 @@ pub mod testing {
         pub packet_lost: u32,
         pub recovery_metrics: u32,
         pub congestion: u32,
+        pub transmission_limited: u32,
+        pub pto_armed: u32,
+        pub pto_expired: u32,
+        pub receive_buffer_advice: u32,
         pub ack_processed: u32,
         pub rx_ack_range_dropped: u32,
         pub ack_range_received: u32,
@@ -6527,6 +7161,7 @@ pub mod testing {
         pub keep_alive_timer_expired: u32,
         pub mtu_updated: u32,
         pub slow_start_exited: u32,
+        pub congestion_window_increased: u32,
         pub version_information: u32,
         pub endpoint_packet_sent: u32,
         pub endpoint_packet_received: u32,
@@ -6566,6 +7201,10 @@ pub mod testing {
                 packet_lost: 0,
                 recovery_metrics: 0,
                 congestion: 0,
+                transmission_limited: 0,
+                pto_armed: 0,
+                pto_expired: 0,
+                receive_buffer_advice: 0,
                 ack_processed: 0,
                 rx_ack_range_dropped: 0,
                 ack_range_received: 0,
@@ -6591,6 +7230,7 @@ pub mod testing {
                 keep_alive_timer_expired: 0,
                 mtu_updated: 0,
                 slow_start_exited: 0,
+                congestion_window_increased: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
                 endpoint_packet_received: 0,
@@ -6767,6 +7407,34 @@ pub mod testing {
                 self.output.push(format!("{:?}", event));
             }
         }
+        fn on_transmission_limited(&mut self, event: builder::TransmissionLimited) {
+            self.transmission_limited += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{:?}", event));
+            }
+        }
+        fn on_pto_armed(&mut self, event: builder::PtoArmed) {
+            self.pto_armed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{:?}", event));
+            }
+        }
+        fn on_pto_expired(&mut self, event: builder::PtoExpired) {
+            self.pto_expired += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{:?}", event));
+            }
+        }
+        fn on_receive_buffer_advice(&mut self, event: builder::ReceiveBufferAdvice) {
+            self.receive_buffer_advice += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{:?}", event));
+            }
+        }
         #[allow(deprecated)]
         fn on_ack_processed(&mut self, event: builder::AckProcessed) {
             self.ack_processed += 1;
@@ -6946,6 +7614,13 @@ pub mod testing {
                 self.output.push(format!("{:?}", event));
             }
         }
+        fn on_congestion_window_increased(&mut self, event: builder::CongestionWindowIncreased) {
+            self.congestion_window_increased += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                self.output.push(format!("{:?}", event));
+            }
+        }
         fn quic_version(&self) -> u32 {
             1
         }
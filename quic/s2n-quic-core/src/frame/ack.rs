@@ -175,6 +175,42 @@ impl<A: AckRanges> EncoderValue for Ack<A> {
     }
 }
 
+/// Returns the encoded length of an ACK frame acknowledging `ack_ranges`, without ECN counts
+///
+/// The ranges must be provided in descending order, as they are written to the frame. This
+/// allows the size of the frame to be estimated before deciding whether it should be
+/// bundled in a packet.
+pub fn estimated_encoding_size<I>(ack_ranges: I, ack_delay: VarInt) -> usize
+where
+    I: IntoIterator<Item = RangeInclusive<VarInt>>,
+{
+    let mut iter = ack_ranges.into_iter();
+
+    let first_ack_range = iter.next().expect("at least one ack range is required");
+    let (mut smallest, largest_acknowledged) = first_ack_range.into_inner();
+
+    let mut len = ACK_TAG.encoding_size()
+        + largest_acknowledged.encoding_size()
+        + ack_delay.encoding_size()
+        + (largest_acknowledged - smallest).encoding_size();
+    let mut ack_range_count = 0usize;
+
+    for range in iter {
+        let (start, end) = range.into_inner();
+        let gap = smallest - end - 2;
+        let ack_range = end - start;
+        len += gap.encoding_size() + ack_range.encoding_size();
+        smallest = start;
+        ack_range_count += 1;
+    }
+
+    let ack_range_count: VarInt = ack_range_count
+        .try_into()
+        .expect("ack range count cannot exceed VarInt::MAX");
+
+    len + ack_range_count.encoding_size()
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-19.3.1
 //# Each ACK Range consists of alternating Gap and ACK Range Length
 //# values in descending packet number order.  ACK Ranges can be
@@ -539,7 +575,70 @@ impl EncoderValue for EcnCounts {
 
 #[cfg(test)]
 mod tests {
-    use crate::{frame::ack::EcnCounts, inet::ExplicitCongestionNotification};
+    use crate::{
+        frame::ack::{estimated_encoding_size, Ack, AckRanges, EcnCounts},
+        inet::ExplicitCongestionNotification,
+        varint::VarInt,
+    };
+    use core::ops::RangeInclusive;
+    use s2n_codec::EncoderValue;
+
+    #[derive(Clone, Copy)]
+    struct SliceRanges<'a>(&'a [RangeInclusive<VarInt>]);
+
+    impl<'a> AckRanges for SliceRanges<'a> {
+        type Iter = core::iter::Cloned<core::slice::Iter<'a, RangeInclusive<VarInt>>>;
+
+        fn ack_ranges(&self) -> Self::Iter {
+            self.0.iter().cloned()
+        }
+    }
+
+    fn encoding_size(ack_ranges: &[RangeInclusive<VarInt>], ack_delay: VarInt) -> usize {
+        Ack {
+            ack_delay,
+            ack_ranges: SliceRanges(ack_ranges),
+            ecn_counts: None,
+        }
+        .encoding_size()
+    }
+
+    #[test]
+    fn estimated_encoding_size_single_range() {
+        let ack_ranges = [VarInt::from_u8(0)..=VarInt::from_u8(10)];
+        let ack_delay = VarInt::from_u8(0);
+
+        // tag + largest acknowledged + ack delay + range count + first ack range
+        assert_eq!(
+            estimated_encoding_size(ack_ranges.iter().cloned(), ack_delay),
+            5
+        );
+        assert_eq!(
+            estimated_encoding_size(ack_ranges.iter().cloned(), ack_delay),
+            encoding_size(&ack_ranges, ack_delay)
+        );
+    }
+
+    #[test]
+    fn estimated_encoding_size_large_gaps() {
+        let ack_ranges = [
+            VarInt::from_u32(2_000_000_000)..=VarInt::from_u32(2_000_000_010),
+            VarInt::from_u32(1_000_000)..=VarInt::from_u32(1_000_100),
+            VarInt::from_u8(0)..=VarInt::from_u8(1),
+        ];
+        let ack_delay = VarInt::from_u16(1000);
+
+        // tag (1) + largest acknowledged (8) + ack delay (2) + range count (1) +
+        // first ack range (1) + gap (8) + ack range (2) + gap (4) + ack range (1)
+        assert_eq!(
+            estimated_encoding_size(ack_ranges.iter().cloned(), ack_delay),
+            28
+        );
+        assert_eq!(
+            estimated_encoding_size(ack_ranges.iter().cloned(), ack_delay),
+            encoding_size(&ack_ranges, ack_delay)
+        );
+    }
 
     #[test]
     fn as_option() {
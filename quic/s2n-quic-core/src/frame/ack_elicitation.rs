@@ -64,6 +64,9 @@ impl<AckRanges> AckElicitable for crate::frame::Ack<AckRanges> {
         AckElicitation::NonEliciting
     }
 }
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+// ACK_FREQUENCY frames are ack-eliciting.
+impl AckElicitable for crate::frame::AckFrequency {}
 impl AckElicitable for crate::frame::ConnectionClose<'_> {
     #[inline]
     fn ack_elicitation(&self) -> AckElicitation {
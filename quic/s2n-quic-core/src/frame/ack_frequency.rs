@@ -0,0 +1,151 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::Tag, varint::VarInt};
+use core::{convert::TryInto, time::Duration};
+use s2n_codec::{decoder_value, Encoder, EncoderValue};
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+// Delaying acknowledgements as much as possible reduces both work done
+// by the endpoints and network load.  An endpoint's loss detection and
+// congestion control mechanisms however need to be tolerant of this
+// delay at the peer.  An endpoint signals its tolerance to its peer
+// using an ACK_FREQUENCY frame, shown below:
+
+macro_rules! ack_frequency_tag {
+    () => {
+        0xafu64
+    };
+}
+
+const ACK_FREQUENCY_TAG: VarInt = VarInt::from_u8(ack_frequency_tag!() as u8);
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+// ACK_FREQUENCY Frame {
+//   Type (i) = 0xaf,
+//   Sequence Number (i),
+//   Ack-Eliciting Threshold (i),
+//   Request Max Ack Delay (i),
+//   Reordering Threshold (i),
+// }
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+// Following the common frame format described in Section 12.4 of
+// [QUIC-TRANSPORT], ACK_FREQUENCY frames have a type of 0xaf, and
+// contain the following fields:
+//
+// Sequence Number:  A variable-length integer representing the sequence
+//    number assigned to the ACK_FREQUENCY frame by the sender to allow
+//    receivers to ignore obsolete frames.
+//
+// Ack-Eliciting Threshold:  A variable-length integer representing the
+//    maximum number of ack-eliciting packets the recipient of this frame
+//    receives before sending an acknowledgment.
+//
+// Request Max Ack Delay:  A variable-length integer representing an
+//    update to the peer's max_ack_delay transport parameter in
+//    microseconds.
+//
+// Reordering Threshold:  A variable-length integer that indicates the
+//    maximum packet reordering before eliciting an immediate ACK.
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AckFrequency {
+    /// A variable-length integer representing the sequence number assigned
+    /// to the ACK_FREQUENCY frame by the sender
+    pub sequence_number: VarInt,
+
+    /// A variable-length integer representing the maximum number of ack-eliciting
+    /// packets the recipient of this frame receives before sending an acknowledgment
+    pub ack_eliciting_threshold: VarInt,
+
+    /// A variable-length integer representing an update to the peer's
+    /// `max_ack_delay` transport parameter in microseconds
+    pub request_max_ack_delay: VarInt,
+
+    /// A variable-length integer that indicates the maximum packet reordering
+    /// before eliciting an immediate ACK
+    pub reordering_threshold: VarInt,
+}
+
+impl AckFrequency {
+    pub const fn tag(&self) -> Tag {
+        ack_frequency_tag!() as Tag
+    }
+
+    /// Returns the requested `max_ack_delay` as a `Duration`
+    #[inline]
+    pub fn request_max_ack_delay(&self) -> Duration {
+        Duration::from_micros(self.request_max_ack_delay.as_u64())
+    }
+
+    /// Encodes the given `max_ack_delay` into the Request Max Ack Delay field
+    #[inline]
+    pub fn encode_max_ack_delay(max_ack_delay: Duration) -> VarInt {
+        let micros: u64 = max_ack_delay.as_micros().try_into().unwrap_or(u64::MAX);
+        VarInt::new(micros).unwrap_or(VarInt::MAX)
+    }
+}
+
+decoder_value!(
+    impl<'a> AckFrequency {
+        fn decode(buffer: Buffer) -> Result<Self> {
+            let (sequence_number, buffer) = buffer.decode()?;
+            let (ack_eliciting_threshold, buffer) = buffer.decode()?;
+            let (request_max_ack_delay, buffer) = buffer.decode()?;
+            let (reordering_threshold, buffer) = buffer.decode()?;
+
+            let frame = AckFrequency {
+                sequence_number,
+                ack_eliciting_threshold,
+                request_max_ack_delay,
+                reordering_threshold,
+            };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for AckFrequency {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&ACK_FREQUENCY_TAG);
+        buffer.encode(&self.sequence_number);
+        buffer.encode(&self.ack_eliciting_threshold);
+        buffer.encode(&self.request_max_ack_delay);
+        buffer.encode(&self.reordering_threshold);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{Frame, FrameMut};
+    use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+
+    #[test]
+    fn round_trip() {
+        let frame = AckFrequency {
+            sequence_number: VarInt::from_u8(3),
+            ack_eliciting_threshold: VarInt::from_u8(10),
+            request_max_ack_delay: AckFrequency::encode_max_ack_delay(Duration::from_millis(25)),
+            reordering_threshold: VarInt::from_u8(1),
+        };
+        assert_eq!(frame.request_max_ack_delay(), Duration::from_millis(25));
+
+        let mut buffer = [0u8; 32];
+        let mut encoder = EncoderBuffer::new(&mut buffer);
+        encoder.encode(&frame);
+        let len = encoder.len();
+
+        // The frame type is encoded as a two byte variable-length integer
+        assert_eq!(&buffer[..2], &[0x40, 0xaf]);
+
+        let (decoded, remaining) = DecoderBufferMut::new(&mut buffer[..len])
+            .decode::<FrameMut>()
+            .unwrap();
+        assert!(remaining.is_empty());
+        assert_eq!(decoded, Frame::AckFrequency(frame));
+    }
+}
@@ -19,6 +19,7 @@ impl<AckRanges> CongestionControlled for crate::frame::Ack<AckRanges> {
         false
     }
 }
+impl CongestionControlled for crate::frame::AckFrequency {}
 impl CongestionControlled for crate::frame::ConnectionClose<'_> {}
 impl<Data> CongestionControlled for crate::frame::Crypto<Data> {}
 //= https://www.rfc-editor.org/rfc/rfc9221#section-5.4
@@ -33,13 +33,20 @@ pub trait FrameTrait: AckElicitable + CongestionControlled + path_validation::Pr
 impl<T: AckElicitable + CongestionControlled + path_validation::Probing> FrameTrait for T {}
 
 macro_rules! frames {
-    ($ack:ident, $data:ident | $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)*) => {
+    ($ack:ident, $data:ident | $($tag_macro:ident => $module:ident, $handler:ident, $ty:ident $([$($generics:tt)+])?;)*
+        | $($ext_tag_macro:ident => $ext_module:ident, $ext_handler:ident, $ext_ty:ident;)*) => {
         $(
             #[macro_use]
             pub mod $module;
             pub use $module::$ty;
         )*
 
+        $(
+            #[macro_use]
+            pub mod $ext_module;
+            pub use $ext_module::$ext_ty;
+        )*
+
         pub type RemainingBuffer<'a> = Option<DecoderBufferMut<'a>>;
 
         #[derive(Debug, PartialEq, Eq)]
@@ -47,6 +54,9 @@ macro_rules! frames {
             $(
                 $ty($module::$ty $(<$($generics)*>)?),
             )*
+            $(
+                $ext_ty($ext_module::$ext_ty),
+            )*
         }
 
         impl<'a, $ack, $data> Frame<'a, $ack, $data> {
@@ -56,6 +66,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.tag(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.tag(),
+                    )*
                 }
             }
         }
@@ -71,6 +84,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(inner) => inner.into_event(),
                     )*
+                    $(
+                        Frame::$ext_ty(inner) => inner.into_event(),
+                    )*
                 }
             }
         }
@@ -82,6 +98,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.ack_elicitation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.ack_elicitation(),
+                    )*
                 }
             }
         }
@@ -93,6 +112,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => frame.path_validation(),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => frame.path_validation(),
+                    )*
                 }
             }
         }
@@ -106,6 +128,15 @@ macro_rules! frames {
             }
         )*
 
+        $(
+            impl<'a, $ack, $data> From<$ext_module::$ext_ty> for Frame<'a, $ack, $data> {
+                #[inline]
+                fn from(v: $ext_module::$ext_ty)  -> Frame<'a, $ack, $data> {
+                    Frame::$ext_ty(v)
+                }
+            }
+        )*
+
         impl<'a, $ack, $data: DecoderValueMut<'a>> DecoderValueMut<'a> for Frame<'a, $ack, $data>
         where ack::Ack<$ack>: DecoderParameterizedValueMut<'a, Parameter = Tag> {
             #[inline]
@@ -121,6 +152,9 @@ macro_rules! frames {
                     $(
                         Frame::$ty(frame) => buffer.encode(frame),
                     )*
+                    $(
+                        Frame::$ext_ty(frame) => buffer.encode(frame),
+                    )*
                 }
             }
         }
@@ -137,6 +171,13 @@ macro_rules! frames {
                     Ok(Frame::$ty(frame))
                 }
             )*
+
+            $(
+                #[inline]
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty) -> Result<Self::Output, DecoderError> {
+                    Ok(Frame::$ext_ty(frame))
+                }
+            )*
         }
 
         pub trait FrameDecoder<'a, $ack, $data: DecoderValueMut<'a>>
@@ -147,6 +188,10 @@ macro_rules! frames {
                 fn $handler(&mut self, frame: $module::$ty $(<$($generics)*>)?) -> Result<Self::Output, DecoderError>;
             )*
 
+            $(
+                fn $ext_handler(&mut self, frame: $ext_module::$ext_ty) -> Result<Self::Output, DecoderError>;
+            )*
+
             fn handle_extension_frame(&mut self, buffer: DecoderBufferMut<'a>) -> DecoderBufferMutResult<'a, Self::Output> {
                 let _ = buffer;
 
@@ -162,7 +207,7 @@ macro_rules! frames {
                 match tag {
                     // Make sure the single byte frame tags fit into a small variable-integer
                     // otherwise fallback to extension selection
-                    0b0100_0000..=0xff => self.handle_extension_frame(buffer),
+                    0b0100_0000..=0xff => self.decode_extension_frame(buffer),
                     $(
                         $tag_macro!() => {
                             let buffer = buffer.skip(core::mem::size_of::<Tag>())?;
@@ -174,6 +219,30 @@ macro_rules! frames {
                     _ => self.handle_extension_frame(buffer),
                 }
             }
+
+            /// Decodes frames with a tag that requires a multi-byte variable-length integer
+            #[inline]
+            fn decode_extension_frame(
+                &mut self,
+                buffer: DecoderBufferMut<'a>,
+            ) -> DecoderBufferMutResult<'a, Self::Output> {
+                // variable-length integers are at most 8 bytes
+                let peek_len = buffer.len().min(8);
+                let (tag, _) = buffer
+                    .peek_range(0..peek_len)?
+                    .decode::<crate::varint::VarInt>()?;
+                match tag.as_u64() {
+                    $(
+                        $ext_tag_macro!() => {
+                            let buffer = buffer.skip(tag.encoding_size())?;
+                            let (frame, buffer) = buffer.decode()?;
+                            let output = self.$ext_handler(frame)?;
+                            Ok((output, buffer))
+                        },
+                    )*
+                    _ => self.handle_extension_frame(buffer),
+                }
+            }
         }
 
         #[cfg(test)]
@@ -191,6 +260,17 @@ macro_rules! frames {
                     ));
                 }
             )*
+
+            $(
+                #[test]
+                fn $ext_module() {
+                    assert_codec_round_trip_sample_file!(FrameMut, concat!(
+                        "src/frame/test_samples/",
+                        stringify!($ext_module),
+                        ".bin"
+                    ));
+                }
+            )*
         }
     };
 }
@@ -252,6 +332,8 @@ frames! {
     connection_close_tag => connection_close, handle_connection_close_frame, ConnectionClose['a];
     handshake_done_tag => handshake_done, handle_handshake_done_frame, HandshakeDone;
     datagram_tag => datagram, handle_datagram_frame, Datagram[Data];
+    |
+    ack_frequency_tag => ack_frequency, handle_ack_frequency_frame, AckFrequency;
 }
 
 #[derive(Clone, Copy, Debug, Default)]
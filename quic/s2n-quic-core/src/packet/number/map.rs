@@ -52,6 +52,8 @@ pub struct Map<V> {
     ///
     /// This field will be set to the `packets.len()` if the map is empty
     index: usize,
+    /// The number of occupied entries between `start` and `end`
+    len: usize,
 }
 
 impl<V: fmt::Debug> fmt::Debug for Map<V> {
@@ -85,6 +87,7 @@ impl<V> Default for Map<V> {
             start: base,
             end: base,
             index,
+            len: 0,
         }
     }
 }
@@ -97,9 +100,17 @@ impl<V> Map<V> {
             self.end = packet_number;
             self.values[0] = Some(value);
             self.index = 0;
+            self.len = 1;
             return;
         }
 
+        // A packet number that is already present indicates it was sent more than once
+        debug_assert!(
+            self.get(packet_number).is_none(),
+            "packet number {:?} was already inserted",
+            packet_number
+        );
+
         // The implementation assumes monotonicity of insertion
         debug_assert!(
             packet_number > self.start && packet_number > self.end,
@@ -123,6 +134,7 @@ impl<V> Map<V> {
 
         self.values[index] = Some(value);
         self.end = packet_number;
+        self.len += 1;
     }
 
     /// Inserts the given `value` into the map or updates the existing entry
@@ -137,6 +149,7 @@ impl<V> Map<V> {
             self.end = packet_number;
             self.values[0] = Some(value);
             self.index = 0;
+            self.len = 1;
             return;
         }
 
@@ -166,6 +179,7 @@ impl<V> Map<V> {
             update(prev);
         } else {
             *entry = Some(value);
+            self.len += 1;
         }
 
         self.end = self.end.max(packet_number);
@@ -178,11 +192,19 @@ impl<V> Map<V> {
         self.values[index].as_ref()
     }
 
+    /// Returns a mutable reference to the `V` associated with the given `packet_number`
+    #[inline]
+    pub fn get_mut(&mut self, packet_number: PacketNumber) -> Option<&mut V> {
+        let index = self.pn_index(packet_number)?;
+        self.values[index].as_mut()
+    }
+
     /// Removes the value associated with the given `packet_number`
     /// and returns the value if it was present
     pub fn remove(&mut self, packet_number: PacketNumber) -> Option<V> {
         let index = self.pn_index(packet_number)?;
         let info = self.values[index].take()?;
+        self.len -= 1;
 
         // update the bounds
         match (self.start == packet_number, self.end == packet_number) {
@@ -190,7 +212,7 @@ impl<V> Map<V> {
             //              [_, _, _, 3]
             // remove(3) => [_, _, _, _]
             (true, true) => {
-                self.clear();
+                self.reset();
             }
             // the packet was removed from the front
             //              [0, 1, _, 3, 4]
@@ -220,11 +242,43 @@ impl<V> Map<V> {
     }
 
     /// Removes a range of packets from the map and returns their value
+    ///
+    /// [`PacketNumberRange`] guarantees that `range.start() <= range.end()`, so a
+    /// reversed range can't be passed here.
     #[inline]
     pub fn remove_range(&mut self, range: PacketNumberRange) -> RemoveIter<V> {
         RemoveIter::new(self, range)
     }
 
+    /// Returns an iterator over mutable references to the values in the given `range`
+    ///
+    /// This allows updating a contiguous span of entries in a single traversal, rather
+    /// than looking up each packet number with [`Self::get_mut`].
+    #[inline]
+    pub fn range_mut(&mut self, range: PacketNumberRange) -> RangeMut<V> {
+        RangeMut::new(self, range)
+    }
+
+    /// Splits the map into two at the given `packet_number`
+    ///
+    /// Returns a new map containing all of the entries greater than or equal to
+    /// `packet_number`, leaving the lower entries in `self`.
+    pub fn split_off(&mut self, packet_number: PacketNumber) -> Self {
+        let mut other = Self::default();
+
+        if self.is_empty() || packet_number > self.end {
+            return other;
+        }
+
+        let start = packet_number.max(self.start);
+        let end = self.end;
+        for (packet_number, value) in self.remove_range(PacketNumberRange::new(start, end)) {
+            other.insert(packet_number, value);
+        }
+
+        other
+    }
+
     /// Get the inclusive PacketNumberRange
     #[inline]
     pub fn get_range(&self) -> PacketNumberRange {
@@ -243,9 +297,38 @@ impl<V> Map<V> {
         self.index == self.values.len()
     }
 
-    /// Clears all of the packet information in the sent
+    /// Returns the number of entries
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Removes all of the entries
+    ///
+    /// The next inserted packet number becomes the new lower bound of the map, regardless
+    /// of the packet numbers that were previously contained.
     #[inline]
     pub fn clear(&mut self) {
+        if !self.is_empty() {
+            // drop the values in the occupied span so they aren't visible after the
+            // next insertion
+            let len = self.values.len();
+            let span = (self.end.as_u64() - self.start.as_u64()) as usize;
+            for offset in 0..=span {
+                self.values[(self.index + offset) % len] = None;
+            }
+        }
+
+        self.reset();
+        self.len = 0;
+    }
+
+    /// Marks the map as empty without dropping any of the stored values
+    ///
+    /// The caller must ensure the occupied slots have been, or are about to be, vacated.
+    /// `len` is not updated, since it is decremented as each of the values is taken.
+    #[inline]
+    fn reset(&mut self) {
         self.index = self.values.len();
     }
 
@@ -403,6 +486,84 @@ impl<'a, V> Iterator for Iter<'a, V> {
     }
 }
 
+/// An iterator over mutable references to the values in a range of packet numbers
+#[derive(Debug)]
+pub struct RangeMut<'a, V> {
+    /// The slots from the first packet in the range to the end of the buffer
+    head: &'a mut [Option<V>],
+    /// The slots wrapped around to the front of the buffer
+    tail: &'a mut [Option<V>],
+    packet_number: Option<PacketNumber>,
+}
+
+impl<'a, V> RangeMut<'a, V> {
+    #[inline]
+    fn new(packets: &'a mut Map<V>, range: PacketNumberRange) -> Self {
+        let mut iter = Self {
+            head: &mut [],
+            tail: &mut [],
+            packet_number: None,
+        };
+
+        // make sure we have at least one packet
+        if packets.is_empty() {
+            return iter;
+        }
+
+        // ensure the range overlaps with the contained items
+        if range.end() < packets.start || range.start() > packets.end {
+            return iter;
+        }
+
+        let start = range.start().max(packets.start);
+        let end = range.end().min(packets.end);
+
+        let index = packets
+            .pn_index(start)
+            .expect("packet number bounds have already been checked");
+        // we always have at least 1 items since the range is inclusive
+        let count = (end.as_u64() - start.as_u64()) as usize + 1;
+
+        debug_assert!(count <= packets.values.len());
+
+        let (front, back) = packets.values.split_at_mut(index);
+        if count <= back.len() {
+            iter.head = &mut back[..count];
+        } else {
+            let wrapped = count - back.len();
+            iter.head = back;
+            iter.tail = &mut front[..wrapped];
+        }
+        iter.packet_number = Some(start);
+
+        iter
+    }
+}
+
+impl<'a, V> Iterator for RangeMut<'a, V> {
+    type Item = (PacketNumber, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let slots = if self.head.is_empty() {
+                core::mem::take(&mut self.tail)
+            } else {
+                core::mem::take(&mut self.head)
+            };
+            let (slot, rest) = slots.split_first_mut()?;
+            self.head = rest;
+
+            let packet_number = self.packet_number?;
+            self.packet_number = packet_number.next();
+
+            if let Some(value) = slot.as_mut() {
+                return Some((packet_number, value));
+            }
+        }
+    }
+}
+
 /// An iterator which removes a set of packet numbers in a range
 ///
 /// This iterator is optimized to reduce the amount of bounds checks being performed
@@ -448,8 +609,9 @@ impl<'a, V> RemoveIter<'a, V> {
 
                 // clear the sent packets
                 //
-                // NOTE: this doesn't actually delete anything in the buffer
-                iter.packets.clear();
+                // NOTE: this doesn't actually delete anything in the buffer, since the
+                // iterator takes each of the values as it goes
+                iter.packets.reset();
 
                 // no need to update index as it's already set to the lower bound
             }
@@ -510,6 +672,7 @@ impl<'a, V> Iterator for RemoveIter<'a, V> {
             self.index = (index + 1) % self.packets.values.len();
 
             if let Some(info) = self.packets.values[index].take() {
+                self.packets.len -= 1;
                 return Some((packet_number, info));
             }
         }
@@ -592,6 +755,19 @@ mod tests {
         assert!(!sent_packets.is_empty());
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "was already inserted")]
+    fn duplicate_insert() {
+        let mut sent_packets = TestMap::default();
+        let packet_number_1 = PacketNumberSpace::Initial.new_packet_number(VarInt::from_u8(1));
+        let packet_number_2 = packet_number_1.next().unwrap();
+
+        sent_packets.insert(packet_number_1, 1);
+        sent_packets.insert(packet_number_2, 2);
+        sent_packets.insert(packet_number_2, 3);
+    }
+
     #[test]
     #[should_panic]
     fn wrong_packet_space_on_insert() {
@@ -612,6 +788,16 @@ mod tests {
         sent_packets.get(packet_number);
     }
 
+    #[test]
+    #[should_panic]
+    fn wrong_packet_space_on_get_mut() {
+        let mut sent_packets = new_sent_packets(PacketNumberSpace::Initial);
+
+        let packet_number =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        sent_packets.get_mut(packet_number);
+    }
+
     #[test]
     #[should_panic]
     fn wrong_packet_space_on_remove_range() {
@@ -629,6 +815,23 @@ mod tests {
             .for_each(|_| ());
     }
 
+    #[test]
+    #[should_panic]
+    fn wrong_packet_space_on_range_mut() {
+        let mut sent_packets = new_sent_packets(PacketNumberSpace::Initial);
+
+        let packet_number_start =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        let packet_number_end =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(2));
+        sent_packets
+            .range_mut(PacketNumberRange::new(
+                packet_number_start,
+                packet_number_end,
+            ))
+            .for_each(|_| ());
+    }
+
     #[test]
     #[should_panic]
     fn wrong_packet_space_on_remove() {
@@ -639,6 +842,119 @@ mod tests {
         sent_packets.remove(packet_number);
     }
 
+    #[test]
+    #[should_panic]
+    fn wrong_packet_space_on_split_off() {
+        let mut sent_packets = new_sent_packets(PacketNumberSpace::Initial);
+
+        let packet_number =
+            PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1));
+        sent_packets.split_off(packet_number);
+    }
+
+    #[test]
+    fn split_off() {
+        let space = PacketNumberSpace::ApplicationData;
+        let pn = |packet_number: u8| space.new_packet_number(VarInt::from_u8(packet_number));
+        let sum = |map: &TestMap| map.iter().map(|(_, value)| *value).sum::<u64>();
+        let keys = |map: &TestMap| map.iter().map(|(pn, _)| pn).collect::<Vec<_>>();
+
+        let populated = || {
+            let mut sent_packets = TestMap::default();
+            // Insert enough entries to wrap around the ring, leaving gaps at 13 and 16
+            for packet_number in 10u8..=20 {
+                sent_packets.insert(pn(packet_number), packet_number as u64);
+            }
+            sent_packets.remove(pn(10));
+            sent_packets.remove(pn(13));
+            sent_packets.remove(pn(16));
+            sent_packets
+        };
+
+        let mut lower = populated();
+        let upper = lower.split_off(pn(15));
+        assert_eq!(keys(&lower), [pn(11), pn(12), pn(14)]);
+        assert_eq!(keys(&upper), [pn(15), pn(17), pn(18), pn(19), pn(20)]);
+        assert_eq!(sum(&lower), 11 + 12 + 14);
+        assert_eq!(sum(&upper), 15 + 17 + 18 + 19 + 20);
+        assert_eq!(sum(&lower) + sum(&upper), sum(&populated()));
+        assert!(upper.get_range() == PacketNumberRange::new(pn(15), pn(20)));
+
+        // Splitting at a gap starts the new map at the next entry
+        let mut lower = populated();
+        let upper = lower.split_off(pn(16));
+        assert_eq!(keys(&lower), [pn(11), pn(12), pn(14), pn(15)]);
+        assert!(upper.get_range() == PacketNumberRange::new(pn(17), pn(20)));
+
+        // Splitting below the start moves every entry
+        let mut lower = populated();
+        let upper = lower.split_off(pn(1));
+        assert!(lower.is_empty());
+        assert_eq!(keys(&upper), keys(&populated()));
+
+        // Splitting above the end moves nothing
+        let mut lower = populated();
+        let upper = lower.split_off(pn(21));
+        assert!(upper.is_empty());
+        assert_eq!(keys(&lower), keys(&populated()));
+
+        // Both halves continue to accept new packets
+        let mut lower = populated();
+        let mut upper = lower.split_off(pn(15));
+        upper.insert(pn(21), 21);
+        assert_eq!(upper.get(pn(21)), Some(&21));
+        lower.remove(pn(11));
+        assert_eq!(keys(&lower), [pn(12), pn(14)]);
+
+        // Splitting an empty map returns an empty map
+        assert!(TestMap::default().split_off(pn(1)).is_empty());
+    }
+
+    #[test]
+    fn range_mut() {
+        let pn = |pn: u8| PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(pn));
+        let mut map = TestMap::default();
+
+        for packet_number in 0..8 {
+            map.insert(pn(packet_number), packet_number as u64);
+        }
+        // Remove packets from the front and insert more so the entries wrap around the buffer
+        map.remove_range(PacketNumberRange::new(pn(0), pn(5)))
+            .for_each(|_| ());
+        for packet_number in 8..12 {
+            map.insert(pn(packet_number), packet_number as u64);
+        }
+        map.remove(pn(9));
+
+        for (_, value) in map.range_mut(PacketNumberRange::new(pn(7), pn(10))) {
+            *value *= 100;
+        }
+
+        let entries: Vec<_> = map
+            .iter()
+            .map(|(packet_number, value)| (packet_number, *value))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (pn(6), 6),
+                (pn(7), 700),
+                (pn(8), 800),
+                (pn(10), 1000),
+                (pn(11), 11)
+            ]
+        );
+
+        // The full range yields the same entries as `iter`
+        let keys: Vec<_> = map
+            .range_mut(map.get_range())
+            .map(|(packet_number, _)| packet_number)
+            .collect();
+        assert!(keys
+            .into_iter()
+            .eq(map.iter().map(|(packet_number, _)| packet_number)));
+    }
+
     fn new_sent_packets(space: PacketNumberSpace) -> TestMap {
         let mut sent_packets = TestMap::default();
         let packet_number = space.new_packet_number(VarInt::from_u8(0));
@@ -715,6 +1031,9 @@ mod tests {
             }
 
             fn check_consistency(&self) {
+                assert_eq!(self.subject.len(), self.oracle.len());
+                assert_eq!(self.subject.is_empty(), self.oracle.is_empty());
+
                 let mut subject = self.subject.iter();
                 let mut oracle = self.oracle.iter();
                 loop {
@@ -181,6 +181,21 @@ impl PacketNumber {
         self.space().assert_eq(rhs.space());
         Self::as_u64(self).checked_sub(Self::as_u64(rhs))
     }
+
+    /// Computes the unsigned distance between this packet number and the given packet number,
+    /// regardless of which one is larger.
+    ///
+    /// Both packet numbers must be in the same space.
+    #[inline]
+    pub fn distance(self, other: PacketNumber) -> u64 {
+        self.space().assert_eq(other.space());
+        let (a, b) = (Self::as_u64(self), Self::as_u64(other));
+        if a > b {
+            a - b
+        } else {
+            b - a
+        }
+    }
 }
 
 #[cfg(test)]
@@ -227,6 +242,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn distance() {
+        let space = PacketNumberSpace::ApplicationData;
+        let a = space.new_packet_number(VarInt::from_u8(3));
+        let b = space.new_packet_number(VarInt::from_u32(1000));
+
+        assert_eq!(b.distance(a), 997);
+        assert_eq!(a.distance(b), 997);
+        assert_eq!(a.distance(a), 0);
+
+        let max = space.new_packet_number(VarInt::MAX);
+        let zero = space.new_packet_number(VarInt::from_u8(0));
+        assert_eq!(max.distance(zero), VarInt::MAX.as_u64());
+        assert_eq!(zero.distance(max), VarInt::MAX.as_u64());
+    }
+
+    #[test]
+    #[should_panic]
+    fn distance_wrong_packet_number_space() {
+        PacketNumberSpace::ApplicationData
+            .new_packet_number(VarInt::from_u8(0))
+            .distance(PacketNumberSpace::Handshake.new_packet_number(VarInt::from_u8(0)));
+    }
+
     #[test]
     #[should_panic]
     fn wrong_packet_number_space() {
@@ -281,9 +281,11 @@ impl CongestionController for BbrCongestionController {
         random_generator: &mut Rnd,
         ack_receive_time: Timestamp,
     ) {
+        // An accounting error or misbehaving peer may acknowledge bytes that were never
+        // counted as in flight, so bytes in flight saturates at zero rather than underflowing
         self.bytes_in_flight
-            .try_sub(bytes_acknowledged)
-            .expect("bytes_acknowledged should not exceed u32::MAX");
+            .try_sub(bytes_acknowledged.min(*self.bytes_in_flight as usize))
+            .expect("bytes_in_flight is a u32");
         self.bw_estimator.on_ack(
             bytes_acknowledged,
             newest_acked_time_sent,
@@ -415,9 +417,27 @@ impl CongestionController for BbrCongestionController {
         self.next_departure_time
     }
 
+    fn pacing_rate(&self, _rtt_estimator: &RttEstimator) -> u64 {
+        self.pacing_rate * Duration::from_secs(1)
+    }
+
     fn send_quantum(&self) -> Option<usize> {
         Some(self.send_quantum)
     }
+
+    fn app_limited_round_ratio(&self) -> Option<f32> {
+        self.round_counter.app_limited_round_ratio()
+    }
+
+    fn delivery_rate(&self) -> Option<Bandwidth> {
+        let rate_sample = self.bw_estimator.rate_sample();
+
+        if rate_sample.interval.is_zero() {
+            return None;
+        }
+
+        Some(rate_sample.delivery_rate())
+    }
 }
 
 impl BbrCongestionController {
@@ -215,5 +215,49 @@ impl BbrCongestionController {
 
 #[cfg(test)]
 mod tests {
-    // TODO
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+
+    #[test]
+    fn fresh_min_rtt_does_not_enter_probe_rtt() {
+        let now = NoopClock.get_time();
+        let mut bbr = BbrCongestionController::new(1200, now);
+        let mut rng = random::testing::Generator::default();
+
+        bbr.data_volume_model
+            .update_min_rtt(Duration::from_millis(100), now);
+
+        // The min probe RTT was refreshed less than ProbeRTTInterval (5 seconds) ago
+        let now = now + Duration::from_secs(4);
+        bbr.data_volume_model
+            .update_min_rtt(Duration::from_millis(150), now);
+        assert!(!bbr.data_volume_model.probe_rtt_expired());
+
+        bbr.check_probe_rtt(&mut rng, now);
+        assert!(!bbr.state.is_probing_rtt());
+    }
+
+    #[test]
+    fn stale_min_rtt_enters_probe_rtt() {
+        let now = NoopClock.get_time();
+        let mut bbr = BbrCongestionController::new(1200, now);
+        let mut rng = random::testing::Generator::default();
+
+        bbr.data_volume_model
+            .update_min_rtt(Duration::from_millis(100), now);
+
+        // The min probe RTT has not been refreshed for ProbeRTTInterval (5 seconds)
+        let now = now + Duration::from_secs(5);
+        bbr.data_volume_model
+            .update_min_rtt(Duration::from_millis(150), now);
+        assert!(bbr.data_volume_model.probe_rtt_expired());
+
+        bbr.check_probe_rtt(&mut rng, now);
+        assert!(bbr.state.is_probing_rtt());
+
+        // No bandwidth has been measured, so the cwnd is capped at BBRMinPipeCwnd
+        assert_eq!(bbr.probe_rtt_cwnd(), 4 * 1200);
+        bbr.set_cwnd(0);
+        assert_eq!(bbr.cwnd, 4 * 1200);
+    }
 }
@@ -21,6 +21,11 @@ pub(crate) struct Counter {
     round_start: bool,
     /// The number of rounds counted since initialization
     round_count: u64,
+    /// True if any packet acknowledged in the current round was sent while application limited
+    round_is_app_limited: bool,
+    /// The number of completed rounds in which a packet sent while application limited
+    /// was acknowledged
+    app_limited_round_count: u64,
 }
 
 impl Counter {
@@ -37,11 +42,17 @@ impl Counter {
         //#     BBR.round_start = false
         if packet_info.delivered_bytes >= self.next_round_delivered_bytes {
             self.set_round_end(delivered_bytes);
+            if self.round_is_app_limited {
+                self.app_limited_round_count += 1;
+            }
+            self.round_is_app_limited = false;
             self.round_count += 1;
             self.round_start = true;
         } else {
             self.round_start = false;
         }
+
+        self.round_is_app_limited |= packet_info.is_app_limited;
     }
 
     /// Sets the end of the current round to the given `delivered_bytes`
@@ -66,6 +77,21 @@ impl Counter {
     pub fn round_count(&self) -> u64 {
         self.round_count
     }
+
+    /// The fraction of completed rounds that were application limited, from 0.0 to 1.0
+    ///
+    /// A round is application limited if any packet acknowledged during the round was
+    /// sent while the application was not supplying enough data to fill the congestion
+    /// window. `None` is returned if no rounds have completed.
+    pub fn app_limited_round_ratio(&self) -> Option<f32> {
+        // The round in progress has not completed yet
+        let completed_rounds = self
+            .round_count
+            .checked_sub(1)
+            .filter(|&rounds| rounds > 0)?;
+
+        Some(self.app_limited_round_count as f32 / completed_rounds as f32)
+    }
 }
 
 #[cfg(test)]
@@ -172,4 +198,47 @@ mod tests {
         assert!(counter.round_start());
         assert_eq!(2, counter.round_count());
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn app_limited_round_ratio() {
+        let mut counter = Counter::default();
+        assert_eq!(None, counter.app_limited_round_ratio());
+
+        let now = NoopClock.get_time();
+        let mut packet_info = PacketInfo {
+            delivered_bytes: 0,
+            delivered_time: now,
+            lost_bytes: 0,
+            ecn_ce_count: 0,
+            first_sent_time: now,
+            bytes_in_flight: 0,
+            is_app_limited: false,
+        };
+
+        // Start the first round
+        let mut delivered_bytes = 100;
+        counter.on_ack(packet_info, delivered_bytes);
+        assert_eq!(None, counter.app_limited_round_ratio());
+
+        // Complete 4 rounds, with an app limited packet acknowledged in the 2nd and 4th
+        for round in 1..=4 {
+            if round % 2 == 0 {
+                // An app limited packet mid-round marks the whole round as app limited
+                packet_info.is_app_limited = true;
+                packet_info.delivered_bytes = delivered_bytes - 50;
+                counter.on_ack(packet_info, delivered_bytes);
+                assert!(!counter.round_start());
+                packet_info.is_app_limited = false;
+            }
+
+            packet_info.delivered_bytes = delivered_bytes;
+            delivered_bytes += 100;
+            counter.on_ack(packet_info, delivered_bytes);
+            assert!(counter.round_start());
+        }
+
+        assert_eq!(5, counter.round_count());
+        assert_eq!(Some(0.5), counter.app_limited_round_ratio());
+    }
 }
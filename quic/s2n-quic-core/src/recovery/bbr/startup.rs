@@ -50,3 +50,97 @@ impl BbrCongestionController {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        recovery::{
+            bandwidth::{PacketInfo, RateSample},
+            bbr::drain,
+        },
+        time::{Clock, NoopClock},
+    };
+    use core::time::Duration;
+
+    #[test]
+    fn startup_gains() {
+        //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#2.6
+        //= type=test
+        //# A constant specifying the minimum gain value for calculating the pacing rate that will
+        //# allow the sending rate to double each round (4*ln(2) ~= 2.77)
+        assert_eq!(Ratio::new_raw(277, 100), State::Startup.pacing_gain());
+
+        //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#2.6
+        //= type=test
+        //# A constant specifying the minimum gain value for calculating the
+        //# cwnd that will allow the sending rate to double each round (2.0)
+        assert_eq!(Ratio::new_raw(2, 1), State::Startup.cwnd_gain());
+    }
+
+    #[test]
+    fn bandwidth_plateau_enters_drain() {
+        let now = NoopClock.get_time();
+        let mut bbr = BbrCongestionController::new(1200, now);
+        let mut delivered_bytes = 0;
+        let mut packet_info = PacketInfo {
+            delivered_bytes: 0,
+            delivered_time: now,
+            lost_bytes: 0,
+            ecn_ce_count: 0,
+            first_sent_time: now,
+            bytes_in_flight: 0,
+            is_app_limited: false,
+        };
+
+        // Updates the max bandwidth with a sample of `bytes_per_second` and accepts an ack
+        // that starts a new round
+        let mut on_round = |bbr: &mut BbrCongestionController, bytes_per_second: u64| {
+            bbr.data_rate_model.update_max_bw(RateSample {
+                interval: Duration::from_secs(1),
+                delivered_bytes: bytes_per_second,
+                ..Default::default()
+            });
+            packet_info.delivered_bytes = delivered_bytes;
+            delivered_bytes += bytes_per_second;
+            bbr.round_counter.on_ack(packet_info, delivered_bytes);
+            assert!(bbr.round_counter.round_start());
+            bbr.check_startup_done();
+        };
+
+        // The bandwidth doubles each round, so the pipe has not been filled
+        for bytes_per_second in [1000, 2000, 4000] {
+            on_round(&mut bbr, bytes_per_second);
+            assert!(bbr.state.is_startup());
+        }
+
+        // Two rounds without at least 25% growth is not enough to fill the pipe
+        on_round(&mut bbr, 4500);
+        on_round(&mut bbr, 4500);
+        assert!(bbr.state.is_startup());
+        assert!(!bbr.full_pipe_estimator.filled_pipe());
+
+        // After the third round without at least 25% growth, BBR exits Startup and enters Drain
+        on_round(&mut bbr, 4500);
+        assert!(bbr.full_pipe_estimator.filled_pipe());
+        assert!(bbr.state.is_drain());
+        assert_eq!(drain::PACING_GAIN, bbr.state.pacing_gain());
+        assert_eq!(CWND_GAIN, bbr.state.cwnd_gain());
+    }
+
+    #[test]
+    fn startup_done_only_checked_on_round_start() {
+        let now = NoopClock.get_time();
+        let mut bbr = BbrCongestionController::new(1200, now);
+
+        // The full pipe estimator only runs at the start of a round, so checking
+        // repeatedly within a round does not count towards the plateau
+        for _ in 0..5 {
+            bbr.check_startup_done();
+        }
+
+        assert!(!bbr.round_counter.round_start());
+        assert!(!bbr.full_pipe_estimator.filled_pipe());
+        assert!(bbr.state.is_startup());
+    }
+}
@@ -4,12 +4,13 @@
 use crate::{
     event::{api::SocketAddress, IntoEvent},
     inet,
+    packet::number::PacketNumber,
     path::MINIMUM_MTU,
     random,
-    recovery::RttEstimator,
+    recovery::{bandwidth::Bandwidth, pacing::Pacer, prr::PrrState, RttEstimator},
     time::Timestamp,
 };
-use core::fmt::Debug;
+use core::{fmt, fmt::Debug, str::FromStr, time::Duration};
 
 pub trait Endpoint: 'static + Debug + Send {
     type CongestionController: CongestionController;
@@ -36,6 +37,62 @@ impl<'a> PathInfo<'a> {
     }
 }
 
+/// The congestion control algorithms implemented by s2n-quic
+///
+/// As `CongestionController` is not object safe, an algorithm selected by name at runtime
+/// is used to choose which `Endpoint` to construct rather than returning a boxed controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Algorithm {
+    Cubic,
+    Bbr,
+}
+
+impl Algorithm {
+    /// Returns the algorithm with the given name
+    pub fn from_name(name: &str) -> Result<Self, UnknownAlgorithmError> {
+        match name {
+            "cubic" => Ok(Self::Cubic),
+            "bbr" => Ok(Self::Bbr),
+            _ => Err(UnknownAlgorithmError(())),
+        }
+    }
+
+    /// Returns the name of the algorithm
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cubic => "cubic",
+            Self::Bbr => "bbr",
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = UnknownAlgorithmError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::from_name(name)
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownAlgorithmError(());
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownAlgorithmError {}
+
+impl fmt::Display for UnknownAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown congestion control algorithm")
+    }
+}
+
 pub trait CongestionController: 'static + Clone + Send + Debug {
     /// Additional metadata about a packet to track until a sent packet
     /// is either acknowledged or declared lost
@@ -120,6 +177,13 @@ pub trait CongestionController: 'static + Clone + Send + Debug {
     /// `ce_count` represents the incremental number of packets marked with the ECN CE codepoint
     fn on_explicit_congestion(&mut self, ce_count: u64, event_time: Timestamp);
 
+    /// Invoked for each ACK frame carrying ECN counts with the smoothed fraction of
+    /// ECN-marked packets that were marked CE, from 0.0 to 1.0
+    ///
+    /// Controllers that respond in proportion to the extent of congestion, such as DCTCP,
+    /// may use this rate. The default implementation ignores it.
+    fn on_ecn_mark_rate(&mut self, _ecn_mark_rate: f32) {}
+
     /// Invoked when the path maximum transmission unit is updated.
     fn on_mtu_update(&mut self, max_data_size: u16);
 
@@ -131,6 +195,21 @@ pub trait CongestionController: 'static + Clone + Send + Debug {
     /// If the time is in the past or is `None`, the packet should be transmitted immediately.
     fn earliest_departure_time(&self) -> Option<Timestamp>;
 
+    /// Returns the rate, in bytes per second, that the congestion controller is pacing
+    /// packets at
+    ///
+    /// This may be used for display or passed to OS-level pacing (e.g. `SO_MAX_PACING_RATE`).
+    /// The default implementation paces the congestion window over the smoothed RTT, as
+    /// described in RFC 9002 Section 7.7.
+    #[inline]
+    fn pacing_rate(&self, rtt_estimator: &RttEstimator) -> u64 {
+        Pacer::rate(
+            rtt_estimator.smoothed_rtt(),
+            self.congestion_window(),
+            self.is_slow_start(),
+        )
+    }
+
     /// The maximum number of bytes for an aggregation of packets scheduled and transmitted together.
     ///
     /// If the value is `None`, the congestion controller does not influence the send aggregation.
@@ -140,6 +219,159 @@ pub trait CongestionController: 'static + Clone + Send + Debug {
     fn send_quantum(&self) -> Option<usize> {
         None
     }
+
+    /// Invoked when a new round trip starts
+    ///
+    /// A round trip ends when a packet sent after the start of the previous round trip is
+    /// acknowledged. The default implementation does nothing.
+    fn on_round_start(&mut self) {}
+
+    /// Invoked before any packets are sent on a resumed connection with the congestion
+    /// window remembered from a prior connection to the same peer
+    ///
+    /// The default implementation ignores the remembered window.
+    fn on_remembered_congestion_window(&mut self, _congestion_window: u32) {}
+
+    /// Invoked before any packets are sent on a resumed connection with the slow start
+    /// threshold remembered from a prior connection to the same peer
+    ///
+    /// The default implementation ignores the remembered threshold.
+    fn on_remembered_slow_start_threshold(&mut self, _slow_start_threshold: u32) {}
+
+    /// Invoked when the connection is closed, after any packets in flight have been discarded
+    ///
+    /// State tracking an ongoing recovery period should be cleared so it does not carry over
+    /// if the controller is reused. The default implementation does nothing.
+    fn on_connection_close(&mut self) {}
+
+    /// Invoked after `on_packet_sent` with the packet number of the sent packet
+    ///
+    /// The default implementation does nothing.
+    fn on_packet_number_sent(&mut self, _packet_number: PacketNumber) {}
+
+    /// Returns the packet number that ends the current recovery period
+    ///
+    /// This is the most recent packet sent when the recovery period began. Acknowledging a
+    /// packet sent after it ends the recovery period, and losses of packets up to and including
+    /// it do not start a new congestion event. `None` is returned outside of a recovery period.
+    ///
+    /// The default implementation does not track recovery periods and always returns `None`.
+    fn recovery_end_packet(&self) -> Option<PacketNumber> {
+        None
+    }
+
+    /// Returns the delivery rate measured from the most recent rate sample
+    ///
+    /// `None` is returned if no rate sample has been taken. The default implementation
+    /// does not sample the delivery rate and always returns `None`.
+    fn delivery_rate(&self) -> Option<Bandwidth> {
+        None
+    }
+
+    /// Returns the fraction of completed rounds that were application limited, from 0.0 to 1.0
+    ///
+    /// A high ratio indicates the sender is usually limited by the application rather
+    /// than the network. `None` is returned if no rounds have completed. The default
+    /// implementation does not count rounds and always returns `None`.
+    fn app_limited_round_ratio(&self) -> Option<f32> {
+        None
+    }
+
+    /// Returns the number of bytes that may be transmitted at the given time
+    ///
+    /// The default implementation is limited by the available congestion window and
+    /// the earliest departure time.
+    fn send_budget(&self, now: Timestamp) -> usize {
+        if self
+            .earliest_departure_time()
+            .map_or(false, |edt| !edt.has_elapsed(now))
+        {
+            return 0;
+        }
+
+        self.congestion_window()
+            .saturating_sub(self.bytes_in_flight()) as usize
+    }
+
+    /// Returns the number of packets of `max_datagram_size` needed to carry the bytes in flight
+    ///
+    /// A partially filled packet is counted as a whole packet, so any bytes in flight
+    /// result in at least one packet.
+    #[inline]
+    fn packets_in_flight(&self, max_datagram_size: u16) -> u32 {
+        let max_datagram_size = max_datagram_size as u32;
+
+        debug_assert!(max_datagram_size > 0, "max_datagram_size must be non-zero");
+
+        let bytes_in_flight = self.bytes_in_flight();
+        let packets = bytes_in_flight / max_datagram_size;
+
+        if bytes_in_flight % max_datagram_size > 0 {
+            packets + 1
+        } else {
+            packets
+        }
+    }
+
+    /// Returns a snapshot of the current state of the congestion controller
+    ///
+    /// The default implementation reports an unbounded slow start threshold and does
+    /// not distinguish a recovery period from congestion avoidance.
+    fn snapshot(&self) -> ControllerState {
+        ControllerState {
+            cwnd: self.congestion_window(),
+            ssthresh: u32::MAX,
+            bytes_in_flight: self.bytes_in_flight(),
+            state: if self.is_slow_start() {
+                Phase::SlowStart
+            } else {
+                Phase::CongestionAvoidance
+            },
+            prr: None,
+        }
+    }
+}
+
+/// A point-in-time view of a congestion controller, intended for tests and metrics
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ControllerState {
+    /// The congestion window in bytes
+    pub cwnd: u32,
+    /// The slow start threshold in bytes
+    pub ssthresh: u32,
+    /// The current bytes in flight
+    pub bytes_in_flight: u32,
+    /// The phase of the congestion controller
+    pub state: Phase,
+    /// The Proportional Rate Reduction state, if the controller is in a recovery period
+    pub prr: Option<PrrState>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    SlowStart,
+    CongestionAvoidance,
+    Recovery,
+}
+
+/// The cumulative time a congestion controller has spent in each [`Phase`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PhaseDurations {
+    pub slow_start: Duration,
+    pub congestion_avoidance: Duration,
+    pub recovery: Duration,
+}
+
+impl PhaseDurations {
+    /// Returns a mutable reference to the duration for the given `phase`
+    #[inline]
+    pub fn get_mut(&mut self, phase: Phase) -> &mut Duration {
+        match phase {
+            Phase::SlowStart => &mut self.slow_start,
+            Phase::CongestionAvoidance => &mut self.congestion_avoidance,
+            Phase::Recovery => &mut self.recovery,
+        }
+    }
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -243,6 +475,10 @@ pub mod testing {
             fn earliest_departure_time(&self) -> Option<Timestamp> {
                 None
             }
+
+            fn pacing_rate(&self, _rtt_estimator: &RttEstimator) -> u64 {
+                u64::MAX
+            }
         }
     }
 
@@ -282,6 +518,13 @@ pub mod testing {
             pub loss_bursts: u32,
             pub app_limited: Option<bool>,
             pub slow_start: bool,
+            pub earliest_departure_time: Option<Timestamp>,
+            /// The number of bytes the congestion window grows by on each ack
+            pub congestion_window_increase_on_ack: u32,
+            pub on_round_start: u32,
+            pub on_connection_close: u32,
+            pub delivery_rate: Option<Bandwidth>,
+            pub ecn_mark_rate: Option<f32>,
         }
 
         impl Default for CongestionController {
@@ -300,6 +543,12 @@ pub mod testing {
                     loss_bursts: 0,
                     app_limited: None,
                     slow_start: true,
+                    earliest_departure_time: None,
+                    congestion_window_increase_on_ack: 0,
+                    on_round_start: 0,
+                    on_connection_close: 0,
+                    delivery_rate: None,
+                    ecn_mark_rate: None,
                 }
             }
         }
@@ -359,6 +608,7 @@ pub mod testing {
                 _ack_receive_time: Timestamp,
             ) {
                 self.on_packet_ack += 1;
+                self.congestion_window += self.congestion_window_increase_on_ack;
             }
 
             fn on_packet_lost<Rnd: random::Generator>(
@@ -386,6 +636,10 @@ pub mod testing {
                 self.slow_start = false;
             }
 
+            fn on_ecn_mark_rate(&mut self, ecn_mark_rate: f32) {
+                self.ecn_mark_rate = Some(ecn_mark_rate);
+            }
+
             fn on_mtu_update(&mut self, _max_data_size: u16) {
                 self.on_mtu_update += 1;
             }
@@ -395,8 +649,88 @@ pub mod testing {
             }
 
             fn earliest_departure_time(&self) -> Option<Timestamp> {
-                None
+                self.earliest_departure_time
+            }
+
+            fn on_round_start(&mut self) {
+                self.on_round_start += 1;
+            }
+
+            fn on_connection_close(&mut self) {
+                self.on_connection_close += 1;
+            }
+
+            fn delivery_rate(&self) -> Option<Bandwidth> {
+                self.delivery_rate
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithm_from_name() {
+        for algorithm in [Algorithm::Cubic, Algorithm::Bbr] {
+            assert_eq!(Algorithm::from_name(algorithm.name()), Ok(algorithm));
+            assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+        }
+
+        assert_eq!(Algorithm::from_name("cubic"), Ok(Algorithm::Cubic));
+        assert_eq!(Algorithm::from_name("bbr"), Ok(Algorithm::Bbr));
+
+        // Reno is not implemented
+        assert!(Algorithm::from_name("reno").is_err());
+        assert!(Algorithm::from_name("CUBIC").is_err());
+        assert!(Algorithm::from_name("").is_err());
+    }
+
+    #[test]
+    fn packets_in_flight() {
+        let mut cc = testing::mock::CongestionController::default();
+        assert_eq!(cc.packets_in_flight(1200), 0);
+
+        // Exact multiples of the datagram size
+        cc.bytes_in_flight = 1200;
+        assert_eq!(cc.packets_in_flight(1200), 1);
+        cc.bytes_in_flight = 12_000;
+        assert_eq!(cc.packets_in_flight(1200), 10);
+
+        // Any remainder is rounded up to a whole packet
+        cc.bytes_in_flight = 1;
+        assert_eq!(cc.packets_in_flight(1200), 1);
+        cc.bytes_in_flight = 1201;
+        assert_eq!(cc.packets_in_flight(1200), 2);
+        cc.bytes_in_flight = 12_001;
+        assert_eq!(cc.packets_in_flight(1200), 11);
+        assert_eq!(cc.packets_in_flight(u16::MAX), 1);
+    }
+
+    #[test]
+    fn pacing_rate() {
+        use crate::{
+            packet::number::PacketNumberSpace,
+            time::{Clock, NoopClock},
+        };
+
+        let mut cc = testing::mock::CongestionController::default();
+        let mut rtt_estimator = RttEstimator::default();
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(100),
+            NoopClock.get_time(),
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        // The default implementation paces the window over the smoothed RTT
+        // rate = 2 * 15_000 / 100ms in slow start
+        assert_eq!(cc.pacing_rate(&rtt_estimator), 300_000);
+
+        // rate = 1.25 * 15_000 / 100ms in congestion avoidance
+        cc.slow_start = false;
+        assert_eq!(cc.pacing_rate(&rtt_estimator), 187_500);
+    }
+}
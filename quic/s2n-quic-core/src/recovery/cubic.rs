@@ -3,13 +3,17 @@
 
 use crate::{
     counter::Counter,
+    packet::number::PacketNumber,
     random,
     recovery::{
-        congestion_controller::{self, CongestionController},
+        congestion_controller::{
+            self, CongestionController, ControllerState, Phase, PhaseDurations,
+        },
         cubic::{FastRetransmission::*, State::*},
         hybrid_slow_start::HybridSlowStart,
         pacing::Pacer,
-        RttEstimator,
+        prr::Prr,
+        RttEstimator, DEFAULT_INITIAL_RTT,
     },
     time::Timestamp,
 };
@@ -143,6 +147,10 @@ pub struct CubicCongestionController {
     //# distance networks.
     slow_start: HybridSlowStart,
     pacer: Pacer,
+    // The congestion controller is shared by every packet number space on the path, so
+    // losses in any space are part of the same recovery period and a single PRR instance
+    // is used. Loss detection timers remain per space in the recovery manager.
+    prr: Prr,
     max_datagram_size: u16,
     congestion_window: f32,
     state: State,
@@ -161,6 +169,29 @@ pub struct CubicCongestionController {
     // The highest number of bytes in flight seen when an ACK was received,
     // since the last congestion event.
     bytes_in_flight_hi: BytesInFlight,
+    // The highest number of bytes in flight seen over the lifetime of the controller
+    max_bytes_in_flight: BytesInFlight,
+    // The most recent smoothed RTT, used for reporting the pacing rate and debouncing
+    // congestion events
+    smoothed_rtt: Duration,
+    // The congestion window in bytes above which Limited Slow Start restricts growth
+    max_ssthresh: Option<u32>,
+    // The number of bytes in each GSO segment, which the send budget is rounded down to
+    send_quantum: Option<u32>,
+    // If true, the congestion window is reduced at most once per smoothed RTT
+    congestion_debounce: bool,
+    // The time of the most recent congestion event that reduced the congestion window
+    last_reduction_time: Option<Timestamp>,
+    // The packet number of the most recently sent packet
+    last_sent_packet: Option<PacketNumber>,
+    // The most recently sent packet when the current recovery period began
+    recovery_end_packet: Option<PacketNumber>,
+    // If true, internal invariants are checked after every operation in debug builds
+    invariant_checks: bool,
+    // The cumulative time spent in each phase, not including the current phase
+    phase_durations: PhaseDurations,
+    // The time the current phase began, or `None` if no packets have been sent
+    phase_start: Option<Timestamp>,
 }
 
 type BytesInFlight = Counter<u32>;
@@ -212,6 +243,12 @@ impl CongestionController for CubicCongestionController {
         self.bytes_in_flight
             .try_add(bytes_sent)
             .expect("bytes sent should not exceed u32::MAX");
+        self.max_bytes_in_flight = self.max_bytes_in_flight.max(self.bytes_in_flight);
+
+        if self.phase_start.is_none() {
+            // The controller starts in slow start once the first packet is sent
+            self.phase_start = Some(time_sent);
+        }
 
         if let Some(app_limited) = app_limited {
             // We check both the given `app_limited` value and is_congestion_window_under_utilized()
@@ -226,13 +263,18 @@ impl CongestionController for CubicCongestionController {
             self.under_utilized = self.is_congestion_window_under_utilized();
         }
 
-        if let Recovery(recovery_start_time, RequiresTransmission) = self.state {
-            // A packet has been sent since we entered recovery (fast retransmission)
-            // so flip the state back to idle.
-            self.state = Recovery(recovery_start_time, Idle);
+        self.prr.on_packet_sent(bytes_sent);
+
+        if let Recovery(recovery_start_time, fast_retransmission) = &self.state {
+            if *fast_retransmission == RequiresTransmission {
+                // A packet has been sent since we entered recovery (fast retransmission)
+                // so flip the state back to idle.
+                self.state = Recovery(*recovery_start_time, Idle);
+            }
         }
 
         self.time_of_last_sent_packet = Some(time_sent);
+        self.smoothed_rtt = rtt_estimator.smoothed_rtt();
 
         let slow_start = matches!(self.state, State::SlowStart);
 
@@ -244,6 +286,8 @@ impl CongestionController for CubicCongestionController {
             self.max_datagram_size,
             slow_start,
         );
+
+        self.check_invariants();
     }
 
     #[inline]
@@ -253,6 +297,8 @@ impl CongestionController for CubicCongestionController {
         now: Timestamp,
         rtt_estimator: &RttEstimator,
     ) {
+        self.smoothed_rtt = rtt_estimator.smoothed_rtt();
+
         // Update the Slow Start algorithm each time the RTT
         // estimate is updated to find the slow start threshold.
         self.slow_start.on_rtt_update(
@@ -272,6 +318,7 @@ impl CongestionController for CubicCongestionController {
             //# t is the elapsed time since the beginning of the current congestion
             //# avoidance, K is set to 0, and W_max is set to the congestion window
             //# size at the beginning of the current congestion avoidance.
+            self.on_phase_end(now);
             self.state = State::congestion_avoidance(now);
             self.cubic.on_slow_start_exit(self.congestion_window);
         }
@@ -288,9 +335,23 @@ impl CongestionController for CubicCongestionController {
         ack_receive_time: Timestamp,
     ) {
         self.bytes_in_flight_hi = self.bytes_in_flight_hi.max(self.bytes_in_flight);
+        // An accounting error or misbehaving peer may acknowledge bytes that were never
+        // counted as in flight, so bytes in flight saturates at zero rather than underflowing
         self.bytes_in_flight
-            .try_sub(bytes_acknowledged)
-            .expect("bytes_acknowledged should not exceed u32::MAX");
+            .try_sub(bytes_acknowledged.min(*self.bytes_in_flight as usize))
+            .expect("bytes_in_flight is a u32");
+
+        if let Recovery(recovery_start_time, _) = self.state {
+            // The ack that concludes the recovery period is excluded from PRR
+            if newest_acked_time_sent <= recovery_start_time {
+                self.prr.on_ack(
+                    bytes_acknowledged,
+                    *self.bytes_in_flight,
+                    self.congestion_window(),
+                    self.max_datagram_size,
+                );
+            }
+        }
 
         if self.under_utilized {
             self.state.on_app_limited(ack_receive_time);
@@ -301,6 +362,7 @@ impl CongestionController for CubicCongestionController {
             //# underutilized.  This can happen due to insufficient application data
             //# or flow control limits.  When this occurs, the congestion window
             //# SHOULD NOT be increased in either slow start or congestion avoidance.
+            self.check_invariants();
             return;
         }
 
@@ -310,7 +372,9 @@ impl CongestionController for CubicCongestionController {
                 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
                 //# A recovery period ends and the sender enters congestion avoidance
                 //# when a packet sent during the recovery period is acknowledged.
-                self.state = State::congestion_avoidance(ack_receive_time)
+                self.on_phase_end(ack_receive_time);
+                self.state = State::congestion_avoidance(ack_receive_time);
+                self.prr.on_recovery_end();
             }
         };
 
@@ -330,6 +394,7 @@ impl CongestionController for CubicCongestionController {
 
         if self.congestion_window >= max_cwnd {
             // The window is already larger than the max, so we can return early
+            self.check_invariants();
             return;
         }
 
@@ -340,13 +405,15 @@ impl CongestionController for CubicCongestionController {
                 //# the number of bytes acknowledged when each acknowledgment is
                 //# processed.  This results in exponential growth of the congestion
                 //# window.
-                self.congestion_window = (self.congestion_window
-                    + self.slow_start.cwnd_increment(bytes_acknowledged))
-                .min(max_cwnd);
+                let cwnd_increment = self.limited_slow_start_increment(
+                    self.slow_start.cwnd_increment(bytes_acknowledged),
+                );
+                self.congestion_window = (self.congestion_window + cwnd_increment).min(max_cwnd);
 
                 if self.congestion_window >= self.slow_start.threshold {
                     // The congestion window has exceeded a previously determined slow start threshold
                     // so transition to congestion avoidance and notify cubic of the slow start exit
+                    self.on_phase_end(ack_receive_time);
                     self.state = State::congestion_avoidance(ack_receive_time);
                     self.cubic.on_slow_start_exit(self.congestion_window);
                 }
@@ -375,6 +442,7 @@ impl CongestionController for CubicCongestionController {
         };
 
         debug_assert!(self.congestion_window >= self.cubic.minimum_window());
+        self.check_invariants();
     }
 
     #[inline]
@@ -399,9 +467,16 @@ impl CongestionController for CubicCongestionController {
         //# [RFC5681].
         if persistent_congestion {
             self.congestion_window = self.cubic.minimum_window();
+            // The slow start threshold reduced by the congestion event is retained, so slow
+            // start ends once the window has grown back to it
+            self.on_phase_end(timestamp);
             self.state = State::SlowStart;
+            // Slow start replaces the recovery period, so PRR no longer bounds sending
+            self.prr.on_recovery_end();
             self.cubic.reset();
         }
+
+        self.check_invariants();
     }
 
     #[inline]
@@ -412,6 +487,7 @@ impl CongestionController for CubicCongestionController {
         //# Experienced (CE) codepoint in the IP header as a signal of
         //# congestion.
         self.on_congestion_event(event_time);
+        self.check_invariants();
     }
 
     //= https://www.rfc-editor.org/rfc/rfc8899#section-3
@@ -436,9 +512,11 @@ impl CongestionController for CubicCongestionController {
         let old_max_datagram_size = self.max_datagram_size;
         self.max_datagram_size = max_datagram_size;
         self.cubic.max_datagram_size = max_datagram_size;
+        self.prr.on_mtu_update(max_datagram_size);
 
         self.congestion_window =
             (self.congestion_window / old_max_datagram_size as f32) * max_datagram_size as f32;
+        self.check_invariants();
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9002#section-6.4
@@ -460,12 +538,112 @@ impl CongestionController for CubicCongestionController {
             // retransmission that may never come.
             self.state = Recovery(recovery_start_time, Idle);
         }
+
+        self.check_invariants();
     }
 
     #[inline]
     fn earliest_departure_time(&self) -> Option<Timestamp> {
         self.pacer.earliest_departure_time()
     }
+
+    #[inline]
+    fn on_remembered_congestion_window(&mut self, congestion_window: u32) {
+        debug_assert_eq!(
+            *self.bytes_in_flight, 0,
+            "the remembered window should be applied before any packets are sent"
+        );
+
+        // The remembered window is clamped so a stale or corrupted value neither
+        // starts the connection below the initial window nor with an excessive burst
+        const MAX_REMEMBERED_WINDOW_PACKETS: u32 = 1000;
+        let initial_window = Self::initial_window(self.max_datagram_size);
+        let max_window = MAX_REMEMBERED_WINDOW_PACKETS * self.max_datagram_size as u32;
+        self.congestion_window = congestion_window.max(initial_window).min(max_window) as f32;
+        self.check_invariants();
+    }
+
+    #[inline]
+    fn on_remembered_slow_start_threshold(&mut self, slow_start_threshold: u32) {
+        debug_assert_eq!(
+            *self.bytes_in_flight, 0,
+            "the remembered threshold should be applied before any packets are sent"
+        );
+
+        // Slow start is exited once the congestion window reaches the remembered threshold
+        self.slow_start
+            .on_congestion_event(slow_start_threshold as f32);
+        self.check_invariants();
+    }
+
+    #[inline]
+    fn on_connection_close(&mut self) {
+        self.prr.on_recovery_end();
+    }
+
+    #[inline]
+    fn on_packet_number_sent(&mut self, packet_number: PacketNumber) {
+        self.last_sent_packet = Some(packet_number);
+    }
+
+    #[inline]
+    fn recovery_end_packet(&self) -> Option<PacketNumber> {
+        if let Recovery(_, _) = self.state {
+            self.recovery_end_packet
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn send_budget(&self, now: Timestamp) -> usize {
+        let mut budget = self
+            .congestion_window()
+            .saturating_sub(*self.bytes_in_flight) as usize;
+
+        if let Recovery(_, _) = self.state {
+            budget = budget.min(self.prr.bytes_allowed_on_ack());
+        }
+
+        // The pacer applies in every state, including recovery. PRR limits how much may be
+        // sent in response to each ack, while the pacer limits how quickly that allowance is
+        // released, so the budget is the smaller of the two.
+        if let Some(available_bytes) = self.pacer.available_bytes(now, self.max_datagram_size) {
+            budget = budget.min(available_bytes);
+        }
+
+        if let Some(send_quantum) = self.send_quantum {
+            // Rounding is skipped when nothing is in flight so a congestion window smaller
+            // than the quantum can't prevent transmission entirely
+            if *self.bytes_in_flight > 0 {
+                budget -= budget % send_quantum as usize;
+            }
+        }
+
+        if self.requires_fast_retransmission() {
+            // A single packet may be sent on entering recovery regardless of the congestion
+            // window or pacer, so the budget always allows at least one datagram
+            budget = budget.max(self.max_datagram_size as usize);
+        }
+
+        budget
+    }
+
+    #[inline]
+    fn snapshot(&self) -> ControllerState {
+        let prr = match self.state {
+            Recovery(_, _) => Some(self.prr.state()),
+            _ => None,
+        };
+
+        ControllerState {
+            cwnd: self.congestion_window(),
+            ssthresh: self.slow_start.threshold as u32,
+            bytes_in_flight: *self.bytes_in_flight,
+            state: self.phase(),
+            prr,
+        }
+    }
 }
 
 impl CubicCongestionController {
@@ -474,6 +652,7 @@ impl CubicCongestionController {
             cubic: Cubic::new(max_datagram_size),
             slow_start: HybridSlowStart::new(max_datagram_size),
             pacer: Pacer::default(),
+            prr: Prr::new().with_max_datagram_size(max_datagram_size),
             max_datagram_size,
             congestion_window: CubicCongestionController::initial_window(max_datagram_size) as f32,
             state: SlowStart,
@@ -481,7 +660,155 @@ impl CubicCongestionController {
             time_of_last_sent_packet: None,
             under_utilized: true,
             bytes_in_flight_hi: Counter::new(0),
+            max_bytes_in_flight: Counter::new(0),
+            smoothed_rtt: DEFAULT_INITIAL_RTT,
+            max_ssthresh: None,
+            send_quantum: None,
+            congestion_debounce: false,
+            last_reduction_time: None,
+            last_sent_packet: None,
+            recovery_end_packet: None,
+            invariant_checks: false,
+            phase_durations: PhaseDurations::default(),
+            phase_start: None,
+        }
+    }
+
+    /// Enables Limited Slow Start as specified in <https://www.rfc-editor.org/rfc/rfc3742>
+    ///
+    /// Once the congestion window exceeds `max_ssthresh` bytes, slow start grows the window
+    /// by at most `max_ssthresh / 2` bytes per RTT rather than doubling it.
+    pub fn with_limited_slow_start(mut self, max_ssthresh: u32) -> Self {
+        self.max_ssthresh = Some(max_ssthresh);
+        self
+    }
+
+    /// Limits congestion window reductions to at most one per smoothed RTT
+    ///
+    /// A congestion event following a recovery period normally reduces the window again,
+    /// even if the previous reduction was moments earlier. With debouncing enabled, events
+    /// within one smoothed RTT of the last reduction are ignored.
+    pub fn with_congestion_debounce(mut self, enabled: bool) -> Self {
+        self.congestion_debounce = enabled;
+        self
+    }
+
+    /// Sends in multiples of `send_quantum` bytes, such as the segment size used with GSO
+    ///
+    /// The send budget and the bytes released by the pacer are rounded down to the quantum
+    /// to avoid dribbling out segments smaller than the quantum.
+    pub fn with_send_quantum(mut self, send_quantum: u32) -> Self {
+        debug_assert_ne!(send_quantum, 0, "send_quantum must be at least one byte");
+        let send_quantum = send_quantum.max(1);
+        self.send_quantum = Some(send_quantum);
+        self.pacer = self.pacer.with_send_quantum(send_quantum);
+        self
+    }
+
+    /// Checks the internal invariants of the controller after every operation
+    ///
+    /// This is intended for fuzzing and other tests that exercise the controller with
+    /// arbitrary inputs. Violations fail a `debug_assert!`, so they only panic in debug
+    /// builds. Bytes in flight are checked against the sent packets by the packet
+    /// space manager rather than here, since the controller is shared by every packet number
+    /// space.
+    pub fn with_invariant_checks(mut self, enabled: bool) -> Self {
+        self.invariant_checks = enabled;
+        self
+    }
+
+    /// Asserts the invariants of the controller if `invariant_checks` are enabled
+    #[inline]
+    fn check_invariants(&self) {
+        if !self.invariant_checks {
+            return;
+        }
+
+        debug_assert!(
+            self.congestion_window >= self.cubic.minimum_window(),
+            "congestion window {} is below the minimum window {} in state {:?}",
+            self.congestion_window,
+            self.cubic.minimum_window(),
+            self.state
+        );
+
+        // The slow start threshold is always determined when slow start is exited,
+        // either by Hybrid Slow Start or by a congestion event
+        debug_assert!(
+            matches!(self.state, SlowStart) || self.slow_start.threshold < f32::MAX,
+            "slow start threshold is not set in state {:?}",
+            self.state
+        );
+
+        // PRR accounting is unsigned, so it is only checked that PRR is limited to
+        // recovery periods
+        debug_assert!(
+            !self.prr.is_active() || matches!(self.state, Recovery(_, _)),
+            "PRR is active outside of a recovery period in state {:?}: {:?}",
+            self.state,
+            self.prr.state()
+        );
+    }
+
+    /// Limits the slow start increment when the congestion window exceeds `max_ssthresh`
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc3742#section-2>: for each ack, the
+    /// increment is divided by `K = int(cwnd / (0.5 * max_ssthresh))`.
+    #[inline]
+    fn limited_slow_start_increment(&self, cwnd_increment: f32) -> f32 {
+        let max_ssthresh = match self.max_ssthresh {
+            Some(max_ssthresh) if self.congestion_window > max_ssthresh as f32 => {
+                max_ssthresh as f32
+            }
+            _ => return cwnd_increment,
+        };
+
+        let k = (self.congestion_window / (0.5 * max_ssthresh))
+            .floor()
+            .max(1.0);
+        cwnd_increment / k
+    }
+
+    /// Returns the cumulative time spent in each phase, up to `now`
+    ///
+    /// Time is counted from when the first packet is sent, and the time spent in the
+    /// current phase so far is included.
+    #[inline]
+    pub fn phase_durations(&self, now: Timestamp) -> PhaseDurations {
+        let mut phase_durations = self.phase_durations;
+
+        if let Some(phase_start) = self.phase_start {
+            *phase_durations.get_mut(self.phase()) += now.saturating_duration_since(phase_start);
         }
+
+        phase_durations
+    }
+
+    #[inline]
+    fn phase(&self) -> Phase {
+        match self.state {
+            SlowStart => Phase::SlowStart,
+            Recovery(_, _) => Phase::Recovery,
+            CongestionAvoidance(_) => Phase::CongestionAvoidance,
+        }
+    }
+
+    /// Adds the time spent in the current phase, which is about to end at `now`
+    #[inline]
+    fn on_phase_end(&mut self, now: Timestamp) {
+        self.phase_durations = self.phase_durations(now);
+        if self.phase_start.is_some() {
+            self.phase_start = Some(now);
+        }
+    }
+
+    /// Returns the highest number of bytes in flight seen over the lifetime of the controller
+    ///
+    /// Unlike the value used to limit the congestion window, this is not reset on
+    /// congestion events.
+    #[inline]
+    pub fn max_bytes_in_flight(&self) -> u64 {
+        *self.max_bytes_in_flight as u64
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
@@ -587,6 +914,16 @@ impl CubicCongestionController {
             return;
         }
 
+        // No reaction if the window was already reduced within the last RTT
+        if self.congestion_debounce {
+            if let Some(last_reduction_time) = self.last_reduction_time {
+                if event_time.saturating_duration_since(last_reduction_time) < self.smoothed_rtt {
+                    return;
+                }
+            }
+        }
+        self.last_reduction_time = Some(event_time);
+
         // Enter recovery period.
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.1
@@ -599,7 +936,10 @@ impl CubicCongestionController {
         //# single packet can be sent prior to reduction.  This speeds up loss
         //# recovery if the data in the lost packet is retransmitted and is
         //# similar to TCP as described in Section 5 of [RFC6675].
+        self.on_phase_end(event_time);
         self.state = Recovery(event_time, RequiresTransmission);
+        self.recovery_end_packet = self.last_sent_packet;
+        self.prr.on_congestion_event(*self.bytes_in_flight);
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.3.2
         //# Implementations MAY reduce the congestion window immediately upon
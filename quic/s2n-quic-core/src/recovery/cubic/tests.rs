@@ -4,6 +4,7 @@ use super::*;
 use crate::{
     packet::number::PacketNumberSpace,
     time::{Clock, NoopClock},
+    varint::VarInt,
 };
 use core::time::Duration;
 
@@ -270,6 +271,39 @@ fn on_packet_sent() {
     assert_delta!(cc.slow_start.threshold, 100_000.0, 0.001);
 }
 
+#[test]
+fn max_bytes_in_flight() {
+    let mut cc = CubicCongestionController::new(1000);
+    let rtt_estimator = RttEstimator::default();
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+
+    cc.congestion_window = 100_000.0;
+    assert_eq!(cc.max_bytes_in_flight(), 0);
+
+    for i in 1..=5 {
+        cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+        assert_eq!(cc.max_bytes_in_flight(), i * 1000);
+    }
+
+    // Bytes in flight decreasing doesn't lower the watermark
+    cc.on_ack(now, 3000, (), &rtt_estimator, random, now);
+    assert_eq!(cc.bytes_in_flight, 2000);
+    assert_eq!(cc.max_bytes_in_flight(), 5000);
+
+    // Congestion events don't reset the watermark
+    cc.on_packet_lost(1000, (), false, false, random, now);
+    assert_eq!(cc.bytes_in_flight, 1000);
+    assert_eq!(cc.max_bytes_in_flight(), 5000);
+
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+    assert_eq!(cc.max_bytes_in_flight(), 5000);
+
+    // A new peak raises the watermark
+    cc.on_packet_sent(now, 4000, None, &rtt_estimator);
+    assert_eq!(cc.max_bytes_in_flight(), 6000);
+}
+
 #[test]
 fn on_packet_sent_application_limited() {
     let mut cc = CubicCongestionController::new(1000);
@@ -473,6 +507,151 @@ fn congestion_avoidance_after_idle_period() {
     }
 }
 
+#[test]
+fn pacing_rate() {
+    let max_datagram_size = 1200;
+    let mut cc = CubicCongestionController::new(max_datagram_size);
+    let now = NoopClock.get_time();
+    let mut rtt_estimator = RttEstimator::default();
+
+    // Before an RTT sample, the rate is based on the initial window and initial RTT
+    // rate = 2 * 12_000 / 333ms
+    assert_eq!(cc.congestion_window(), 12_000);
+    assert_eq!(cc.pacing_rate(&rtt_estimator), 72_072);
+
+    rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(100),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    cc.congestion_window = 100_000.0;
+    cc.state = State::congestion_avoidance(now);
+    cc.on_packet_sent(now, 1200, None, &rtt_estimator);
+
+    // rate = 1.25 * 100_000 / 100ms
+    assert_eq!(cc.pacing_rate(&rtt_estimator), 1_250_000);
+}
+
+#[test]
+fn consecutive_congestion_events_fast_convergence() {
+    let max_datagram_size = 1200;
+    let mut cc = CubicCongestionController::new(max_datagram_size);
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+    cc.bytes_in_flight = BytesInFlight::new(200);
+    cc.congestion_window = 100_000.0;
+    cc.state = State::congestion_avoidance(now);
+
+    // The first congestion event has no previous W_max, so only the normal reduction applies
+    cc.on_packet_lost(100, (), false, false, random, now);
+    assert_delta!(cc.congestion_window, 100_000.0 * BETA_CUBIC, 0.001);
+    assert_delta!(cc.cubic.w_max, 100_000.0 / max_datagram_size as f32, 0.001);
+    assert_delta!(cc.cubic.w_last_max, cc.cubic.w_max, 0.001);
+
+    // Exit recovery without the window growing back to the previous W_max
+    let now = now + Duration::from_secs(1);
+    cc.state = State::congestion_avoidance(now);
+    cc.congestion_window = 80_000.0;
+
+    // The second congestion event occurs at a lower window, so W_max is further reduced
+    cc.on_packet_lost(100, (), false, false, random, now);
+    assert_delta!(cc.congestion_window, 80_000.0 * BETA_CUBIC, 0.001);
+    assert_delta!(
+        cc.cubic.w_last_max,
+        80_000.0 / max_datagram_size as f32,
+        0.001
+    );
+    // W_max = W_max*(1.0+beta_cubic)/2.0 = W_max * .85
+    assert_delta!(
+        cc.cubic.w_max,
+        80_000.0 * 0.85 / max_datagram_size as f32,
+        0.001
+    );
+}
+
+#[test]
+fn congestion_debounce() {
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+
+    for debounce in [false, true] {
+        let mut cc = CubicCongestionController::new(1000).with_congestion_debounce(debounce);
+        cc.bytes_in_flight = BytesInFlight::new(10_000);
+        cc.congestion_window = 100_000.0;
+        cc.state = State::congestion_avoidance(now);
+        assert_eq!(cc.smoothed_rtt, DEFAULT_INITIAL_RTT);
+
+        cc.on_packet_lost(100, (), false, false, random, now);
+        assert_delta!(cc.congestion_window, 70_000.0, 0.001);
+
+        // Exit recovery and signal congestion again within one RTT of the first event
+        let now = now + Duration::from_millis(100);
+        cc.state = State::congestion_avoidance(now);
+        cc.on_packet_lost(100, (), false, false, random, now);
+
+        if debounce {
+            // The second event is ignored, so only one reduction occurred
+            assert_delta!(cc.congestion_window, 70_000.0, 0.001);
+            assert!(matches!(cc.state, CongestionAvoidance(_)));
+        } else {
+            assert_delta!(cc.congestion_window, 70_000.0 * BETA_CUBIC, 0.001);
+            assert!(matches!(cc.state, Recovery(_, _)));
+            continue;
+        }
+
+        // An event more than one RTT after the last reduction reduces the window
+        let now = now + DEFAULT_INITIAL_RTT;
+        cc.on_packet_lost(100, (), false, false, random, now);
+        assert_delta!(cc.congestion_window, 70_000.0 * BETA_CUBIC, 0.001);
+        assert!(matches!(cc.state, Recovery(_, _)));
+    }
+}
+
+#[test]
+fn recovery_end_packet() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+    let rtt_estimator = RttEstimator::new(Duration::from_millis(0));
+    let space = PacketNumberSpace::ApplicationData;
+    cc.congestion_window = 100_000.0;
+    cc.state = State::congestion_avoidance(now);
+
+    for packet_number in 1..=3 {
+        cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+        cc.on_packet_number_sent(space.new_packet_number(VarInt::from_u8(packet_number)));
+    }
+    assert_eq!(cc.recovery_end_packet(), None);
+
+    // Entering recovery records the most recently sent packet
+    let recovery_start = now + Duration::from_millis(10);
+    cc.on_packet_lost(1000, (), false, false, random, recovery_start);
+    assert!(matches!(cc.state, Recovery(_, _)));
+    let recovery_end_packet = Some(space.new_packet_number(VarInt::from_u8(3)));
+    assert_eq!(cc.recovery_end_packet(), recovery_end_packet);
+
+    // Packets sent during recovery do not move the end of the recovery period
+    let sent_during_recovery = recovery_start + Duration::from_millis(1);
+    cc.on_packet_sent(sent_during_recovery, 1000, None, &rtt_estimator);
+    cc.on_packet_number_sent(space.new_packet_number(VarInt::from_u8(4)));
+    assert_eq!(cc.recovery_end_packet(), recovery_end_packet);
+
+    // Acknowledging a packet sent during recovery ends the recovery period
+    cc.under_utilized = false;
+    cc.on_ack(
+        sent_during_recovery,
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery + Duration::from_millis(100),
+    );
+    assert!(matches!(cc.state, CongestionAvoidance(_)));
+    assert_eq!(cc.recovery_end_packet(), None);
+}
+
 #[test]
 fn congestion_avoidance_after_fast_convergence() {
     let max_datagram_size = 1200;
@@ -616,6 +795,298 @@ fn on_packet_lost() {
     assert_delta!(cc.slow_start.threshold, 100_000.0 * BETA_CUBIC, 0.001);
 }
 
+#[test]
+fn send_budget_slow_start() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+
+    // Nothing has been sent, so the full congestion window is available
+    assert_eq!(cc.send_budget(now), 10_000);
+
+    cc.on_packet_sent(now, 4000, None, &rtt_estimator);
+    assert!(cc.is_slow_start());
+    assert_eq!(cc.send_budget(now), 6000);
+
+    cc.on_packet_sent(now, 6000, None, &rtt_estimator);
+    assert_eq!(cc.send_budget(now), 0);
+}
+
+#[test]
+fn send_budget_recovery() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+
+    cc.on_packet_sent(now, 50_000, None, &rtt_estimator);
+    cc.on_packet_lost(1000, (), false, false, random, now);
+
+    // The congestion window has space, but PRR has not allowed any transmission yet
+    assert_eq!(cc.congestion_window(), 70_000);
+    assert_eq!(cc.bytes_in_flight, 49_000);
+    assert_eq!(cc.send_budget(now), 0);
+
+    // PRR-SSRB allows MAX(prr_delivered - prr_out, DeliveredData) + MSS
+    cc.on_ack(now, 2000, (), &rtt_estimator, random, now);
+    assert_eq!(cc.send_budget(now), 3000);
+}
+
+#[test]
+fn send_budget_recovery_pacing() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+
+    cc.on_packet_sent(now, 50_000, None, &rtt_estimator);
+    cc.on_packet_lost(1000, (), false, false, random, now);
+    cc.on_ack(now, 2000, (), &rtt_estimator, random, now);
+    assert!(matches!(cc.state, Recovery(_, _)));
+
+    // PRR is the binding constraint
+    assert_eq!(cc.prr.bytes_allowed_on_ack(), 3000);
+    assert_eq!(cc.pacer.available_bytes(now, 1000), Some(10_000));
+    assert_eq!(cc.send_budget(now), 3000);
+
+    // Sending starts a new departure time slot, so the pacer is the binding constraint
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+    assert_eq!(cc.prr.bytes_allowed_on_ack(), 2000);
+    assert_eq!(cc.pacer.available_bytes(now, 1000), Some(0));
+    assert_eq!(cc.send_budget(now), 0);
+
+    // Once the departure time is reached, the remaining PRR allowance may be sent
+    let departure_time = cc.earliest_departure_time().unwrap();
+    assert!(departure_time > now);
+    assert_eq!(cc.send_budget(departure_time), 2000);
+}
+
+#[test]
+fn send_budget_requires_fast_retransmission() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+
+    cc.on_packet_sent(now, 10_000, None, &rtt_estimator);
+    cc.on_packet_lost(1000, (), false, false, random, now);
+
+    // The congestion window is full, but a fast retransmission is still allowed
+    assert!(cc.requires_fast_retransmission());
+    assert!(cc.congestion_window() < cc.bytes_in_flight());
+    assert_eq!(cc.send_budget(now), 1000);
+
+    // Once the fast retransmission is sent, the congestion window applies again
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+    assert!(!cc.requires_fast_retransmission());
+    assert_eq!(cc.send_budget(now), 0);
+}
+
+#[test]
+fn send_budget_pacing() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    cc.congestion_window = 100_000.0;
+
+    // The first burst of packets may be sent immediately
+    cc.on_packet_sent(now, 10_000, None, &rtt_estimator);
+    assert_eq!(cc.send_budget(now), 10_000);
+
+    // The next burst is delayed by the pacer
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+    assert_eq!(cc.send_budget(now), 0);
+
+    let departure_time = cc.earliest_departure_time().unwrap();
+    assert!(departure_time > now);
+    assert_eq!(cc.send_budget(departure_time), 9000);
+}
+
+#[test]
+fn send_budget_send_quantum() {
+    let now = NoopClock.get_time();
+    let mut low_rtt_estimator = RttEstimator::default();
+    // Pacing is not used on low RTT networks, so only the congestion window is rounded
+    low_rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(1),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+
+    let mut cc = CubicCongestionController::new(1000).with_send_quantum(4000);
+    cc.congestion_window = 100_000.0;
+    cc.on_packet_sent(now, 1000, None, &low_rtt_estimator);
+
+    // 99_000 bytes of the window are available, which is rounded down to 24 quanta
+    assert_eq!(cc.send_budget(now), 96_000);
+
+    // Less than one quantum of window remains
+    cc.on_packet_sent(now, 96_000, None, &low_rtt_estimator);
+    assert_eq!(cc.congestion_window() - *cc.bytes_in_flight, 3000);
+    assert_eq!(cc.send_budget(now), 0);
+
+    // A window smaller than the quantum is not rounded down when nothing is in flight,
+    // so transmission can't stall
+    let mut cc = CubicCongestionController::new(1000).with_send_quantum(64_000);
+    assert_eq!(cc.send_budget(now), 10_000);
+
+    cc.on_packet_sent(now, 1000, None, &low_rtt_estimator);
+    assert_eq!(cc.send_budget(now), 0);
+}
+
+#[test]
+fn snapshot() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+
+    assert_eq!(
+        cc.snapshot(),
+        ControllerState {
+            cwnd: 100_000,
+            ssthresh: u32::MAX,
+            bytes_in_flight: 0,
+            state: Phase::SlowStart,
+            prr: None,
+        }
+    );
+
+    cc.on_packet_sent(now, 50_000, None, &rtt_estimator);
+    assert_eq!(cc.snapshot().bytes_in_flight, 50_000);
+
+    // A loss enters recovery and initializes PRR
+    let recovery_start = now + Duration::from_secs(1);
+    cc.on_packet_lost(1000, (), false, false, random, recovery_start);
+
+    let snapshot = cc.snapshot();
+    assert_eq!(snapshot.state, Phase::Recovery);
+    assert_eq!(snapshot.cwnd, 70_000);
+    assert_eq!(snapshot.ssthresh, 70_000);
+    assert_eq!(snapshot.bytes_in_flight, 49_000);
+    let prr = snapshot.prr.expect("PRR state is reported during recovery");
+    assert_eq!(prr.bytes_in_flight_at_recovery, 49_000);
+    assert_eq!(prr.bytes_sent_during_recovery, 0);
+
+    let sent_during_recovery = recovery_start + Duration::from_millis(500);
+    cc.on_packet_sent(sent_during_recovery, 1000, None, &rtt_estimator);
+    cc.on_ack(
+        now,
+        10_000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery,
+    );
+
+    let prr = cc.snapshot().prr.unwrap();
+    assert_eq!(prr.bytes_sent_during_recovery, 1000);
+    assert_eq!(prr.bytes_delivered_during_recovery, 10_000);
+
+    // Acknowledging a packet sent during recovery ends the recovery period
+    cc.under_utilized = false;
+    cc.on_ack(
+        sent_during_recovery,
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery + Duration::from_millis(100),
+    );
+
+    let snapshot = cc.snapshot();
+    assert_eq!(snapshot.state, Phase::CongestionAvoidance);
+    assert_eq!(snapshot.bytes_in_flight, 39_000);
+    assert_eq!(snapshot.prr, None);
+
+    // The PRR counters are cleared for the next recovery period
+    assert_eq!(cc.prr, Prr::new().with_max_datagram_size(1000));
+}
+
+// Packets from every packet number space share the congestion controller, so a loss
+// reported by a different space during a recovery period must not restart PRR
+#[test]
+fn multi_space_losses_during_recovery() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+
+    // Handshake packets are sent, followed by ApplicationData packets
+    cc.on_packet_sent(now, 2_000, None, &rtt_estimator);
+    cc.on_packet_sent(now, 48_000, None, &rtt_estimator);
+
+    // An ApplicationData packet is lost, which starts the recovery period
+    let recovery_start = now + Duration::from_secs(1);
+    cc.on_packet_lost(1000, (), false, true, random, recovery_start);
+    assert_eq!(cc.snapshot().state, Phase::Recovery);
+    assert_eq!(cc.congestion_window(), 70_000);
+
+    // ApplicationData packets are acknowledged and new data is sent
+    let sent_during_recovery = recovery_start + Duration::from_millis(100);
+    cc.on_ack(
+        now,
+        10_000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery,
+    );
+    cc.on_packet_sent(sent_during_recovery, 1000, None, &rtt_estimator);
+    let prr = cc.snapshot().prr.unwrap();
+    assert_eq!(prr.bytes_in_flight_at_recovery, 49_000);
+    assert_eq!(prr.bytes_delivered_during_recovery, 10_000);
+    assert_eq!(prr.bytes_sent_during_recovery, 1000);
+
+    // A Handshake packet sent before the recovery period is declared lost
+    cc.on_packet_lost(
+        1000,
+        (),
+        false,
+        true,
+        random,
+        sent_during_recovery + Duration::from_millis(10),
+    );
+
+    // The recovery period and its PRR state are retained
+    assert_eq!(cc.congestion_window(), 70_000);
+    let prr = cc.snapshot().prr.unwrap();
+    assert_eq!(prr.bytes_in_flight_at_recovery, 49_000);
+    assert_eq!(prr.bytes_delivered_during_recovery, 10_000);
+    assert_eq!(prr.bytes_sent_during_recovery, 1000);
+
+    // The remaining Handshake packet is acknowledged and counts as delivered in PRR
+    cc.on_ack(
+        now,
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery + Duration::from_millis(20),
+    );
+    let prr = cc.snapshot().prr.unwrap();
+    assert_eq!(prr.bytes_delivered_during_recovery, 11_000);
+
+    // Acknowledging the packet sent during recovery ends the recovery period
+    cc.under_utilized = false;
+    cc.on_ack(
+        sent_during_recovery,
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        sent_during_recovery + Duration::from_millis(100),
+    );
+    assert_eq!(cc.snapshot().state, Phase::CongestionAvoidance);
+    assert_eq!(cc.snapshot().prr, None);
+}
+
 #[test]
 fn on_packet_lost_below_minimum_window() {
     let mut cc = CubicCongestionController::new(1000);
@@ -673,6 +1144,34 @@ fn on_packet_lost_persistent_congestion() {
     assert_eq!(cc.cubic.k, Duration::from_millis(0));
 }
 
+// Persistent congestion may be declared outside of a recovery period, in which case the
+// slow start threshold is reduced by the congestion event before the window collapses
+#[test]
+fn on_packet_lost_persistent_congestion_outside_recovery() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+    cc.congestion_window = 100_000.0;
+    cc.bytes_in_flight = BytesInFlight::new(50_000);
+    cc.state = SlowStart;
+
+    cc.on_packet_lost(1000, (), true, false, random, now);
+
+    // The controller re-enters slow start with the minimum window
+    assert_eq!(cc.state, SlowStart);
+    assert_delta!(cc.congestion_window, 2.0 * 1000.0, 0.001);
+    assert_eq!(cc.congestion_window(), 2000);
+
+    // Slow start exits at the reduced window rather than growing without bound
+    assert_delta!(cc.slow_start.threshold, 100_000.0 * BETA_CUBIC, 0.001);
+    assert_eq!(cc.snapshot().ssthresh, 70_000);
+
+    // The recovery period started by the loss is abandoned, so PRR is cleared
+    assert!(!cc.prr.is_active());
+    assert_eq!(cc.prr, Prr::new().with_max_datagram_size(1000));
+    assert_eq!(cc.snapshot().prr, None);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
 //= type=test
 //# If the maximum datagram size changes during the connection, the
@@ -726,6 +1225,44 @@ fn on_packet_discarded() {
     assert_eq!(Recovery(now, FastRetransmission::Idle), cc.state);
 }
 
+#[test]
+fn on_remembered_congestion_window() {
+    let mut cc = CubicCongestionController::new(1000);
+    assert_eq!(cc.congestion_window(), 10_000);
+
+    // A resumed controller starts with the remembered window
+    cc.on_remembered_congestion_window(80_000);
+    assert_eq!(cc.congestion_window(), 80_000);
+    assert!(cc.is_slow_start());
+
+    // Out of bounds values are clamped
+    let mut cc = CubicCongestionController::new(1000);
+    cc.on_remembered_congestion_window(1000);
+    assert_eq!(cc.congestion_window(), 10_000);
+
+    let mut cc = CubicCongestionController::new(1000);
+    cc.on_remembered_congestion_window(u32::MAX);
+    assert_eq!(cc.congestion_window(), 1_000_000);
+}
+
+#[allow(clippy::float_cmp)]
+#[test]
+fn on_packet_discarded_no_congestion_response() {
+    let mut cc = CubicCongestionController::new(5000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    cc.congestion_window = 100_000.0;
+    cc.on_packet_sent(now, 20_000, None, &rtt_estimator);
+
+    cc.on_packet_discarded(5000);
+
+    // Discarding only removes the packets from flight
+    assert_eq!(cc.bytes_in_flight, 15_000);
+    assert_eq!(cc.congestion_window(), 100_000);
+    assert_eq!(cc.state, SlowStart);
+    assert_eq!(cc.slow_start.threshold, f32::MAX);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
 //= type=test
 //# When bytes in flight is smaller than the congestion window and
@@ -939,6 +1476,52 @@ fn on_packet_ack_slow_start_to_congestion_avoidance() {
     );
 }
 
+#[test]
+fn on_packet_ack_limited_slow_start() {
+    let max_datagram_size = 1200;
+    let max_ssthresh = 100 * max_datagram_size as u32;
+    let mut cc =
+        CubicCongestionController::new(max_datagram_size).with_limited_slow_start(max_ssthresh);
+    let now = NoopClock.get_time();
+    let random = &mut random::testing::Generator::default();
+
+    cc.state = SlowStart;
+    cc.under_utilized = false;
+    cc.bytes_in_flight_hi = BytesInFlight::new(1_000_000);
+
+    let mut ack = |cc: &mut CubicCongestionController, congestion_window: f32| {
+        cc.congestion_window = congestion_window;
+        cc.bytes_in_flight = BytesInFlight::new(max_datagram_size as u32);
+        cc.on_ack(
+            now,
+            max_datagram_size as usize,
+            (),
+            &RttEstimator::default(),
+            random,
+            now + Duration::from_millis(2),
+        );
+        cc.congestion_window - congestion_window
+    };
+
+    // Below max_ssthresh, the window grows by the bytes acknowledged
+    assert_delta!(ack(&mut cc, 12_000.0), 1200.0, 0.001);
+    assert_delta!(ack(&mut cc, max_ssthresh as f32), 1200.0, 0.001);
+
+    // Above max_ssthresh, K = int(cwnd / (0.5 * max_ssthresh))
+    // K = int(150_000 / 60_000) = 2
+    assert_delta!(ack(&mut cc, 150_000.0), 600.0, 0.001);
+    // K = int(240_000 / 60_000) = 4
+    assert_delta!(ack(&mut cc, 240_000.0), 300.0, 0.001);
+    assert_eq!(cc.state, SlowStart);
+
+    // Without Limited Slow Start, growth is not capped
+    let mut cc = CubicCongestionController::new(max_datagram_size);
+    cc.state = SlowStart;
+    cc.under_utilized = false;
+    cc.bytes_in_flight_hi = BytesInFlight::new(1_000_000);
+    assert_delta!(ack(&mut cc, 240_000.0), 1200.0, 0.001);
+}
+
 #[test]
 fn on_packet_ack_recovery() {
     let mut cc = CubicCongestionController::new(5000);
@@ -1108,3 +1691,116 @@ fn on_packet_ack_congestion_avoidance_too_large_increase() {
     assert!(cc.cubic.w_cubic(t) > cc.cubic.w_est(t, rtt));
     assert_delta!(cc.congestion_window, 3_600_000.0 + 1000.0 / 2.0, 0.001);
 }
+
+#[test]
+fn invariant_checks() {
+    let mut cc = CubicCongestionController::new(1000).with_invariant_checks(true);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+
+    // Exercise slow start, recovery, congestion avoidance and persistent congestion
+    cc.on_packet_sent(now, 5000, None, &rtt_estimator);
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now);
+    cc.on_packet_lost(1000, (), false, false, random, now);
+    cc.on_packet_sent(now + Duration::from_millis(1), 1000, None, &rtt_estimator);
+    cc.on_ack(
+        now + Duration::from_millis(1),
+        1000,
+        (),
+        &rtt_estimator,
+        random,
+        now + Duration::from_millis(2),
+    );
+    cc.on_mtu_update(1200);
+    cc.on_packet_lost(
+        1000,
+        (),
+        true,
+        false,
+        random,
+        now + Duration::from_millis(3),
+    );
+    cc.on_packet_discarded(2000);
+
+    assert_eq!(cc.bytes_in_flight, 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "is below the minimum window")]
+fn invariant_checks_violation() {
+    let mut cc = CubicCongestionController::new(1000).with_invariant_checks(true);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+
+    // Break the minimum window invariant, which is detected by the next operation
+    cc.congestion_window = 1000.0;
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+}
+
+#[test]
+fn phase_durations() {
+    let mut cc = CubicCongestionController::new(1000);
+    let t0 = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+    let ms = Duration::from_millis;
+
+    // Time is not counted until the first packet is sent
+    assert_eq!(cc.phase_durations(t0 + ms(50)), PhaseDurations::default());
+    cc.on_packet_sent(t0, 1000, None, &rtt_estimator);
+
+    // Slow start -> Recovery
+    cc.on_packet_lost(1000, (), false, false, random, t0 + ms(100));
+    cc.on_packet_sent(t0 + ms(150), 1000, None, &rtt_estimator);
+
+    // Recovery -> Congestion Avoidance
+    cc.under_utilized = false;
+    cc.on_ack(t0 + ms(150), 1000, (), &rtt_estimator, random, t0 + ms(300));
+    assert!(matches!(cc.state, CongestionAvoidance(_)));
+    cc.on_packet_sent(t0 + ms(900), 1000, None, &rtt_estimator);
+
+    assert_eq!(
+        cc.phase_durations(t0 + ms(1000)),
+        PhaseDurations {
+            slow_start: ms(100),
+            congestion_avoidance: ms(700),
+            recovery: ms(200),
+        }
+    );
+
+    // Persistent congestion returns to slow start without spending time in recovery
+    cc.on_packet_lost(1000, (), true, false, random, t0 + ms(1000));
+    assert!(matches!(cc.state, SlowStart));
+
+    assert_eq!(
+        cc.phase_durations(t0 + ms(1500)),
+        PhaseDurations {
+            slow_start: ms(600),
+            congestion_avoidance: ms(700),
+            recovery: ms(200),
+        }
+    );
+}
+
+#[test]
+fn on_ack_more_than_bytes_in_flight() {
+    let mut cc = CubicCongestionController::new(1000);
+    let now = NoopClock.get_time();
+    let rtt_estimator = RttEstimator::default();
+    let random = &mut random::testing::Generator::default();
+
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+
+    // Acknowledging bytes that were never in flight doesn't underflow
+    cc.on_ack(now, 3000, (), &rtt_estimator, random, now);
+    assert_eq!(cc.bytes_in_flight, 0);
+
+    cc.on_ack(now, 1000, (), &rtt_estimator, random, now);
+    assert_eq!(cc.bytes_in_flight, 0);
+
+    // Bytes in flight continue to be counted from zero
+    cc.on_packet_sent(now, 1000, None, &rtt_estimator);
+    assert_eq!(cc.bytes_in_flight, 1000);
+}
@@ -0,0 +1,138 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An estimator for the fraction of packets marked with ECN-CE
+//!
+//! Rather than reacting to every increase in the CE count, controllers such as
+//! DCTCP (https://www.rfc-editor.org/rfc/rfc8257) and L4S respond in proportion to
+//! the fraction of packets the network has marked. This estimator maintains an
+//! exponentially weighted moving average of that fraction from the cumulative ECN
+//! counts reported in ACK frames.
+
+use crate::{frame::ack::EcnCounts, number::CheckedSub};
+
+/// The weight given to each new sample
+///
+/// This matches the estimation gain recommended for DCTCP in RFC 8257 Section 4.2.
+const DEFAULT_GAIN: f32 = 1.0 / 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EcnMarkRate {
+    /// The smoothed fraction of packets that were marked with CE
+    rate: f32,
+    /// The weight given to each new sample
+    gain: f32,
+    /// The ECN counts from the most recently processed ACK frame
+    baseline: EcnCounts,
+}
+
+impl Default for EcnMarkRate {
+    fn default() -> Self {
+        Self::new(DEFAULT_GAIN)
+    }
+}
+
+impl EcnMarkRate {
+    /// Creates a new estimator with the given `gain` applied to each sample
+    pub fn new(gain: f32) -> Self {
+        debug_assert!(
+            gain > 0.0 && gain <= 1.0,
+            "gain must be in the range (0, 1]"
+        );
+
+        Self {
+            rate: 0.0,
+            gain,
+            baseline: EcnCounts::default(),
+        }
+    }
+
+    /// Updates the estimate with the cumulative ECN counts from an ACK frame
+    pub fn on_ack(&mut self, ecn_counts: EcnCounts) {
+        // ECN counts only increase, so a decrease indicates the ACK frame was
+        // reordered and has already been accounted for
+        let newly_acked = match ecn_counts.checked_sub(self.baseline) {
+            Some(newly_acked) => newly_acked,
+            None => return,
+        };
+        self.baseline = ecn_counts;
+
+        let marked = newly_acked.ce_count.as_u64();
+        let total = marked
+            .saturating_add(newly_acked.ect_0_count.as_u64())
+            .saturating_add(newly_acked.ect_1_count.as_u64());
+
+        if total == 0 {
+            // No ECN-marked packets were newly acknowledged
+            return;
+        }
+
+        let fraction = marked as f32 / total as f32;
+        self.rate = (1.0 - self.gain) * self.rate + self.gain * fraction;
+    }
+
+    /// Returns the smoothed fraction of packets that were marked with CE, from 0.0 to 1.0
+    #[inline]
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+    use crate::varint::VarInt;
+
+    fn counts(ect_0_count: u32, ce_count: u32) -> EcnCounts {
+        EcnCounts {
+            ect_0_count: VarInt::from_u32(ect_0_count),
+            ect_1_count: VarInt::from_u32(0),
+            ce_count: VarInt::from_u32(ce_count),
+        }
+    }
+
+    #[test]
+    fn converges() {
+        let mut mark_rate = EcnMarkRate::default();
+        assert_eq!(mark_rate.rate(), 0.0);
+
+        // Half of each round of packets is marked
+        let mut ect_0_count = 0;
+        let mut ce_count = 0;
+        for _ in 0..200 {
+            ect_0_count += 5;
+            ce_count += 5;
+            mark_rate.on_ack(counts(ect_0_count, ce_count));
+        }
+        assert!((mark_rate.rate() - 0.5).abs() < 0.001);
+
+        // Marking stops, so the rate decays towards zero
+        for _ in 0..200 {
+            ect_0_count += 10;
+            mark_rate.on_ack(counts(ect_0_count, ce_count));
+        }
+        assert!(mark_rate.rate() < 0.001);
+    }
+
+    #[test]
+    fn single_sample() {
+        let mut mark_rate = EcnMarkRate::new(0.25);
+
+        // Every newly acknowledged packet was marked
+        mark_rate.on_ack(counts(0, 4));
+        assert_eq!(mark_rate.rate(), 0.25);
+
+        // An ACK frame without new ECN counts is not a sample
+        mark_rate.on_ack(counts(0, 4));
+        assert_eq!(mark_rate.rate(), 0.25);
+
+        // Reordered acks with lower counts are ignored
+        mark_rate.on_ack(counts(0, 2));
+        assert_eq!(mark_rate.rate(), 0.25);
+
+        // None of the newly acknowledged packets were marked
+        mark_rate.on_ack(counts(4, 4));
+        assert_eq!(mark_rate.rate(), 0.1875);
+    }
+}
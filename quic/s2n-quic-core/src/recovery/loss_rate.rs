@@ -0,0 +1,130 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An estimator for the fraction of packets that were declared lost
+//!
+//! The loss rate is computed as `lost / (acked + lost)` over a sliding window
+//! of recently resolved packets. The window is approximated with two buckets:
+//! once the current bucket has resolved `window` packets, it replaces the
+//! previous bucket and a new one is started. The rate is then computed over both
+//! buckets, so it always reflects between `window` and `2 * window` packets once
+//! enough packets have been resolved.
+
+/// The default number of packets in each bucket of the sliding window
+const DEFAULT_WINDOW: u32 = 1000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Bucket {
+    acked: u32,
+    lost: u32,
+}
+
+impl Bucket {
+    #[inline]
+    fn total(&self) -> u32 {
+        self.acked + self.lost
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LossRate {
+    /// The number of packets resolved in each bucket before it is rotated
+    window: u32,
+    /// The packets resolved since the last rotation
+    current: Bucket,
+    /// The packets resolved in the bucket before the current one
+    previous: Bucket,
+}
+
+impl Default for LossRate {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl LossRate {
+    /// Creates a new estimator with buckets of `window` packets
+    pub fn new(window: u32) -> Self {
+        debug_assert!(window > 0, "window must be non-zero");
+
+        Self {
+            window,
+            current: Bucket::default(),
+            previous: Bucket::default(),
+        }
+    }
+
+    /// Called when `count` packets are newly acknowledged
+    #[inline]
+    pub fn on_ack(&mut self, count: u32) {
+        self.current.acked = self.current.acked.saturating_add(count);
+        self.rotate();
+    }
+
+    /// Called when `count` packets are declared lost
+    #[inline]
+    pub fn on_loss(&mut self, count: u32) {
+        self.current.lost = self.current.lost.saturating_add(count);
+        self.rotate();
+    }
+
+    /// Returns the fraction of packets in the window that were lost, from 0.0 to 1.0
+    ///
+    /// 0.0 is returned if no packets have been acknowledged or lost.
+    #[inline]
+    pub fn rate(&self) -> f32 {
+        let lost = self.current.lost as u64 + self.previous.lost as u64;
+        let total = self.current.total() as u64 + self.previous.total() as u64;
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        lost as f32 / total as f32
+    }
+
+    #[inline]
+    fn rotate(&mut self) {
+        if self.current.total() >= self.window {
+            self.previous = core::mem::take(&mut self.current);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let loss_rate = LossRate::default();
+        assert_eq!(loss_rate.rate(), 0.0);
+    }
+
+    #[test]
+    fn known_mix() {
+        let mut loss_rate = LossRate::new(100);
+
+        // 1 in every 10 packets is lost
+        for _ in 0..5 {
+            loss_rate.on_ack(9);
+            loss_rate.on_loss(1);
+        }
+        assert!((loss_rate.rate() - 0.1).abs() < 0.001);
+
+        // Losses stop, so older samples fall out of the window
+        for _ in 0..20 {
+            loss_rate.on_ack(10);
+        }
+        assert_eq!(loss_rate.rate(), 0.0);
+
+        // Every packet is lost, completing a bucket with half of its packets lost
+        loss_rate.on_loss(50);
+        assert_eq!(loss_rate.rate(), 0.5);
+        loss_rate.on_loss(50);
+        assert!((loss_rate.rate() - 100.0 / 150.0).abs() < 0.001);
+        loss_rate.on_loss(50);
+        assert_eq!(loss_rate.rate(), 1.0);
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    packet::number::PacketNumberSpace,
+    time::{
+        timer::{self, Provider as _},
+        Timer, Timestamp,
+    },
+};
+
+/// Stores a loss timer for each packet number space
+///
+/// Loss detection must be armed for the earliest deadline across all of the packet
+/// number spaces. This aggregate tracks each deadline individually and reports the
+/// earliest one along with the space it belongs to.
+#[derive(Clone, Debug, Default)]
+pub struct LossTimers {
+    initial: Timer,
+    handshake: Timer,
+    application_data: Timer,
+}
+
+impl LossTimers {
+    /// Arms the timer for the given `space` to expire at `time`
+    #[inline]
+    pub fn set(&mut self, space: PacketNumberSpace, time: Timestamp) {
+        self.timer_mut(space).set(time)
+    }
+
+    /// Cancels the timer for the given `space`
+    #[inline]
+    pub fn cancel(&mut self, space: PacketNumberSpace) {
+        self.timer_mut(space).cancel()
+    }
+
+    /// Returns the deadline of the timer for the given `space`, if armed
+    #[inline]
+    pub fn get(&self, space: PacketNumberSpace) -> Option<Timestamp> {
+        self.timer(space).next_expiration()
+    }
+
+    /// Returns the earliest armed deadline and the space it belongs to
+    ///
+    /// If multiple spaces share the earliest deadline, the earlier space is returned.
+    #[inline]
+    pub fn earliest(&self) -> Option<(PacketNumberSpace, Timestamp)> {
+        let mut earliest: Option<(PacketNumberSpace, Timestamp)> = None;
+
+        for space in [
+            PacketNumberSpace::Initial,
+            PacketNumberSpace::Handshake,
+            PacketNumberSpace::ApplicationData,
+        ] {
+            if let Some(time) = self.get(space) {
+                if earliest.map_or(true, |(_, earliest)| time < earliest) {
+                    earliest = Some((space, time));
+                }
+            }
+        }
+
+        earliest
+    }
+
+    #[inline]
+    fn timer(&self, space: PacketNumberSpace) -> &Timer {
+        match space {
+            PacketNumberSpace::Initial => &self.initial,
+            PacketNumberSpace::Handshake => &self.handshake,
+            PacketNumberSpace::ApplicationData => &self.application_data,
+        }
+    }
+
+    #[inline]
+    fn timer_mut(&mut self, space: PacketNumberSpace) -> &mut Timer {
+        match space {
+            PacketNumberSpace::Initial => &mut self.initial,
+            PacketNumberSpace::Handshake => &mut self.handshake,
+            PacketNumberSpace::ApplicationData => &mut self.application_data,
+        }
+    }
+}
+
+impl timer::Provider for LossTimers {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.initial.timers(query)?;
+        self.handshake.timers(query)?;
+        self.application_data.timers(query)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{timer::Provider, Clock, NoopClock};
+    use core::time::Duration;
+
+    #[test]
+    fn earliest() {
+        let now = NoopClock.get_time();
+        let mut timers = LossTimers::default();
+        assert_eq!(timers.earliest(), None);
+
+        timers.set(
+            PacketNumberSpace::ApplicationData,
+            now + Duration::from_millis(10),
+        );
+        timers.set(
+            PacketNumberSpace::Handshake,
+            now + Duration::from_millis(20),
+        );
+        assert_eq!(
+            timers.earliest(),
+            Some((
+                PacketNumberSpace::ApplicationData,
+                now + Duration::from_millis(10)
+            ))
+        );
+        assert_eq!(
+            timers.next_expiration(),
+            Some(now + Duration::from_millis(10))
+        );
+        assert_eq!(timers.armed_timer_count(), 2);
+
+        // Cancelling the earliest timer moves to the next space
+        timers.cancel(PacketNumberSpace::ApplicationData);
+        assert_eq!(
+            timers.earliest(),
+            Some((
+                PacketNumberSpace::Handshake,
+                now + Duration::from_millis(20)
+            ))
+        );
+        assert_eq!(timers.get(PacketNumberSpace::ApplicationData), None);
+
+        // Ties are resolved in favor of the earlier space
+        timers.set(PacketNumberSpace::Initial, now + Duration::from_millis(20));
+        assert_eq!(
+            timers.earliest(),
+            Some((PacketNumberSpace::Initial, now + Duration::from_millis(20)))
+        );
+
+        timers.cancel(PacketNumberSpace::Initial);
+        timers.cancel(PacketNumberSpace::Handshake);
+        assert_eq!(timers.earliest(), None);
+        assert!(!timers.is_armed());
+    }
+}
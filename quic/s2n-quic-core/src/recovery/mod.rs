@@ -10,8 +10,13 @@ pub mod bandwidth;
 pub mod bbr;
 pub mod congestion_controller;
 pub mod cubic;
+pub mod ecn_mark_rate;
 mod hybrid_slow_start;
+pub mod loss_rate;
+pub mod loss_timers;
 mod pacing;
+pub mod prr;
+pub mod resumption;
 mod rtt_estimator;
 mod sent_packets;
 
@@ -42,15 +42,52 @@ const INITIAL_INTERVAL: Duration = Duration::from_millis(0);
 const MINIMUM_PACING_RTT: Duration = Duration::from_millis(2);
 
 /// A packet pacer that returns departure times that evenly distribute bursts of packets over time
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Pacer {
     // The capacity of the current departure time slot
     capacity: Counter<u32, Saturating>,
     // The time the next packet should be transmitted
     next_packet_departure_time: Option<Timestamp>,
+    // The number of packets that may be sent back-to-back in each departure time slot
+    max_burst: u16,
+    // Transmission is allowed in multiples of this number of bytes
+    send_quantum: u32,
+}
+
+impl Default for Pacer {
+    fn default() -> Self {
+        Self {
+            capacity: Counter::default(),
+            next_packet_departure_time: None,
+            max_burst: MAX_BURST_PACKETS,
+            send_quantum: 1,
+        }
+    }
 }
 
 impl Pacer {
+    /// Sets the number of packets that may be sent back-to-back before the pacing
+    /// interval is enforced
+    ///
+    /// Larger bursts reduce the number of timer wake ups required, which helps on systems
+    /// with coarse timers. Defaults to `MAX_BURST_PACKETS`.
+    pub fn with_max_burst(mut self, max_burst: u16) -> Self {
+        debug_assert_ne!(max_burst, 0, "max_burst must be at least one packet");
+        self.max_burst = max_burst.max(1);
+        self
+    }
+
+    /// Sets the number of bytes in each segment sent with generic segmentation offload (GSO)
+    ///
+    /// The bytes available in a departure time slot are rounded down to a multiple of the
+    /// send quantum, and each slot holds at least one quantum. A slot with less than one
+    /// quantum remaining is considered full.
+    pub fn with_send_quantum(mut self, send_quantum: u32) -> Self {
+        debug_assert_ne!(send_quantum, 0, "send_quantum must be at least one byte");
+        self.send_quantum = send_quantum.max(1);
+        self
+    }
+
     /// Called when each packet has been written
     #[inline]
     pub fn on_packet_sent(
@@ -66,9 +103,9 @@ impl Pacer {
             return;
         }
 
-        if self.capacity == 0 {
+        if self.is_slot_full() {
             if let Some(next_packet_departure_time) = self.next_packet_departure_time {
-                let interval = Self::interval(
+                let interval = self.interval(
                     rtt_estimator,
                     congestion_window,
                     max_datagram_size,
@@ -79,7 +116,7 @@ impl Pacer {
             } else {
                 self.next_packet_departure_time = Some(now + INITIAL_INTERVAL);
             }
-            self.capacity = Counter::new((MAX_BURST_PACKETS * max_datagram_size) as u32);
+            self.capacity = Counter::new(self.burst_size(max_datagram_size));
         }
 
         self.capacity -= bytes_sent as u32;
@@ -92,9 +129,96 @@ impl Pacer {
         self.next_packet_departure_time
     }
 
+    /// Returns the time the next packet may be transmitted
+    ///
+    /// If the packet may be transmitted at `now`, `None` is returned. Otherwise, the caller
+    /// can wait until the returned time rather than polling the pacer.
+    #[inline]
+    pub fn next_send_time(
+        &self,
+        now: Timestamp,
+        rtt_estimator: &RttEstimator,
+        congestion_window: u32,
+        max_datagram_size: u16,
+        slow_start: bool,
+    ) -> Option<Timestamp> {
+        if rtt_estimator.smoothed_rtt() < MINIMUM_PACING_RTT {
+            return None;
+        }
+
+        let mut departure_time = self.next_packet_departure_time?;
+
+        if self.is_slot_full() {
+            // The current departure time slot is full, so the next packet is sent in the
+            // following slot
+            departure_time += self.interval(
+                rtt_estimator,
+                congestion_window,
+                max_datagram_size,
+                slow_start,
+            );
+        }
+
+        if departure_time.has_elapsed(now) {
+            None
+        } else {
+            Some(departure_time)
+        }
+    }
+
+    /// Returns the number of bytes that may be transmitted at `now` before the pacer
+    /// delays further transmission.
+    ///
+    /// If the value is `None`, the pacer is not currently limiting transmission.
+    #[inline]
+    pub fn available_bytes(&self, now: Timestamp, max_datagram_size: u16) -> Option<usize> {
+        let departure_time = self.next_packet_departure_time?;
+
+        if !departure_time.has_elapsed(now) {
+            return Some(0);
+        }
+
+        if self.is_slot_full() {
+            // The next packet starts a new departure time slot
+            return Some(self.round_to_quantum(self.burst_size(max_datagram_size)) as usize);
+        }
+
+        Some(self.round_to_quantum(*self.capacity) as usize)
+    }
+
+    /// Returns the pacing rate in bytes per second for the given congestion window and RTT
+    #[inline]
+    pub fn rate(smoothed_rtt: Duration, congestion_window: u32, slow_start: bool) -> u64 {
+        let n = if slow_start { SLOW_START_N } else { N };
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
+        //# rate = N * congestion_window / smoothed_rtt
+        let smoothed_rtt = (smoothed_rtt.as_micros() as u64).max(1);
+        congestion_window as u64 * *n.0.numer() as u64 * 1_000_000
+            / (smoothed_rtt * *n.0.denom() as u64)
+    }
+
+    /// Returns the number of bytes that may be sent in each departure time slot
+    #[inline]
+    fn burst_size(&self, max_datagram_size: u16) -> u32 {
+        (self.max_burst as u32 * max_datagram_size as u32).max(self.send_quantum)
+    }
+
+    /// Returns true if the current departure time slot can't fit another quantum
+    #[inline]
+    fn is_slot_full(&self) -> bool {
+        *self.capacity < self.send_quantum
+    }
+
+    #[inline]
+    fn round_to_quantum(&self, bytes: u32) -> u32 {
+        bytes - bytes % self.send_quantum
+    }
+
     // Recalculate the interval between bursts of paced packets
     #[inline]
     fn interval(
+        &self,
         rtt_estimator: &RttEstimator,
         congestion_window: u32,
         max_datagram_size: u16,
@@ -104,9 +228,9 @@ impl Pacer {
 
         let n = if slow_start { SLOW_START_N } else { N };
 
-        // `MAX_BURST_PACKETS` is incorporated into the formula since we are trying to spread
+        // `max_burst` is incorporated into the formula since we are trying to spread
         // bursts of packets evenly over time.
-        let packet_size = (MAX_BURST_PACKETS * max_datagram_size) as u32;
+        let packet_size = self.burst_size(max_datagram_size);
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-7.7
         //# A perfectly paced sender spreads packets exactly evenly over time.
@@ -6,7 +6,7 @@ use crate::{
     path::MINIMUM_MTU,
     recovery::{
         pacing::{Pacer, INITIAL_INTERVAL, N, SLOW_START_N},
-        RttEstimator,
+        RttEstimator, MAX_BURST_PACKETS,
     },
     time::{Clock, NoopClock, Timestamp},
 };
@@ -216,3 +216,151 @@ fn get_interval(
         }
     }
 }
+
+#[test]
+fn next_send_time() {
+    let mut pacer = Pacer::default();
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = 12000;
+    let next_send_time =
+        |pacer: &Pacer, now| pacer.next_send_time(now, &rtt, cwnd, MINIMUM_MTU, false);
+
+    // Nothing has been sent, so a packet may be sent immediately
+    assert_eq!(None, next_send_time(&pacer, now));
+
+    // Packets may be sent immediately until the departure time slot is full
+    for _ in 0..MAX_BURST_PACKETS {
+        assert_eq!(None, next_send_time(&pacer, now));
+        pacer.on_packet_sent(now, MINIMUM_MTU as usize, &rtt, cwnd, MINIMUM_MTU, false);
+    }
+
+    let last_send = now + INITIAL_INTERVAL;
+    let interval = pacer.interval(&rtt, cwnd, MINIMUM_MTU, false);
+    assert!(interval > Duration::ZERO);
+    let expected = last_send + interval;
+    assert_eq!(Some(expected), next_send_time(&pacer, now));
+    assert_eq!(
+        Some(expected),
+        next_send_time(&pacer, expected - Duration::from_millis(1))
+    );
+
+    // Sending at the returned time starts the next departure time slot
+    assert_eq!(None, next_send_time(&pacer, expected));
+    pacer.on_packet_sent(
+        expected,
+        MINIMUM_MTU as usize,
+        &rtt,
+        cwnd,
+        MINIMUM_MTU,
+        false,
+    );
+    assert_eq!(Some(expected), pacer.earliest_departure_time());
+    assert_eq!(None, next_send_time(&pacer, expected));
+
+    // Pacing is not used on low RTT networks
+    let mut low_rtt = RttEstimator::default();
+    low_rtt.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(1),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    assert_eq!(
+        None,
+        pacer.next_send_time(now, &low_rtt, cwnd, MINIMUM_MTU, false)
+    );
+}
+
+#[test]
+fn max_burst() {
+    let max_burst = 3;
+    let mut pacer = Pacer::default().with_max_burst(max_burst);
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = MINIMUM_MTU as u32 * 100;
+    let next_send_time =
+        |pacer: &Pacer, now| pacer.next_send_time(now, &rtt, cwnd, MINIMUM_MTU, false);
+
+    // Packets may be sent back-to-back up to the configured burst size
+    for _ in 0..max_burst {
+        assert_eq!(None, next_send_time(&pacer, now));
+        pacer.on_packet_sent(now, MINIMUM_MTU as usize, &rtt, cwnd, MINIMUM_MTU, false);
+    }
+
+    // The next packet is delayed by the interval
+    let interval = pacer.interval(&rtt, cwnd, MINIMUM_MTU, false);
+    assert!(interval > Duration::ZERO);
+    let expected = now + INITIAL_INTERVAL + interval;
+    assert_eq!(Some(expected), next_send_time(&pacer, now));
+
+    // The interval spreads the smaller bursts evenly, so it is proportional to the burst size
+    let default_interval = Pacer::default().interval(&rtt, cwnd, MINIMUM_MTU, false);
+    assert_eq!(
+        interval,
+        default_interval * max_burst as u32 / MAX_BURST_PACKETS as u32
+    );
+
+    // A new burst may be sent once the interval has elapsed
+    pacer.on_packet_sent(
+        expected,
+        MINIMUM_MTU as usize,
+        &rtt,
+        cwnd,
+        MINIMUM_MTU,
+        false,
+    );
+    assert_eq!(Some(expected), pacer.earliest_departure_time());
+    assert_eq!(
+        Some((max_burst as usize - 1) * MINIMUM_MTU as usize),
+        pacer.available_bytes(expected, MINIMUM_MTU)
+    );
+}
+
+#[test]
+fn send_quantum() {
+    let send_quantum = 5000;
+    let mut pacer = Pacer::default().with_send_quantum(send_quantum);
+    let now = NoopClock.get_time();
+    let rtt = RttEstimator::default();
+    let cwnd = MINIMUM_MTU as u32 * 100;
+    let burst_size = MAX_BURST_PACKETS as usize * MINIMUM_MTU as usize;
+
+    pacer.on_packet_sent(now, MINIMUM_MTU as usize, &rtt, cwnd, MINIMUM_MTU, false);
+
+    // The remaining capacity in the slot is rounded down to two quanta
+    assert_eq!(burst_size - MINIMUM_MTU as usize, 10_800);
+    assert_eq!(Some(10_000), pacer.available_bytes(now, MINIMUM_MTU));
+
+    // Less than a quantum remains, so the slot is full and the next packet is delayed
+    pacer.on_packet_sent(now, 6000, &rtt, cwnd, MINIMUM_MTU, false);
+    let interval = pacer.interval(&rtt, cwnd, MINIMUM_MTU, false);
+    assert_eq!(
+        Some(now + INITIAL_INTERVAL + interval),
+        pacer.next_send_time(now, &rtt, cwnd, MINIMUM_MTU, false)
+    );
+
+    // A slot always has room for at least one quantum
+    let mut pacer = Pacer::default().with_send_quantum(64_000);
+    pacer.on_packet_sent(now, MINIMUM_MTU as usize, &rtt, cwnd, MINIMUM_MTU, false);
+    assert_eq!(Some(64_000), pacer.available_bytes(now, MINIMUM_MTU));
+    assert!(64_000 > burst_size);
+}
+
+#[test]
+fn rate() {
+    // rate = N * congestion_window / smoothed_rtt = 1.25 * 100_000 / 100ms
+    assert_eq!(
+        Pacer::rate(Duration::from_millis(100), 100_000, false),
+        1_250_000
+    );
+    // rate = 2 * 100_000 / 100ms
+    assert_eq!(
+        Pacer::rate(Duration::from_millis(100), 100_000, true),
+        2_000_000
+    );
+    assert_eq!(Pacer::rate(Duration::from_millis(100), 0, false), 0);
+    // A zero RTT does not result in dividing by zero
+    assert_eq!(Pacer::rate(Duration::ZERO, 1, false), 1_250_000);
+}
@@ -0,0 +1,593 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of Proportional Rate Reduction (PRR) as described in
+//! https://www.rfc-editor.org/rfc/rfc6937
+
+//= https://www.rfc-editor.org/rfc/rfc6937#section-1
+//# This document describes an experimental algorithm, PRR (Proportional
+//# Rate Reduction), to improve the accuracy of the amount of data sent
+//# by TCP during loss recovery.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Prr {
+    /// Total bytes sent during recovery (prr_out)
+    bytes_sent_during_recovery: usize,
+    /// Total bytes delivered during recovery (prr_delivered)
+    bytes_delivered_during_recovery: usize,
+    /// FlightSize at the start of recovery (RecoverFS)
+    bytes_in_flight_at_recovery: usize,
+    /// The number of bytes that may be sent in response to the most recent ack (sndcnt)
+    bytes_allowed_on_ack: usize,
+    /// The maximum datagram size (MSS) used for the reduction bound
+    max_datagram_size: u16,
+    /// The inputs to the reduction bound, if it was used for the most recent ack
+    reduction_bound: Option<ReductionBound>,
+    /// Indicates whether a recovery period is in progress
+    active: bool,
+}
+
+/// The values used to compute the PRR-SSRB allowance on the most recent ack
+///
+/// These are retained so the allowance can be recomputed if the maximum datagram
+/// size changes before the next ack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ReductionBound {
+    /// ssthresh - pipe
+    headroom: usize,
+    /// MAX(prr_delivered - prr_out, DeliveredData), excluding the MSS
+    limit: usize,
+    /// The bytes sent since the allowance was computed
+    bytes_sent: usize,
+}
+
+impl ReductionBound {
+    #[inline]
+    fn bytes_allowed(&self, max_datagram_size: u16) -> usize {
+        self.headroom
+            .min(self.limit.saturating_add(max_datagram_size as usize))
+            .saturating_sub(self.bytes_sent)
+    }
+}
+
+/// A copy of the values tracked by [`Prr`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrrState {
+    pub bytes_sent_during_recovery: usize,
+    pub bytes_delivered_during_recovery: usize,
+    pub bytes_in_flight_at_recovery: usize,
+    pub bytes_allowed_on_ack: usize,
+}
+
+impl Prr {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum datagram size used by `can_transmit` before the first ack or
+    /// MTU update is observed
+    pub fn with_max_datagram_size(mut self, max_datagram_size: u16) -> Self {
+        self.max_datagram_size = max_datagram_size;
+        self
+    }
+
+    /// Initializes the PRR state at the beginning of a recovery period
+    pub fn on_congestion_event(&mut self, bytes_in_flight: u32) {
+        //= https://www.rfc-editor.org/rfc/rfc6937#section-3.1
+        //# At the beginning of recovery, initialize PRR state.  This assumes a
+        //# modern congestion control algorithm, CongCtrlAlg(), that might set
+        //# ssthresh to something other than FlightSize/2:
+        //#
+        //#    ssthresh = CongCtrlAlg()  // Target cwnd after recovery
+        //#    prr_delivered = 0         // Total bytes delivered during recovery
+        //#    prr_out = 0               // Total bytes sent during recovery
+        //#    RecoverFS = snd.nxt-snd.una // FlightSize at the start of recovery
+        self.bytes_sent_during_recovery = 0;
+        self.bytes_delivered_during_recovery = 0;
+        self.bytes_in_flight_at_recovery = bytes_in_flight as usize;
+        self.bytes_allowed_on_ack = 0;
+        self.reduction_bound = None;
+        self.active = true;
+    }
+
+    /// Returns true if a recovery period is in progress
+    ///
+    /// PRR is active from `on_congestion_event` until `on_recovery_end`.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Clears the PRR state when the recovery period ends
+    ///
+    /// This prevents the counters from carrying over into the next recovery period,
+    /// even if the next congestion event is not preceded by `on_congestion_event`.
+    /// The maximum datagram size is retained, since it reflects the path rather than
+    /// the recovery period.
+    pub fn on_recovery_end(&mut self) {
+        *self = Self::new().with_max_datagram_size(self.max_datagram_size);
+    }
+
+    /// Called when a packet is transmitted
+    ///
+    /// This may be called for every packet; only bytes sent while PRR is active count
+    /// towards `prr_out`.
+    pub fn on_packet_sent(&mut self, bytes_sent: usize) {
+        if !self.active {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc6937#section-3.1
+        //# On any data transmission or retransmission:
+        //#    prr_out += (data sent) // strictly less than or equal to sndcnt
+        self.bytes_sent_during_recovery =
+            self.bytes_sent_during_recovery.saturating_add(bytes_sent);
+        self.bytes_allowed_on_ack = self.bytes_allowed_on_ack.saturating_sub(bytes_sent);
+
+        if let Some(reduction_bound) = self.reduction_bound.as_mut() {
+            reduction_bound.bytes_sent = reduction_bound.bytes_sent.saturating_add(bytes_sent);
+        }
+    }
+
+    /// Called for every ack received during recovery, excluding the ack that
+    /// concludes the recovery period
+    pub fn on_ack(
+        &mut self,
+        bytes_acknowledged: usize,
+        bytes_in_flight: u32,
+        slow_start_threshold: u32,
+        max_datagram_size: u16,
+    ) {
+        //= https://www.rfc-editor.org/rfc/rfc6937#section-3.1
+        //# On every ACK during recovery compute:
+        //#
+        //#    DeliveredData = change_in(snd.una) + change_in(SACKd)
+        //#    prr_delivered += DeliveredData
+        //#    pipe = (RFC 6675 pipe algorithm)
+        //#    if (pipe > ssthresh) {
+        //#       // Proportional Rate Reduction
+        //#       sndcnt = CEIL(prr_delivered * ssthresh / RecoverFS) - prr_out
+        //#    } else {
+        //#       // Two versions of the Reduction Bound
+        //#       if (conservative) {    // PRR-CRB
+        //#         limit = prr_delivered - prr_out
+        //#       } else {               // PRR-SSRB
+        //#         limit = MAX(prr_delivered - prr_out, DeliveredData) + MSS
+        //#       }
+        //#       // Attempt to catch up, as permitted by limit
+        //#       sndcnt = MIN(ssthresh - pipe, limit)
+        //#    }
+        self.bytes_delivered_during_recovery = self
+            .bytes_delivered_during_recovery
+            .saturating_add(bytes_acknowledged);
+        self.max_datagram_size = max_datagram_size;
+
+        let bytes_in_flight = bytes_in_flight as usize;
+        let slow_start_threshold = slow_start_threshold as usize;
+
+        if bytes_in_flight > slow_start_threshold {
+            // Avoid division by zero if recovery started with nothing in flight
+            let recover_fs = self.bytes_in_flight_at_recovery.max(1);
+            let target = self
+                .bytes_delivered_during_recovery
+                .saturating_mul(slow_start_threshold)
+                .saturating_add(recover_fs - 1)
+                / recover_fs;
+            self.bytes_allowed_on_ack = target.saturating_sub(self.bytes_sent_during_recovery);
+            self.reduction_bound = None;
+        } else {
+            // PRR-SSRB is used to allow the flight size to return to ssthresh
+            // more quickly after large losses
+            let reduction_bound = ReductionBound {
+                headroom: slow_start_threshold - bytes_in_flight,
+                limit: self
+                    .bytes_delivered_during_recovery
+                    .saturating_sub(self.bytes_sent_during_recovery)
+                    .max(bytes_acknowledged),
+                bytes_sent: 0,
+            };
+            self.bytes_allowed_on_ack = reduction_bound.bytes_allowed(max_datagram_size);
+            self.reduction_bound = Some(reduction_bound);
+        }
+    }
+
+    /// Called when the maximum datagram size changes
+    ///
+    /// If the allowance for the most recent ack was computed with the reduction bound,
+    /// it is recomputed so the `+ MSS` term reflects the new maximum datagram size.
+    pub fn on_mtu_update(&mut self, max_datagram_size: u16) {
+        self.max_datagram_size = max_datagram_size;
+
+        if let Some(reduction_bound) = self.reduction_bound {
+            self.bytes_allowed_on_ack = reduction_bound.bytes_allowed(max_datagram_size);
+        }
+    }
+
+    /// Called when bytes that were previously declared lost during the recovery
+    /// period are later determined to have been delivered
+    ///
+    /// The bytes are credited as delivered so PRR does not continue to throttle
+    /// transmission based on a loss that never occurred.
+    pub fn on_spurious_loss(&mut self, bytes: usize) {
+        self.bytes_delivered_during_recovery =
+            self.bytes_delivered_during_recovery.saturating_add(bytes);
+
+        // The spuriously lost bytes were counted against the sender when the
+        // allowance was last computed, so they may be released now
+        let credit = self
+            .bytes_delivered_during_recovery
+            .saturating_sub(self.bytes_sent_during_recovery)
+            .min(bytes);
+        self.bytes_allowed_on_ack = self.bytes_allowed_on_ack.saturating_add(credit);
+        // The allowance no longer follows the reduction bound, so it is not recomputed
+        // on MTU changes until the next ack
+        self.reduction_bound = None;
+    }
+
+    /// Returns a copy of the current PRR state
+    pub fn state(&self) -> PrrState {
+        PrrState {
+            bytes_sent_during_recovery: self.bytes_sent_during_recovery,
+            bytes_delivered_during_recovery: self.bytes_delivered_during_recovery,
+            bytes_in_flight_at_recovery: self.bytes_in_flight_at_recovery,
+            bytes_allowed_on_ack: self.bytes_allowed_on_ack,
+        }
+    }
+
+    /// Returns the number of bytes that may be sent in response to the most recent ack
+    pub fn bytes_allowed_on_ack(&self) -> usize {
+        self.bytes_allowed_on_ack
+    }
+
+    /// Returns true if a packet of the current maximum datagram size may be transmitted
+    ///
+    /// This is equivalent to calling `can_transmit_datagram` with the maximum datagram size
+    /// most recently provided to `with_max_datagram_size`, `on_ack` or `on_mtu_update`.
+    #[inline]
+    pub fn can_transmit(
+        &self,
+        in_recovery: bool,
+        bytes_in_flight: u32,
+        congestion_window: u32,
+    ) -> bool {
+        self.can_transmit_datagram(
+            self.max_datagram_size,
+            in_recovery,
+            bytes_in_flight,
+            congestion_window,
+        )
+    }
+
+    /// Returns true if a packet of the given size may be transmitted
+    ///
+    /// During recovery, PRR determines how much may be sent. Otherwise, the packet must
+    /// fit within the available congestion window.
+    ///
+    /// `datagram_size` must not exceed the maximum datagram size most recently provided to
+    /// `on_ack` or `on_mtu_update`.
+    pub fn can_transmit_datagram(
+        &self,
+        datagram_size: u16,
+        in_recovery: bool,
+        bytes_in_flight: u32,
+        congestion_window: u32,
+    ) -> bool {
+        if in_recovery {
+            debug_assert!(
+                self.max_datagram_size == 0 || datagram_size <= self.max_datagram_size,
+                "datagram size {} exceeds the max datagram size {}",
+                datagram_size,
+                self.max_datagram_size
+            );
+
+            self.bytes_allowed_on_ack >= datagram_size as usize
+        } else {
+            congestion_window.saturating_sub(bytes_in_flight) >= datagram_size as u32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MSS: u16 = 1000;
+
+    /// Replays a recovery period in which each ack delivers one segment
+    ///
+    /// After each ack, `bytes_allowed_on_ack` (sndcnt) is compared to the expected value
+    /// and as many full segments as PRR allows are sent. Returns the final bytes in flight.
+    fn replay(
+        prr: &mut Prr,
+        mut bytes_in_flight: u32,
+        slow_start_threshold: u32,
+        expected_sndcnt: &[usize],
+    ) -> u32 {
+        for (ack, expected) in expected_sndcnt.iter().enumerate() {
+            bytes_in_flight -= MSS as u32;
+            prr.on_ack(MSS as usize, bytes_in_flight, slow_start_threshold, MSS);
+            assert_eq!(prr.bytes_allowed_on_ack, *expected, "ack {}", ack + 1);
+
+            while prr.can_transmit_datagram(MSS, true, bytes_in_flight, slow_start_threshold) {
+                prr.on_packet_sent(MSS as usize);
+                bytes_in_flight += MSS as u32;
+            }
+        }
+
+        bytes_in_flight
+    }
+
+    // Mirrors the single loss scenario in https://www.rfc-editor.org/rfc/rfc6937#section-5
+    #[test]
+    fn rfc_example_single_loss() {
+        let mut prr = Prr::new();
+
+        // A window of 20 segments with a single loss, and ssthresh set to half the window
+        prr.on_congestion_event(20 * MSS as u32);
+        let bytes_in_flight = 19 * MSS as u32;
+        let slow_start_threshold = 10 * MSS as u32;
+
+        // While pipe is above ssthresh, PRR sends one segment for every two delivered:
+        // sndcnt = CEIL(prr_delivered * ssthresh / RecoverFS) - prr_out
+        //
+        // Once pipe reaches ssthresh, PRR-SSRB sends one segment for each delivered:
+        // sndcnt = MIN(ssthresh - pipe, MAX(prr_delivered - prr_out, DeliveredData) + MSS)
+        let bytes_in_flight = replay(
+            &mut prr,
+            bytes_in_flight,
+            slow_start_threshold,
+            &[
+                500, 1000, 500, 1000, 500, 1000, 500, 1000, 500, 1000, 500, 1000, 500, 1000, 500,
+                0, 1000, 1000, 1000,
+            ],
+        );
+
+        assert_eq!(prr.bytes_delivered_during_recovery, 19 * MSS as usize);
+        assert_eq!(prr.bytes_sent_during_recovery, 10 * MSS as usize);
+        assert_eq!(bytes_in_flight, slow_start_threshold);
+    }
+
+    // Mirrors the burst loss scenario in https://www.rfc-editor.org/rfc/rfc6937#section-5
+    #[test]
+    fn rfc_example_burst_loss() {
+        let mut prr = Prr::new();
+
+        // A window of 20 segments with 15 consecutive losses, and ssthresh set to half
+        // the window
+        prr.on_congestion_event(20 * MSS as u32);
+        let bytes_in_flight = 5 * MSS as u32;
+        let slow_start_threshold = 10 * MSS as u32;
+
+        // Pipe is below ssthresh, so PRR-SSRB sends two segments for each delivered,
+        // similar to slow start, until pipe reaches ssthresh
+        let bytes_in_flight = replay(
+            &mut prr,
+            bytes_in_flight,
+            slow_start_threshold,
+            &[2000, 2000, 2000, 2000, 2000],
+        );
+
+        assert_eq!(prr.bytes_delivered_during_recovery, 5 * MSS as usize);
+        assert_eq!(prr.bytes_sent_during_recovery, 10 * MSS as usize);
+        assert_eq!(bytes_in_flight, slow_start_threshold);
+
+        // Once pipe has reached ssthresh, one segment is sent for each delivered
+        replay(
+            &mut prr,
+            bytes_in_flight,
+            slow_start_threshold,
+            &[1000, 1000, 1000],
+        );
+    }
+
+    #[test]
+    fn default() {
+        let prr = Prr::default();
+        let new = Prr::new();
+
+        assert_eq!(
+            prr.bytes_sent_during_recovery,
+            new.bytes_sent_during_recovery
+        );
+        assert_eq!(
+            prr.bytes_delivered_during_recovery,
+            new.bytes_delivered_during_recovery
+        );
+        assert_eq!(
+            prr.bytes_in_flight_at_recovery,
+            new.bytes_in_flight_at_recovery
+        );
+        assert_eq!(prr.bytes_allowed_on_ack, new.bytes_allowed_on_ack);
+        assert_eq!(prr.bytes_allowed_on_ack(), 0);
+    }
+
+    #[test]
+    fn on_spurious_loss() {
+        let mut prr = Prr::new();
+
+        prr.on_congestion_event(10_000);
+        prr.on_packet_sent(1000);
+
+        // Pipe is above ssthresh, so the proportional reduction applies:
+        // CEIL(1000 * 5000 / 10000) - 1000 = 0
+        prr.on_ack(1000, 8000, 5000, 1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 0);
+        assert!(!prr.can_transmit_datagram(1000, true, 8000, 5000));
+
+        // The lost bytes were actually delivered
+        prr.on_spurious_loss(1000);
+        assert_eq!(prr.bytes_delivered_during_recovery, 2000);
+        assert!(prr.can_transmit_datagram(1000, true, 8000, 5000));
+
+        // Subsequent acks take the corrected delivery count into account:
+        // CEIL(3000 * 5000 / 10000) - 1000 = 500
+        prr.on_ack(1000, 7000, 5000, 1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 500);
+
+        // The correction never results in a negative allowance
+        let mut prr = Prr::new();
+        prr.on_congestion_event(10_000);
+        prr.on_packet_sent(5000);
+        prr.on_spurious_loss(1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 0);
+    }
+
+    #[test]
+    fn can_transmit() {
+        let mut prr = Prr::new();
+
+        // In recovery, PRR limits transmission even if the window has room
+        prr.on_congestion_event(10_000);
+        prr.on_ack(1000, 9000, 5000, 1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 500);
+        assert!(!prr.can_transmit_datagram(1000, true, 0, 20_000));
+        assert!(prr.can_transmit_datagram(500, true, 0, 20_000));
+
+        // PRR may allow transmission beyond the window during recovery
+        assert!(prr.can_transmit_datagram(500, true, 20_000, 20_000));
+
+        // Outside of recovery, the congestion window is used
+        assert!(prr.can_transmit_datagram(1000, false, 9000, 10_000));
+        assert!(!prr.can_transmit_datagram(1000, false, 9001, 10_000));
+        assert!(!prr.can_transmit_datagram(1000, false, 12_000, 10_000));
+    }
+
+    #[test]
+    fn can_transmit_max_datagram_size() {
+        let mut prr = Prr::new().with_max_datagram_size(MSS);
+
+        let check = |prr: &Prr, in_recovery: bool, bytes_in_flight: u32, congestion_window: u32| {
+            let expected = prr.can_transmit_datagram(
+                prr.max_datagram_size,
+                in_recovery,
+                bytes_in_flight,
+                congestion_window,
+            );
+            assert_eq!(
+                prr.can_transmit(in_recovery, bytes_in_flight, congestion_window),
+                expected
+            );
+            expected
+        };
+
+        // Outside of recovery, the configured size is compared to the available window
+        assert!(check(&prr, false, 9000, 10_000));
+        assert!(!check(&prr, false, 9001, 10_000));
+
+        // In recovery, the configured size is compared to the PRR allowance
+        prr.on_congestion_event(10_000);
+        prr.on_ack(1000, 9000, 5000, MSS);
+        assert_eq!(prr.bytes_allowed_on_ack, 500);
+        assert!(!check(&prr, true, 0, 20_000));
+        assert!(prr.can_transmit_datagram(500, true, 0, 20_000));
+
+        // A smaller MTU allows the remaining allowance to be used
+        prr.on_mtu_update(500);
+        assert!(check(&prr, true, 0, 20_000));
+
+        // The maximum datagram size outlives the recovery period
+        prr.on_recovery_end();
+        assert_eq!(prr.max_datagram_size, 500);
+        assert!(check(&prr, false, 9500, 10_000));
+        assert!(!check(&prr, false, 9501, 10_000));
+    }
+
+    #[test]
+    fn saturating_counters() {
+        let mut prr = Prr::new();
+        prr.on_congestion_event(10_000);
+
+        prr.on_packet_sent(usize::MAX - 1000);
+        prr.on_packet_sent(2000);
+        assert_eq!(prr.bytes_sent_during_recovery, usize::MAX);
+
+        // Pipe is above ssthresh
+        prr.on_ack(usize::MAX - 1000, 8000, 5000, 1000);
+        prr.on_ack(2000, 8000, 5000, 1000);
+        assert_eq!(prr.bytes_delivered_during_recovery, usize::MAX);
+        assert_eq!(prr.bytes_allowed_on_ack, 0);
+
+        // Pipe is below ssthresh
+        prr.on_ack(usize::MAX, 4000, 5000, 1000);
+        assert_eq!(prr.bytes_delivered_during_recovery, usize::MAX);
+        assert_eq!(prr.bytes_allowed_on_ack, 1000);
+    }
+
+    #[test]
+    fn on_mtu_update() {
+        let mut prr = Prr::new();
+        prr.on_congestion_event(10_000);
+
+        // Pipe is below ssthresh, so the reduction bound applies:
+        // MIN(5000 - 3000, MAX(100 - 0, 100) + 1000) = 1100
+        prr.on_ack(100, 3000, 5000, 1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 1100);
+        assert!(prr.can_transmit_datagram(1000, true, 3000, 5000));
+
+        // The MTU increases before the next ack, so the MSS term uses the new size:
+        // MIN(2000, 100 + 1200) = 1300
+        prr.on_mtu_update(1200);
+        assert_eq!(prr.bytes_allowed_on_ack, 1300);
+        assert!(prr.can_transmit_datagram(1200, true, 3000, 5000));
+
+        // Bytes sent since the ack are still accounted for when the MTU decreases:
+        // MIN(2000, 100 + 1000) - 1200 = 0
+        prr.on_packet_sent(1200);
+        assert_eq!(prr.bytes_allowed_on_ack, 100);
+        prr.on_mtu_update(1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 0);
+        assert!(!prr.can_transmit_datagram(1000, true, 4200, 5000));
+
+        // The proportional reduction does not depend on the MTU
+        let mut prr = Prr::new();
+        prr.on_congestion_event(10_000);
+        prr.on_ack(1000, 9000, 5000, 1000);
+        assert_eq!(prr.bytes_allowed_on_ack, 500);
+        prr.on_mtu_update(1200);
+        assert_eq!(prr.bytes_allowed_on_ack, 500);
+    }
+
+    #[test]
+    fn on_packet_sent_outside_recovery() {
+        let mut prr = Prr::new();
+        assert!(!prr.is_active());
+
+        // Bytes sent before recovery are not counted
+        prr.on_packet_sent(1000);
+        assert_eq!(prr.bytes_sent_during_recovery, 0);
+
+        prr.on_congestion_event(10_000);
+        assert!(prr.is_active());
+        prr.on_packet_sent(1000);
+        prr.on_packet_sent(500);
+        assert_eq!(prr.bytes_sent_during_recovery, 1500);
+
+        // Bytes sent after recovery are not counted
+        prr.on_recovery_end();
+        assert!(!prr.is_active());
+        prr.on_packet_sent(1000);
+        assert_eq!(prr.bytes_sent_during_recovery, 0);
+        assert_eq!(prr, Prr::new());
+    }
+
+    #[test]
+    fn on_recovery_end() {
+        let mut prr = Prr::new();
+
+        prr.on_congestion_event(10_000);
+        prr.on_packet_sent(1000);
+        prr.on_ack(2000, 7000, 5000, 1000);
+        assert_eq!(prr.bytes_sent_during_recovery, 1000);
+        assert_eq!(prr.bytes_delivered_during_recovery, 2000);
+
+        prr.on_recovery_end();
+        assert_eq!(prr, Prr::new().with_max_datagram_size(1000));
+
+        // Packets sent after recovery ends don't count towards the next recovery period
+        prr.on_packet_sent(1000);
+        prr.on_congestion_event(8000);
+        assert_eq!(prr.bytes_sent_during_recovery, 0);
+        assert_eq!(prr.bytes_delivered_during_recovery, 0);
+        assert_eq!(prr.bytes_in_flight_at_recovery, 8000);
+    }
+}
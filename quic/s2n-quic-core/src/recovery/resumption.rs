@@ -0,0 +1,138 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recovery state that may be carried from one connection to the next
+//!
+//! When a connection closes cleanly, its final congestion and RTT state can be
+//! exported as a [`ResumptionState`] and cached by the application, typically keyed
+//! by server name. A later connection to the same peer can then start from that
+//! state rather than from the conservative defaults.
+
+use crate::recovery::{CongestionController, RttEstimator};
+use core::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResumptionState {
+    /// The congestion window in bytes
+    pub congestion_window: u32,
+    /// The slow start threshold in bytes
+    pub slow_start_threshold: u32,
+    /// The smoothed round trip time
+    pub smoothed_rtt: Duration,
+    /// The variance in the observed RTT samples
+    pub rttvar: Duration,
+}
+
+impl ResumptionState {
+    /// Exports the current state of the given congestion controller and RTT estimator
+    pub fn new<CC: CongestionController>(
+        congestion_controller: &CC,
+        rtt_estimator: &RttEstimator,
+    ) -> Self {
+        let snapshot = congestion_controller.snapshot();
+
+        Self {
+            congestion_window: snapshot.cwnd,
+            slow_start_threshold: snapshot.ssthresh,
+            smoothed_rtt: rtt_estimator.smoothed_rtt(),
+            rttvar: rtt_estimator.rttvar(),
+        }
+    }
+
+    /// Creates an RTT estimator that starts from the exported smoothed RTT and RTT variance
+    ///
+    /// The initial RTT is clamped to the bounds supported by the estimator.
+    pub fn rtt_estimator(&self, max_ack_delay: Duration) -> RttEstimator {
+        RttEstimator::new_with_initial_rtt(max_ack_delay, self.smoothed_rtt)
+            .with_initial_rttvar(self.rttvar)
+    }
+
+    /// Warms up a new congestion controller with the exported congestion window and slow
+    /// start threshold
+    ///
+    /// This must be called before any packets are sent with the congestion controller.
+    pub fn warm_up<CC: CongestionController>(&self, congestion_controller: &mut CC) {
+        congestion_controller.on_remembered_congestion_window(self.congestion_window);
+        if let Some(slow_start_threshold) = self.remembered_slow_start_threshold() {
+            congestion_controller.on_remembered_slow_start_threshold(slow_start_threshold);
+        }
+    }
+
+    /// Returns the exported slow start threshold, if the prior connection exited slow start
+    #[inline]
+    pub fn remembered_slow_start_threshold(&self) -> Option<u32> {
+        // A connection that never exited slow start has no threshold to carry over
+        Some(self.slow_start_threshold).filter(|&threshold| threshold != u32::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        packet::number::PacketNumberSpace,
+        recovery::CubicCongestionController,
+        time::{Clock, NoopClock},
+    };
+
+    #[test]
+    fn export_then_import() {
+        let mut now = NoopClock.get_time();
+        let max_ack_delay = Duration::from_millis(25);
+
+        let mut cc = CubicCongestionController::new(1200);
+        cc.on_remembered_congestion_window(60_000);
+
+        // Exit slow start so the slow start threshold is determined
+        cc.on_explicit_congestion(1, now);
+        assert!(!cc.is_slow_start());
+
+        // Take several samples so the RTT variance no longer tracks the smoothed RTT
+        let mut rtt_estimator = RttEstimator::new(max_ack_delay);
+        for rtt in [80, 120, 60, 100] {
+            now += Duration::from_millis(rtt);
+            rtt_estimator.update_rtt(
+                Duration::ZERO,
+                Duration::from_millis(rtt),
+                now,
+                true,
+                PacketNumberSpace::ApplicationData,
+            );
+        }
+        assert_ne!(rtt_estimator.rttvar(), rtt_estimator.smoothed_rtt() / 2);
+
+        let state = ResumptionState::new(&cc, &rtt_estimator);
+        assert_eq!(state.congestion_window, cc.congestion_window());
+        assert_eq!(state.slow_start_threshold, cc.snapshot().ssthresh);
+        assert_ne!(state.remembered_slow_start_threshold(), None);
+        assert_eq!(state.smoothed_rtt, rtt_estimator.smoothed_rtt());
+        assert_eq!(state.rttvar, rtt_estimator.rttvar());
+
+        let mut resumed_cc = CubicCongestionController::new(1200);
+        state.warm_up(&mut resumed_cc);
+        let resumed_rtt_estimator = state.rtt_estimator(max_ack_delay);
+
+        assert_eq!(resumed_cc.congestion_window(), cc.congestion_window());
+        assert_eq!(resumed_cc.snapshot().ssthresh, cc.snapshot().ssthresh);
+        assert_eq!(
+            resumed_rtt_estimator.smoothed_rtt(),
+            rtt_estimator.smoothed_rtt()
+        );
+        assert_eq!(resumed_rtt_estimator.rttvar(), rtt_estimator.rttvar());
+        assert_eq!(
+            ResumptionState::new(&resumed_cc, &resumed_rtt_estimator),
+            state
+        );
+    }
+
+    #[test]
+    fn slow_start_not_exited() {
+        let cc = CubicCongestionController::new(1200);
+        let state = ResumptionState::new(&cc, &RttEstimator::default());
+        assert_eq!(state.remembered_slow_start_threshold(), None);
+
+        let mut resumed_cc = CubicCongestionController::new(1200);
+        state.warm_up(&mut resumed_cc);
+        assert_eq!(resumed_cc.snapshot().ssthresh, cc.snapshot().ssthresh);
+    }
+}
@@ -17,6 +17,12 @@ use core::{
 pub const DEFAULT_INITIAL_RTT: Duration = Duration::from_millis(333);
 const ZERO_DURATION: Duration = Duration::from_millis(0);
 
+/// The largest initial RTT that will be used for a connection
+///
+/// This bounds the PTO period used for the first packets sent when a
+/// remembered RTT from a prior connection is supplied.
+pub const MAX_INITIAL_RTT: Duration = Duration::from_secs(1);
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
 //# The RECOMMENDED value of the timer granularity (kGranularity) is 1 millisecond.
 pub const K_GRANULARITY: Duration = Duration::from_millis(1);
@@ -25,7 +31,7 @@ pub const K_GRANULARITY: Duration = Duration::from_millis(1);
 //# The RECOMMENDED value for kPersistentCongestionThreshold is 3, which
 //# results in behavior that is approximately equivalent to a TCP sender
 //# declaring an RTO after two TLPs.
-const K_PERSISTENT_CONGESTION_THRESHOLD: u32 = 3;
+pub const K_PERSISTENT_CONGESTION_THRESHOLD: u32 = 3;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct RttEstimator {
@@ -43,6 +49,17 @@ pub struct RttEstimator {
     max_ack_delay: Duration,
     /// The time that the first RTT sample was obtained
     first_rtt_sample: Option<Timestamp>,
+    /// The multiplier applied when computing the persistent congestion duration
+    /// (kPersistentCongestionThreshold)
+    persistent_congestion_multiplier: u32,
+    /// The granularity of the local timer used as the floor in loss delay and PTO computations
+    /// (kGranularity)
+    timer_granularity: Duration,
+    /// The RTT used before any RTT samples are available, and when the estimator is reset
+    initial_rtt: Duration,
+    /// The RTT variance used before any RTT samples are available, and when the estimator
+    /// is reset
+    initial_rttvar: Duration,
 }
 
 impl Default for RttEstimator {
@@ -54,6 +71,19 @@ impl Default for RttEstimator {
 impl RttEstimator {
     /// Creates a new RTT Estimator with default initial values using the given `max_ack_delay`.
     pub fn new(max_ack_delay: Duration) -> Self {
+        Self::new_with_initial_rtt(max_ack_delay, DEFAULT_INITIAL_RTT)
+    }
+
+    /// Creates a new RTT Estimator using the given `max_ack_delay` and `initial_rtt`
+    ///
+    /// The `initial_rtt` is clamped between `K_GRANULARITY` and `MAX_INITIAL_RTT`.
+    pub fn new_with_initial_rtt(max_ack_delay: Duration, initial_rtt: Duration) -> Self {
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2
+        //# Resumed connections over the same network MAY use the previous
+        //# connection's final smoothed RTT value as the resumed connection's
+        //# initial RTT.
+        let initial_rtt = initial_rtt.max(K_GRANULARITY).min(MAX_INITIAL_RTT);
+
         //= https://www.rfc-editor.org/rfc/rfc9002#section-5.3
         //# Before any RTT samples are available for a new path or when the
         //# estimator is reset, the estimator is initialized using the initial RTT;
@@ -64,19 +94,88 @@ impl RttEstimator {
         //
         //# smoothed_rtt = kInitialRtt
         //# rttvar = kInitialRtt / 2
-        let smoothed_rtt = DEFAULT_INITIAL_RTT;
-        let rttvar = DEFAULT_INITIAL_RTT / 2;
+        let smoothed_rtt = initial_rtt;
+        let rttvar = initial_rtt / 2;
 
         Self {
-            latest_rtt: DEFAULT_INITIAL_RTT,
-            min_rtt: DEFAULT_INITIAL_RTT,
+            latest_rtt: initial_rtt,
+            min_rtt: initial_rtt,
             smoothed_rtt,
             rttvar,
             max_ack_delay,
             first_rtt_sample: None,
+            persistent_congestion_multiplier: K_PERSISTENT_CONGESTION_THRESHOLD,
+            timer_granularity: K_GRANULARITY,
+            initial_rtt,
+            initial_rttvar: rttvar,
         }
     }
 
+    /// Sets the RTT variance used before any RTT samples are available
+    ///
+    /// A resumed connection may use the previous connection's final RTT variance rather
+    /// than half of the initial RTT. The `rttvar` is clamped to `MAX_INITIAL_RTT`.
+    #[inline]
+    pub fn with_initial_rttvar(mut self, rttvar: Duration) -> Self {
+        debug_assert!(
+            self.first_rtt_sample.is_none(),
+            "the initial rttvar should be set before any RTT samples are taken"
+        );
+        let rttvar = rttvar.min(MAX_INITIAL_RTT);
+        self.rttvar = rttvar;
+        self.initial_rttvar = rttvar;
+        self
+    }
+
+    /// Discards any RTT samples, returning the estimate to the initial RTT
+    ///
+    /// The `max_ack_delay`, persistent congestion threshold and timer granularity are retained.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self {
+            max_ack_delay: self.max_ack_delay,
+            persistent_congestion_multiplier: self.persistent_congestion_multiplier,
+            timer_granularity: self.timer_granularity,
+            rttvar: self.initial_rttvar,
+            initial_rttvar: self.initial_rttvar,
+            ..Self::new_with_initial_rtt(self.max_ack_delay, self.initial_rtt)
+        };
+    }
+
+    /// Sets the multiplier applied when computing the persistent congestion duration
+    ///
+    /// Larger values require losses to span a longer period before persistent
+    /// congestion is declared, which may be desirable on links with high random loss.
+    /// The `threshold` is raised to 1 if 0 is given.
+    #[inline]
+    pub fn with_persistent_congestion_threshold(mut self, threshold: u32) -> Self {
+        self.persistent_congestion_multiplier = threshold.max(1);
+        self
+    }
+
+    /// Gets the multiplier applied when computing the persistent congestion duration
+    #[inline]
+    pub fn persistent_congestion_multiplier(&self) -> u32 {
+        self.persistent_congestion_multiplier
+    }
+
+    /// Sets the timer granularity (kGranularity) used as the floor in loss delay and PTO
+    /// computations
+    ///
+    /// Platforms with timers coarser than the default of 1ms should set a larger value
+    /// to avoid spurious timeouts.
+    #[inline]
+    pub fn with_timer_granularity(mut self, timer_granularity: Duration) -> Self {
+        self.timer_granularity = timer_granularity;
+        self
+    }
+
+    /// Gets the timer granularity (kGranularity)
+    #[inline]
+    pub fn timer_granularity(&self) -> Duration {
+        self.timer_granularity
+    }
+
     /// Gets the latest round trip time sample
     #[inline]
     pub fn latest_rtt(&self) -> Duration {
@@ -118,26 +217,18 @@ impl RttEstimator {
     //# an acknowledgement of a sent packet.
     #[inline]
     pub fn pto_period(&self, pto_backoff: u32, space: PacketNumberSpace) -> Duration {
-        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
-        //# When an ack-eliciting packet is transmitted, the sender schedules a
-        //# timer for the PTO period as follows:
-        //#
-        //# PTO = smoothed_rtt + max(4*rttvar, kGranularity) + max_ack_delay
-        let mut pto_period = self.smoothed_rtt();
-
-        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
-        //# The PTO period MUST be at least kGranularity, to avoid the timer
-        //# expiring immediately.
-        pto_period += max(4 * self.rttvar(), K_GRANULARITY);
-
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# When the PTO is armed for Initial or Handshake packet number spaces,
         //# the max_ack_delay in the PTO period computation is set to 0, since
         //# the peer is expected to not delay these packets intentionally; see
         //# Section 13.2.1 of [QUIC-TRANSPORT].
-        if space.is_application_data() {
-            pto_period += self.max_ack_delay;
-        }
+        let max_ack_delay = if space.is_application_data() {
+            self.max_ack_delay
+        } else {
+            ZERO_DURATION
+        };
+
+        let mut pto_period = self.base_pto_period(max_ack_delay);
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
         //# Even when there are ack-eliciting packets in flight in multiple
@@ -152,6 +243,36 @@ impl RttEstimator {
         //# an acknowledgement of a sent packet.
         pto_period
     }
+
+    /// Computes the PTO period using the given `max_ack_delay`, backed off exponentially
+    /// by `2^pto_count`
+    ///
+    /// Before an RTT sample is available, the period is derived from the initial RTT.
+    #[inline]
+    pub fn pto(&self, max_ack_delay: Duration, pto_count: u32) -> Duration {
+        let backoff = 1u32.checked_shl(pto_count).unwrap_or(u32::MAX);
+        self.base_pto_period(max_ack_delay)
+            .checked_mul(backoff)
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Computes the PTO period before any backoff is applied
+    #[inline]
+    fn base_pto_period(&self, max_ack_delay: Duration) -> Duration {
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
+        //# When an ack-eliciting packet is transmitted, the sender schedules a
+        //# timer for the PTO period as follows:
+        //#
+        //# PTO = smoothed_rtt + max(4*rttvar, kGranularity) + max_ack_delay
+        let mut pto_period = self.smoothed_rtt();
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.1
+        //# The PTO period MUST be at least kGranularity, to avoid the timer
+        //# expiring immediately.
+        pto_period += max(4 * self.rttvar(), self.timer_granularity);
+
+        pto_period + max_ack_delay
+    }
 }
 
 impl RttEstimator {
@@ -169,21 +290,14 @@ impl RttEstimator {
         is_handshake_confirmed: bool,
         space: PacketNumberSpace,
     ) {
+        // Samples below 1ms can't be measured accurately (for example, after a clock
+        // adjustment), so they are clamped to 1ms rather than being rejected or driving
+        // the estimate to zero. This floor is independent of the configured timer
+        // granularity, which only applies to the loss delay and PTO period.
         self.latest_rtt = rtt_sample.max(Duration::from_millis(1));
 
         if self.first_rtt_sample.is_none() {
-            self.first_rtt_sample = Some(timestamp);
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
-            //# min_rtt MUST be set to the latest_rtt on the first RTT sample.
-            self.min_rtt = self.latest_rtt;
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-5.3
-            //# On the first RTT sample after initialization, smoothed_rtt and rttvar
-            //# are set as follows:
-            //#
-            //# smoothed_rtt = latest_rtt
-            //# rttvar = latest_rtt / 2
-            self.smoothed_rtt = self.latest_rtt;
-            self.rttvar = self.latest_rtt / 2;
+            self.on_first_sample(self.latest_rtt, timestamp);
             return;
         }
 
@@ -251,6 +365,27 @@ impl RttEstimator {
         self.rttvar = 3 * self.rttvar / 4 + rttvar_sample / 4;
     }
 
+    /// Initializes the estimate from the first RTT sample
+    ///
+    /// The first sample is typically taken in the Initial or Handshake packet number space,
+    /// before the handshake is confirmed. The acknowledgement delay and the peer's
+    /// `max_ack_delay` are not applied, so the sample is used as measured.
+    pub fn on_first_sample(&mut self, latest_rtt: Duration, timestamp: Timestamp) {
+        self.latest_rtt = latest_rtt;
+        self.first_rtt_sample = Some(timestamp);
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
+        //# min_rtt MUST be set to the latest_rtt on the first RTT sample.
+        self.min_rtt = latest_rtt;
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-5.3
+        //# On the first RTT sample after initialization, smoothed_rtt and rttvar
+        //# are set as follows:
+        //#
+        //# smoothed_rtt = latest_rtt
+        //# rttvar = latest_rtt / 2
+        self.smoothed_rtt = latest_rtt;
+        self.rttvar = latest_rtt / 2;
+    }
+
     /// Calculates the persistent congestion threshold used for determining
     /// if persistent congestion is being encountered.
     pub fn persistent_congestion_threshold(&self) -> Duration {
@@ -268,8 +403,8 @@ impl RttEstimator {
         //# establishing persistent congestion, including some in response to PTO
         //# expiration, as TCP does with Tail Loss Probes [RFC8985] and an RTO
         //# [RFC5681].
-        (self.smoothed_rtt + max(4 * self.rttvar, K_GRANULARITY) + self.max_ack_delay)
-            * K_PERSISTENT_CONGESTION_THRESHOLD
+        (self.smoothed_rtt + max(4 * self.rttvar, self.timer_granularity) + self.max_ack_delay)
+            * self.persistent_congestion_multiplier
     }
 
     /// Allows min_rtt and smoothed_rtt to be overwritten on the next RTT sample
@@ -295,7 +430,10 @@ mod test {
     use crate::{
         packet::number::PacketNumberSpace,
         path::INITIAL_PTO_BACKOFF,
-        recovery::{RttEstimator, DEFAULT_INITIAL_RTT, K_GRANULARITY},
+        recovery::{
+            RttEstimator, DEFAULT_INITIAL_RTT, K_GRANULARITY, K_PERSISTENT_CONGESTION_THRESHOLD,
+            MAX_INITIAL_RTT,
+        },
         time::{Clock, Duration, NoopClock},
         transport::parameters::MaxAckDelay,
         varint::VarInt,
@@ -323,6 +461,175 @@ mod test {
         );
     }
 
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2
+    //= type=test
+    //# Resumed connections over the same network MAY use the previous
+    //# connection's final smoothed RTT value as the resumed connection's
+    //# initial RTT.
+    #[test]
+    fn initial_rtt() {
+        let initial_rtt = Duration::from_millis(50);
+        let rtt_estimator = RttEstimator::new_with_initial_rtt(Duration::ZERO, initial_rtt);
+        assert_eq!(rtt_estimator.smoothed_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.min_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.rttvar(), initial_rtt / 2);
+        assert_eq!(rtt_estimator.first_rtt_sample(), None);
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Initial),
+            Duration::from_millis(150)
+        );
+
+        // Out of bounds values are clamped
+        let rtt_estimator = RttEstimator::new_with_initial_rtt(Duration::ZERO, Duration::ZERO);
+        assert_eq!(rtt_estimator.smoothed_rtt(), K_GRANULARITY);
+        let rtt_estimator =
+            RttEstimator::new_with_initial_rtt(Duration::ZERO, Duration::from_secs(30));
+        assert_eq!(rtt_estimator.smoothed_rtt(), MAX_INITIAL_RTT);
+
+        assert_eq!(
+            RttEstimator::new_with_initial_rtt(Duration::ZERO, DEFAULT_INITIAL_RTT),
+            RttEstimator::new(Duration::ZERO)
+        );
+    }
+
+    /// Test the configured initial RTT is only used until the first RTT sample
+    #[test]
+    fn initial_rtt_first_sample() {
+        let mut rtt_estimator =
+            RttEstimator::new_with_initial_rtt(Duration::ZERO, Duration::from_millis(50));
+
+        // PTO = 50ms + 4 * 25ms
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(150)
+        );
+
+        let now = NoopClock.get_time();
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(200),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        // The first sample replaces the initial RTT rather than being averaged with it
+        assert_eq!(rtt_estimator.first_rtt_sample(), Some(now));
+        assert_eq!(rtt_estimator.smoothed_rtt(), Duration::from_millis(200));
+        assert_eq!(rtt_estimator.rttvar(), Duration::from_millis(100));
+        assert_eq!(rtt_estimator.min_rtt(), Duration::from_millis(200));
+
+        // PTO = 200ms + 4 * 100ms
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(600)
+        );
+    }
+
+    /// Test resetting the estimator returns to the configured initial RTT
+    #[test]
+    fn reset() {
+        let max_ack_delay = Duration::from_millis(10);
+        let initial_rtt = Duration::from_millis(50);
+        let expected = RttEstimator::new_with_initial_rtt(max_ack_delay, initial_rtt)
+            .with_initial_rttvar(Duration::from_millis(10))
+            .with_persistent_congestion_threshold(5)
+            .with_timer_granularity(Duration::from_millis(5));
+        let mut rtt_estimator = expected;
+
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(200),
+            NoopClock.get_time(),
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+        assert_ne!(rtt_estimator, expected);
+
+        rtt_estimator.reset();
+
+        assert_eq!(rtt_estimator, expected);
+        assert_eq!(rtt_estimator.smoothed_rtt(), initial_rtt);
+        assert_eq!(rtt_estimator.rttvar(), Duration::from_millis(10));
+        assert_eq!(rtt_estimator.first_rtt_sample(), None);
+    }
+
+    /// Test the first sample initializes the estimate, while later samples are averaged
+    #[test]
+    fn on_first_sample() {
+        let now = NoopClock.get_time();
+        let max_ack_delay = Duration::from_millis(10);
+        let latest_rtt = Duration::from_millis(100);
+
+        let mut rtt_estimator = RttEstimator::new(max_ack_delay);
+        rtt_estimator.on_first_sample(latest_rtt, now);
+        assert_eq!(rtt_estimator.first_rtt_sample(), Some(now));
+        assert_eq!(rtt_estimator.latest_rtt(), latest_rtt);
+        assert_eq!(rtt_estimator.min_rtt(), latest_rtt);
+        assert_eq!(rtt_estimator.smoothed_rtt(), latest_rtt);
+        assert_eq!(rtt_estimator.rttvar(), latest_rtt / 2);
+
+        // A first sample passed to `update_rtt` ignores the ack delay, even if it is
+        // larger than max_ack_delay
+        let mut updated = RttEstimator::new(max_ack_delay);
+        updated.update_rtt(
+            Duration::from_millis(50),
+            latest_rtt,
+            now,
+            false,
+            PacketNumberSpace::Handshake,
+        );
+        assert_eq!(updated, rtt_estimator);
+
+        // Subsequent samples use the exponentially weighted moving averages, with the
+        // ack delay subtracted
+        let later = now + Duration::from_secs(1);
+        rtt_estimator.update_rtt(
+            Duration::from_millis(5),
+            Duration::from_millis(200),
+            later,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+        let adjusted_rtt = Duration::from_millis(195);
+        let smoothed_rtt = 7 * latest_rtt / 8 + adjusted_rtt / 8;
+        assert_eq!(rtt_estimator.smoothed_rtt(), smoothed_rtt);
+        assert_eq!(
+            rtt_estimator.rttvar(),
+            3 * (latest_rtt / 2) / 4 + (adjusted_rtt - smoothed_rtt) / 4
+        );
+        assert_eq!(rtt_estimator.min_rtt(), latest_rtt);
+        assert_eq!(rtt_estimator.first_rtt_sample(), Some(now));
+    }
+
+    /// Test the peer's max_ack_delay is included in the PTO period for ApplicationData
+    #[test]
+    fn max_ack_delay_pto_period() {
+        let mut rtt_estimator = RttEstimator::new(MaxAckDelay::default().as_duration());
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(1024)
+        );
+
+        rtt_estimator.on_max_ack_delay(MaxAckDelay::new(VarInt::from_u8(100)).unwrap());
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(1099)
+        );
+
+        // Initial and Handshake are not affected by the peer's max_ack_delay
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::Handshake),
+            Duration::from_millis(999)
+        );
+
+        rtt_estimator.on_max_ack_delay(MaxAckDelay::new(VarInt::from_u8(0)).unwrap());
+        assert_eq!(
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData),
+            Duration::from_millis(999)
+        );
+    }
+
     /// Test a zero RTT value is treated as 1 ms
     #[test]
     fn zero_rtt_sample() {
@@ -517,6 +824,78 @@ mod test {
         );
     }
 
+    /// Test a sample smaller than 1ms after the first sample is clamped
+    #[test]
+    fn too_small_rtt_sample() {
+        let mut rtt_estimator = RttEstimator::new(Duration::from_millis(10));
+        let now = NoopClock.get_time();
+
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(100),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_micros(10),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        assert_eq!(rtt_estimator.latest_rtt(), Duration::from_millis(1));
+        assert_eq!(rtt_estimator.min_rtt(), Duration::from_millis(1));
+        assert_eq!(
+            rtt_estimator.smoothed_rtt(),
+            7 * Duration::from_millis(100) / 8 + Duration::from_millis(1) / 8
+        );
+    }
+
+    /// Test an ack delay larger than the RTT sample is not subtracted from the sample
+    #[test]
+    fn ack_delay_exceeds_rtt_sample() {
+        let mut rtt_estimator = RttEstimator::new(Duration::from_millis(500));
+        let now = NoopClock.get_time();
+
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(100),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        let rtt_sample = Duration::from_millis(150);
+        rtt_estimator.update_rtt(
+            Duration::from_millis(400),
+            rtt_sample,
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        assert_eq!(rtt_estimator.latest_rtt(), rtt_sample);
+        assert_eq!(rtt_estimator.min_rtt(), Duration::from_millis(100));
+        assert_eq!(
+            rtt_estimator.smoothed_rtt(),
+            7 * Duration::from_millis(100) / 8 + rtt_sample / 8
+        );
+
+        // Prior to handshake confirmation the sample is ignored entirely
+        let smoothed_rtt = rtt_estimator.smoothed_rtt();
+        rtt_estimator.update_rtt(
+            Duration::from_millis(400),
+            rtt_sample,
+            now,
+            false,
+            PacketNumberSpace::Handshake,
+        );
+        assert_eq!(rtt_estimator.smoothed_rtt(), smoothed_rtt);
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-5.3
     //= type=test
     //# Therefore, prior to handshake
@@ -620,6 +999,72 @@ mod test {
         );
     }
 
+    #[test]
+    fn custom_persistent_congestion_threshold() {
+        let max_ack_delay = Duration::from_millis(10);
+        let mut rtt_estimator =
+            RttEstimator::new(max_ack_delay).with_persistent_congestion_threshold(5);
+        assert_eq!(rtt_estimator.persistent_congestion_multiplier(), 5);
+
+        rtt_estimator.smoothed_rtt = Duration::from_millis(100);
+        rtt_estimator.rttvar = Duration::from_millis(50);
+
+        // = (100 + max(4*50, 1) + 10) * 5 = 1550
+        assert_eq!(
+            Duration::from_millis(1550),
+            rtt_estimator.persistent_congestion_threshold()
+        );
+
+        // Losses spanning 1s establish persistent congestion with the default threshold
+        // of 3 (930ms), but not with the custom threshold
+        let lost_period = Duration::from_secs(1);
+        let default_rtt_estimator =
+            rtt_estimator.with_persistent_congestion_threshold(K_PERSISTENT_CONGESTION_THRESHOLD);
+        assert!(lost_period > default_rtt_estimator.persistent_congestion_threshold());
+        assert!(lost_period < rtt_estimator.persistent_congestion_threshold());
+
+        // A zero threshold would declare persistent congestion on any loss
+        let rtt_estimator = rtt_estimator.with_persistent_congestion_threshold(0);
+        assert_eq!(rtt_estimator.persistent_congestion_multiplier(), 1);
+        assert_eq!(
+            Duration::from_millis(310),
+            rtt_estimator.persistent_congestion_threshold()
+        );
+    }
+
+    #[test]
+    fn custom_timer_granularity() {
+        let max_ack_delay = Duration::from_millis(10);
+        let mut rtt_estimator = RttEstimator::new(max_ack_delay);
+        assert_eq!(rtt_estimator.timer_granularity(), K_GRANULARITY);
+
+        rtt_estimator.smoothed_rtt = Duration::from_millis(100);
+        rtt_estimator.rttvar = Duration::from_millis(1);
+
+        // = 100 + max(4*1, 1) + 10 = 114
+        assert_eq!(
+            Duration::from_millis(114),
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData)
+        );
+
+        // = 100 + max(4*1, 25) + 10 = 135
+        let rtt_estimator = rtt_estimator.with_timer_granularity(Duration::from_millis(25));
+        assert_eq!(rtt_estimator.timer_granularity(), Duration::from_millis(25));
+        assert_eq!(
+            Duration::from_millis(135),
+            rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, PacketNumberSpace::ApplicationData)
+        );
+        assert_eq!(
+            Duration::from_millis(135),
+            rtt_estimator.pto(max_ack_delay, 0)
+        );
+        // = 135 * 3 = 405
+        assert_eq!(
+            Duration::from_millis(405),
+            rtt_estimator.persistent_congestion_threshold()
+        );
+    }
+
     #[test]
     fn set_min_rtt_to_latest_sample_after_persistent_congestion() {
         let mut rtt_estimator = RttEstimator::new(Duration::from_millis(10));
@@ -678,4 +1123,57 @@ mod test {
         let pto_period = rtt_estimator.pto_period(INITIAL_PTO_BACKOFF, space);
         assert!(pto_period >= K_GRANULARITY);
     }
+
+    #[test]
+    fn pto_before_rtt_sample() {
+        let rtt_estimator = RttEstimator::default();
+
+        // smoothed_rtt = 333ms, rttvar = 166.5ms
+        assert_eq!(
+            rtt_estimator.pto(Duration::ZERO, 0),
+            Duration::from_millis(999)
+        );
+        assert_eq!(
+            rtt_estimator.pto(Duration::from_millis(25), 0),
+            Duration::from_millis(1024)
+        );
+    }
+
+    #[test]
+    fn pto_backoff() {
+        let mut rtt_estimator = RttEstimator::default();
+        let now = NoopClock.get_time();
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(100),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+
+        let max_ack_delay = Duration::from_millis(25);
+        // 100ms + 4 * 50ms + 25ms
+        let base = Duration::from_millis(325);
+
+        assert_eq!(rtt_estimator.pto(max_ack_delay, 0), base);
+        assert_eq!(rtt_estimator.pto(max_ack_delay, 1), base * 2);
+        assert_eq!(rtt_estimator.pto(max_ack_delay, 3), base * 8);
+
+        // The backoff matches the equivalent `pto_period` computation
+        let mut rtt_estimator = RttEstimator::new(max_ack_delay);
+        rtt_estimator.update_rtt(
+            Duration::ZERO,
+            Duration::from_millis(100),
+            now,
+            true,
+            PacketNumberSpace::ApplicationData,
+        );
+        assert_eq!(
+            rtt_estimator.pto(max_ack_delay, 2),
+            rtt_estimator.pto_period(4, PacketNumberSpace::ApplicationData)
+        );
+
+        // Large backoff values saturate instead of overflowing
+        assert!(rtt_estimator.pto(max_ack_delay, 64) >= rtt_estimator.pto(max_ack_delay, 31));
+    }
 }
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    frame::ack_elicitation::AckElicitation, inet::ExplicitCongestionNotification, path,
-    time::Timestamp, transmission,
+    frame::ack_elicitation::AckElicitation, inet::ExplicitCongestionNotification,
+    packet::number::PacketNumber, path, time::Timestamp, transmission,
 };
 use core::convert::TryInto;
 
@@ -14,6 +14,90 @@ use core::convert::TryInto;
 #[cfg(feature = "alloc")]
 pub type SentPackets<PacketInfo> = crate::packet::number::Map<SentPacketInfo<PacketInfo>>;
 
+#[cfg(feature = "alloc")]
+impl<PacketInfo: Copy> SentPackets<PacketInfo> {
+    /// Returns the info for every packet sent at least `threshold` packets before
+    /// `largest_acked`
+    ///
+    /// These packets are eligible to be declared lost based on the packet threshold.
+    pub fn collect_below_threshold(
+        &self,
+        largest_acked: PacketNumber,
+        threshold: u64,
+    ) -> alloc::vec::Vec<SentPacketInfo<PacketInfo>> {
+        self.iter()
+            .take_while(|(packet_number, _)| *packet_number < largest_acked)
+            .filter(|(packet_number, _)| largest_acked.distance(*packet_number) >= threshold)
+            .map(|(_, info)| *info)
+            .collect()
+    }
+
+    /// Returns an iterator over the sent packets ordered by the time they were sent
+    ///
+    /// Packets are currently sent in increasing packet number order, so this yields the
+    /// same entries as `iter`. Callers that depend on the send time ordering should use
+    /// this method rather than relying on that correlation.
+    pub fn iter_by_time(
+        &self,
+    ) -> impl Iterator<Item = (PacketNumber, &SentPacketInfo<PacketInfo>)> {
+        let mut packets: alloc::vec::Vec<_> = self.iter().collect();
+        // The sort is stable, so packets sent at the same time remain in packet number order
+        packets.sort_by_key(|(_, info)| info.time_sent);
+        packets.into_iter()
+    }
+
+    /// Returns the earliest in-flight packet at or after `packet_number`
+    ///
+    /// Packets that do not count towards bytes in flight are skipped. This is used to
+    /// select the packet whose frames should be retransmitted in a PTO probe.
+    pub fn first_in_flight_after(
+        &self,
+        packet_number: PacketNumber,
+    ) -> Option<(PacketNumber, &SentPacketInfo<PacketInfo>)> {
+        self.iter()
+            .skip_while(|(sent_packet_number, _)| *sent_packet_number < packet_number)
+            .find(|(_, info)| info.congestion_controlled)
+    }
+
+    /// Returns the packet number and send time of the earliest ack-eliciting packet that
+    /// has not yet been acknowledged or declared lost
+    ///
+    /// Packets that only contain non-ack-eliciting frames are skipped, as they do not
+    /// keep the PTO armed.
+    pub fn earliest_ack_eliciting(&self) -> Option<(PacketNumber, Timestamp)> {
+        self.iter()
+            .find(|(_, info)| info.ack_elicitation.is_ack_eliciting())
+            .map(|(packet_number, info)| (packet_number, info.time_sent))
+    }
+
+    /// Removes the given `lost` packets and returns them in packet number order
+    ///
+    /// The packet number is the reference to the frames carried in each packet, so the
+    /// returned packets are reported to the components that wrote their frames to be
+    /// retransmitted. Packets are returned in packet number order so the oldest frames are
+    /// retransmitted first. Packet numbers that are not being tracked are skipped.
+    pub fn take_lost(
+        &mut self,
+        lost: &[PacketNumber],
+    ) -> alloc::vec::Vec<(PacketNumber, SentPacketInfo<PacketInfo>)> {
+        let mut packets: alloc::vec::Vec<_> = lost
+            .iter()
+            .filter_map(|packet_number| {
+                self.remove(*packet_number)
+                    .map(|info| (*packet_number, info))
+            })
+            .collect();
+        packets.sort_by_key(|(packet_number, _)| *packet_number);
+        packets
+    }
+}
+
+/// Information about a packet that has been sent but not yet acknowledged or declared lost
+///
+/// The frames carried in the packet are not stored here. Components that write
+/// retransmittable frames record the packet number the frames were written in, and
+/// re-queue them when that packet number is reported lost. The packet number is
+/// therefore the reference from a sent packet to its frames.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct SentPacketInfo<PacketInfo> {
@@ -34,8 +118,19 @@ pub struct SentPacketInfo<PacketInfo> {
     pub transmission_mode: transmission::Mode,
     /// Additional packet metadata dictated by the congestion controller
     pub cc_packet_info: PacketInfo,
+    /// The packet number length in the low bits and the transmit count in the high bits
+    ///
+    /// These are packed into a single byte so the struct fits in the padding of the
+    /// other fields.
+    pn_len_and_transmit_count: u8,
 }
 
+const PN_LEN_BITS: u8 = 3;
+const PN_LEN_MASK: u8 = (1 << PN_LEN_BITS) - 1;
+
+/// The largest transmit count recorded for a sent packet
+pub const MAX_TRANSMIT_COUNT: u8 = u8::MAX >> PN_LEN_BITS;
+
 impl<PacketInfo> SentPacketInfo<PacketInfo> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -65,8 +160,91 @@ impl<PacketInfo> SentPacketInfo<PacketInfo> {
             ecn,
             transmission_mode,
             cc_packet_info,
+            pn_len_and_transmit_count: 1 << PN_LEN_BITS,
         }
     }
+
+    /// Returns the number of bytes (1 to 4) used to encode the truncated packet number,
+    /// or 0 if it was not recorded
+    #[inline]
+    pub fn pn_len(&self) -> u8 {
+        self.pn_len_and_transmit_count & PN_LEN_MASK
+    }
+
+    /// Returns the number of times the frames in this packet have been transmitted,
+    /// including this transmission
+    ///
+    /// This is 1 for packets carrying new frames, and is only used for diagnostics. The
+    /// count saturates at `MAX_TRANSMIT_COUNT`.
+    #[inline]
+    pub fn transmit_count(&self) -> u8 {
+        self.pn_len_and_transmit_count >> PN_LEN_BITS
+    }
+
+    /// Records the number of bytes used to encode the truncated packet number
+    #[inline]
+    pub fn with_pn_len(mut self, pn_len: u8) -> Self {
+        debug_assert!(
+            (1..=4).contains(&pn_len),
+            "packet numbers are encoded with 1 to 4 bytes"
+        );
+        self.pn_len_and_transmit_count =
+            (self.pn_len_and_transmit_count & !PN_LEN_MASK) | (pn_len & PN_LEN_MASK);
+        self
+    }
+
+    /// Records the number of times the frames in this packet have been transmitted
+    ///
+    /// Counts above `MAX_TRANSMIT_COUNT` are recorded as `MAX_TRANSMIT_COUNT`.
+    #[inline]
+    pub fn with_transmit_count(mut self, transmit_count: u8) -> Self {
+        debug_assert!(
+            transmit_count > 0,
+            "a sent packet is transmitted at least once"
+        );
+        let transmit_count = transmit_count.min(MAX_TRANSMIT_COUNT);
+        self.pn_len_and_transmit_count =
+            (transmit_count << PN_LEN_BITS) | (self.pn_len_and_transmit_count & PN_LEN_MASK);
+        self
+    }
+
+    /// Returns the `transmit_count` for a packet that retransmits the frames in this packet
+    ///
+    /// The count saturates at `MAX_TRANSMIT_COUNT` for frames that are retransmitted many
+    /// times.
+    #[inline]
+    pub fn next_transmit_count(&self) -> u8 {
+        (self.transmit_count() + 1).min(MAX_TRANSMIT_COUNT)
+    }
+
+    /// Returns true if the packet was sent as a probe when the PTO expired
+    ///
+    /// An acknowledgement of a probe confirms the path is still delivering packets,
+    /// but the probe may not have been sent in response to an available congestion window.
+    #[inline]
+    pub fn is_pto_probe(&self) -> bool {
+        self.transmission_mode.is_loss_recovery_probing()
+    }
+
+    /// Returns true if the packet was sent to probe for a larger path MTU
+    ///
+    /// MTU probes are expected to be lost when the probed size exceeds the path MTU, so
+    /// their loss frees the bytes in flight without triggering a congestion response.
+    #[inline]
+    pub fn is_mtu_probe(&self) -> bool {
+        self.transmission_mode.is_mtu_probing()
+    }
+
+    /// Returns the number of bytes the packet occupied on the wire, given the
+    /// per-packet UDP and IP `overhead`
+    ///
+    /// The overhead is applied at query time rather than stored, so the size of
+    /// `SentPacketInfo` is unaffected. `sent_bytes` remains the value used for
+    /// congestion control accounting.
+    #[inline]
+    pub fn wire_bytes(&self, overhead: u16) -> u32 {
+        self.sent_bytes as u32 + overhead as u32
+    }
 }
 
 #[cfg(test)]
@@ -74,11 +252,491 @@ mod test {
     use crate::{
         frame::ack_elicitation::AckElicitation,
         inet::ExplicitCongestionNotification,
+        packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
         path,
-        recovery::SentPacketInfo,
-        time::{Clock, NoopClock},
+        recovery::{SentPacketInfo, SentPackets},
+        time::{Clock, NoopClock, Timestamp},
         transmission,
+        varint::VarInt,
     };
+    use core::time::Duration;
+
+    fn pn(packet_number: u8) -> PacketNumber {
+        PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(packet_number))
+    }
+
+    fn sent_packet_info(sent_bytes: usize, time_sent: Timestamp) -> SentPacketInfo<()> {
+        SentPacketInfo::new(
+            true,
+            sent_bytes,
+            time_sent,
+            AckElicitation::Eliciting,
+            unsafe { path::Id::new(0) },
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            (),
+        )
+    }
+
+    #[test]
+    fn collect_below_threshold() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        // Leave gaps at packet numbers 3 and 6
+        for packet_number in [1u8, 2, 4, 5, 7, 8] {
+            sent_packets.insert(
+                pn(packet_number),
+                sent_packet_info(
+                    packet_number as usize * 100,
+                    now + Duration::from_millis(packet_number as u64),
+                ),
+            );
+        }
+
+        let sent_bytes = |threshold: u64, largest_acked: u8| -> alloc::vec::Vec<u16> {
+            sent_packets
+                .collect_below_threshold(pn(largest_acked), threshold)
+                .iter()
+                .map(|info| info.sent_bytes)
+                .collect()
+        };
+
+        assert_eq!(sent_bytes(3, 8), [100, 200, 400, 500]);
+        assert_eq!(sent_bytes(1, 8), [100, 200, 400, 500, 700]);
+        assert_eq!(sent_bytes(4, 8), [100, 200, 400]);
+        assert_eq!(sent_bytes(3, 5), [100, 200]);
+        assert_eq!(sent_bytes(3, 7), [100, 200, 400]);
+        assert!(sent_bytes(3, 2).is_empty());
+        assert!(sent_bytes(10, 8).is_empty());
+
+        // The largest acked packet doesn't need to be tracked
+        assert_eq!(sent_bytes(3, 6), [100, 200]);
+
+        // The largest acked packet and any packets after it are never included
+        assert_eq!(sent_bytes(0, 4), [100, 200]);
+    }
+
+    #[test]
+    fn get_mut() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        sent_packets.insert(pn(1), sent_packet_info(100, now));
+        sent_packets.insert(pn(2), sent_packet_info(200, now));
+
+        let info = sent_packets.get_mut(pn(2)).expect("packet 2 was sent");
+        assert_eq!(info.transmission_mode, transmission::Mode::Normal);
+        info.transmission_mode = transmission::Mode::LossRecoveryProbing;
+
+        assert_eq!(
+            sent_packets.get(pn(2)).unwrap().transmission_mode,
+            transmission::Mode::LossRecoveryProbing
+        );
+        // Other entries are unaffected
+        assert_eq!(
+            sent_packets.get(pn(1)).unwrap().transmission_mode,
+            transmission::Mode::Normal
+        );
+
+        // Packets that were never sent or were removed are not returned
+        assert!(sent_packets.get_mut(pn(3)).is_none());
+        sent_packets.remove(pn(1));
+        assert!(sent_packets.get_mut(pn(1)).is_none());
+    }
+
+    #[test]
+    fn range_mut() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        // Leave a gap at packet number 4
+        for packet_number in [1u8, 2, 3, 5, 6] {
+            sent_packets.insert(pn(packet_number), sent_packet_info(100, now));
+        }
+
+        let updated: alloc::vec::Vec<_> = sent_packets
+            .range_mut(PacketNumberRange::new(pn(2), pn(5)))
+            .map(|(packet_number, info)| {
+                info.congestion_controlled = false;
+                packet_number
+            })
+            .collect();
+        // Packet numbers that were never sent are skipped
+        assert_eq!(updated, [pn(2), pn(3), pn(5)]);
+
+        let in_flight = |packet_number: u8| {
+            sent_packets
+                .get(pn(packet_number))
+                .unwrap()
+                .congestion_controlled
+        };
+        assert!(in_flight(1));
+        assert!(!in_flight(2));
+        assert!(!in_flight(3));
+        assert!(!in_flight(5));
+        assert!(in_flight(6));
+
+        // Ranges extending beyond the tracked packets are bounded
+        assert_eq!(
+            sent_packets
+                .range_mut(PacketNumberRange::new(pn(0), pn(10)))
+                .count(),
+            5
+        );
+        assert_eq!(
+            sent_packets
+                .range_mut(PacketNumberRange::new(pn(7), pn(10)))
+                .count(),
+            0
+        );
+        // The entries are not removed
+        assert_eq!(sent_packets.len(), 5);
+    }
+
+    #[test]
+    fn iter_by_time() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        for (packet_number, time_sent) in [(1u8, 30u64), (2, 10), (3, 20), (4, 20), (5, 40)] {
+            sent_packets.insert(
+                pn(packet_number),
+                sent_packet_info(100, now + Duration::from_millis(time_sent)),
+            );
+        }
+
+        let packet_numbers: alloc::vec::Vec<_> = sent_packets
+            .iter_by_time()
+            .map(|(packet_number, _)| packet_number)
+            .collect();
+        assert_eq!(packet_numbers, [pn(2), pn(3), pn(4), pn(1), pn(5)]);
+
+        let times: alloc::vec::Vec<_> = sent_packets
+            .iter_by_time()
+            .map(|(_, info)| info.time_sent)
+            .collect();
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // With monotonic send times the order matches packet number order
+        let mut sent_packets = SentPackets::default();
+        for packet_number in 1..=5 {
+            sent_packets.insert(
+                pn(packet_number),
+                sent_packet_info(100, now + Duration::from_millis(packet_number as u64)),
+            );
+        }
+        assert!(sent_packets.iter_by_time().eq(sent_packets.iter()));
+    }
+
+    #[test]
+    fn first_in_flight_after() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+        assert!(sent_packets.first_in_flight_after(pn(1)).is_none());
+
+        let not_in_flight = SentPacketInfo::new(
+            false,
+            0,
+            now,
+            AckElicitation::NonEliciting,
+            unsafe { path::Id::new(0) },
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            (),
+        );
+
+        // Packets 2 to 4 are not in flight, and packet 6 was never sent
+        sent_packets.insert(pn(1), sent_packet_info(100, now));
+        for packet_number in 2..=4 {
+            sent_packets.insert(pn(packet_number), not_in_flight);
+        }
+        sent_packets.insert(pn(5), sent_packet_info(500, now));
+        sent_packets.insert(pn(7), sent_packet_info(700, now));
+
+        let first = |packet_number: u8| {
+            sent_packets
+                .first_in_flight_after(pn(packet_number))
+                .map(|(packet_number, info)| (packet_number, info.sent_bytes))
+        };
+
+        assert_eq!(first(0), Some((pn(1), 100)));
+        assert_eq!(first(1), Some((pn(1), 100)));
+        // The gap of packets that are not in flight is skipped
+        assert_eq!(first(2), Some((pn(5), 500)));
+        assert_eq!(first(4), Some((pn(5), 500)));
+        assert_eq!(first(5), Some((pn(5), 500)));
+        // Packet numbers that were never sent are skipped
+        assert_eq!(first(6), Some((pn(7), 700)));
+        assert_eq!(first(8), None);
+
+        sent_packets.remove(pn(5));
+        let (packet_number, _) = sent_packets.first_in_flight_after(pn(2)).unwrap();
+        assert_eq!(packet_number, pn(7));
+    }
+
+    #[test]
+    fn len() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+        assert_eq!(sent_packets.len(), 0);
+        assert!(sent_packets.is_empty());
+
+        for packet_number in 1..=5 {
+            sent_packets.insert(pn(packet_number), sent_packet_info(100, now));
+            assert_eq!(sent_packets.len(), packet_number as usize);
+            assert!(!sent_packets.is_empty());
+        }
+
+        // Removing from the middle leaves a gap that isn't counted
+        sent_packets.remove(pn(3));
+        assert_eq!(sent_packets.len(), 4);
+
+        // Packets that aren't tracked don't change the length
+        assert!(sent_packets.remove(pn(3)).is_none());
+        assert!(sent_packets.remove(pn(10)).is_none());
+        assert_eq!(sent_packets.len(), 4);
+
+        for packet_number in [1, 5, 2] {
+            sent_packets.remove(pn(packet_number));
+        }
+        assert_eq!(sent_packets.len(), 1);
+        assert!(!sent_packets.is_empty());
+
+        sent_packets.remove(pn(4));
+        assert_eq!(sent_packets.len(), 0);
+        assert!(sent_packets.is_empty());
+    }
+
+    #[test]
+    fn earliest_ack_eliciting() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+        assert_eq!(sent_packets.earliest_ack_eliciting(), None);
+
+        // Packets 1 and 2 are not ack-eliciting, and packet 4 was never sent
+        for (packet_number, ack_elicitation) in [
+            (1u8, AckElicitation::NonEliciting),
+            (2, AckElicitation::NonEliciting),
+            (3, AckElicitation::Eliciting),
+            (5, AckElicitation::Eliciting),
+            (6, AckElicitation::NonEliciting),
+        ] {
+            let mut info = sent_packet_info(100, now + Duration::from_millis(packet_number as u64));
+            info.ack_elicitation = ack_elicitation;
+            sent_packets.insert(pn(packet_number), info);
+        }
+
+        // The earliest packet is skipped since it isn't ack-eliciting
+        assert_eq!(
+            sent_packets
+                .iter()
+                .next()
+                .map(|(packet_number, _)| packet_number),
+            Some(pn(1))
+        );
+        assert_eq!(
+            sent_packets.earliest_ack_eliciting(),
+            Some((pn(3), now + Duration::from_millis(3)))
+        );
+
+        sent_packets.remove(pn(3));
+        assert_eq!(
+            sent_packets.earliest_ack_eliciting(),
+            Some((pn(5), now + Duration::from_millis(5)))
+        );
+
+        // Only non-ack-eliciting packets remain outstanding
+        sent_packets.remove(pn(5));
+        assert!(!sent_packets.is_empty());
+        assert_eq!(sent_packets.earliest_ack_eliciting(), None);
+    }
+
+    #[test]
+    fn pn_len() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        assert_eq!(sent_packet_info(100, now).pn_len(), 0);
+
+        for pn_len in 1..=4 {
+            sent_packets.insert(pn(pn_len), sent_packet_info(100, now).with_pn_len(pn_len));
+        }
+
+        for pn_len in 1..=4 {
+            assert_eq!(sent_packets.get(pn(pn_len)).unwrap().pn_len(), pn_len);
+        }
+
+        // The packet number length and transmit count are stored independently
+        let info = sent_packet_info(100, now)
+            .with_pn_len(4)
+            .with_transmit_count(MAX_TRANSMIT_COUNT);
+        assert_eq!(info.pn_len(), 4);
+        assert_eq!(info.transmit_count(), MAX_TRANSMIT_COUNT);
+        let info = info.with_pn_len(1);
+        assert_eq!(info.pn_len(), 1);
+        assert_eq!(info.transmit_count(), MAX_TRANSMIT_COUNT);
+    }
+
+    #[test]
+    fn transmit_count() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        // Each time a packet is lost, its frames are resent in the next packet
+        sent_packets.insert(pn(1), sent_packet_info(100, now));
+        for packet_number in 2..=4 {
+            let lost = sent_packets.remove(pn(packet_number - 1)).unwrap();
+            sent_packets.insert(
+                pn(packet_number),
+                sent_packet_info(100, now).with_transmit_count(lost.next_transmit_count()),
+            );
+        }
+        assert_eq!(sent_packets.get(pn(4)).unwrap().transmit_count(), 4);
+
+        // Unrelated packets start a new chain
+        sent_packets.insert(pn(5), sent_packet_info(100, now));
+        assert_eq!(sent_packets.get(pn(5)).unwrap().transmit_count(), 1);
+
+        let info = sent_packet_info(100, now).with_transmit_count(u8::MAX);
+        assert_eq!(info.transmit_count(), MAX_TRANSMIT_COUNT);
+        assert_eq!(info.next_transmit_count(), MAX_TRANSMIT_COUNT);
+    }
+
+    #[test]
+    fn is_pto_probe() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        for (packet_number, transmission_mode) in [
+            (1, transmission::Mode::Normal),
+            (2, transmission::Mode::LossRecoveryProbing),
+            (3, transmission::Mode::MtuProbing),
+            (4, transmission::Mode::PathValidationOnly),
+        ] {
+            let mut info = sent_packet_info(100, now);
+            info.transmission_mode = transmission_mode;
+            sent_packets.insert(pn(packet_number), info);
+        }
+
+        let probes: alloc::vec::Vec<_> = sent_packets
+            .iter()
+            .filter(|(_, info)| info.is_pto_probe())
+            .map(|(packet_number, _)| packet_number)
+            .collect();
+        assert_eq!(probes, [pn(2)]);
+
+        // The marker is retained when the packet is removed on acknowledgement
+        let info = sent_packets.remove(pn(2)).unwrap();
+        assert!(info.is_pto_probe());
+    }
+
+    #[test]
+    fn take_lost() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        for packet_number in 1..=6 {
+            sent_packets.insert(
+                pn(packet_number),
+                sent_packet_info(packet_number as usize * 100, now),
+            );
+        }
+
+        // Lost packets are returned in packet number order, and untracked packets are skipped
+        let lost = sent_packets.take_lost(&[pn(5), pn(2), pn(9), pn(3)]);
+        let lost: alloc::vec::Vec<_> = lost
+            .iter()
+            .map(|(packet_number, info)| (*packet_number, info.sent_bytes))
+            .collect();
+        assert_eq!(lost, [(pn(2), 200), (pn(3), 300), (pn(5), 500)]);
+
+        // The lost packets are no longer tracked
+        let remaining: alloc::vec::Vec<_> = sent_packets
+            .iter()
+            .map(|(packet_number, _)| packet_number)
+            .collect();
+        assert_eq!(remaining, [pn(1), pn(4), pn(6)]);
+
+        // Packets that were already removed are not returned again
+        assert!(sent_packets.take_lost(&[pn(2)]).is_empty());
+    }
+
+    #[test]
+    fn clear() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        for packet_number in 1..=20 {
+            sent_packets.insert(pn(packet_number), sent_packet_info(100, now));
+        }
+        sent_packets.remove(pn(5)).unwrap();
+
+        sent_packets.clear();
+        assert!(sent_packets.is_empty());
+        assert_eq!(sent_packets.len(), 0);
+        assert!(sent_packets.iter().next().is_none());
+        assert!(sent_packets.get(pn(1)).is_none());
+
+        // The next insertion sets the bounds of the map, even below the cleared packets
+        sent_packets.insert(pn(0), sent_packet_info(200, now));
+        sent_packets.insert(pn(3), sent_packet_info(300, now));
+        assert_eq!(
+            sent_packets.get_range(),
+            PacketNumberRange::new(pn(0), pn(3))
+        );
+
+        // None of the cleared packets are visible within the new bounds
+        assert!(sent_packets.get(pn(1)).is_none());
+        assert!(sent_packets.get(pn(2)).is_none());
+        let sent_bytes: alloc::vec::Vec<_> = sent_packets
+            .iter()
+            .map(|(packet_number, info)| (packet_number, info.sent_bytes))
+            .collect();
+        assert_eq!(sent_bytes, [(pn(0), 200), (pn(3), 300)]);
+    }
+
+    #[test]
+    fn is_mtu_probe() {
+        let now = NoopClock.get_time();
+        let mut sent_packets = SentPackets::default();
+
+        for (packet_number, transmission_mode) in [
+            (1, transmission::Mode::Normal),
+            (2, transmission::Mode::LossRecoveryProbing),
+            (3, transmission::Mode::MtuProbing),
+            (4, transmission::Mode::PathValidationOnly),
+        ] {
+            let mut info = sent_packet_info(100, now);
+            info.transmission_mode = transmission_mode;
+            sent_packets.insert(pn(packet_number), info);
+        }
+
+        let probes: alloc::vec::Vec<_> = sent_packets
+            .iter()
+            .filter(|(_, info)| info.is_mtu_probe())
+            .map(|(packet_number, _)| packet_number)
+            .collect();
+        assert_eq!(probes, [pn(3)]);
+    }
+
+    #[test]
+    fn wire_bytes() {
+        let now = NoopClock.get_time();
+        let ipv4_overhead = path::UDP_HEADER_LEN + path::IPV4_MIN_HEADER_LEN;
+        let ipv6_overhead = path::UDP_HEADER_LEN + path::IPV6_MIN_HEADER_LEN;
+
+        let info = sent_packet_info(1200, now);
+        assert_eq!(info.wire_bytes(ipv4_overhead), 1228);
+        assert_eq!(info.wire_bytes(ipv6_overhead), 1248);
+        assert_eq!(info.wire_bytes(0), 1200);
+        // The QUIC framing size used for congestion control is unchanged
+        assert_eq!(info.sent_bytes, 1200);
+
+        // The wire size does not overflow for the largest packets
+        let info = sent_packet_info(u16::MAX as usize, now);
+        assert_eq!(info.wire_bytes(u16::MAX), 2 * u16::MAX as u32);
+    }
 
     #[test]
     #[should_panic]
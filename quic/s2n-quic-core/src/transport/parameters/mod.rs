@@ -212,7 +212,7 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct ValidationError(&'static str);
+pub struct ValidationError(pub(crate) &'static str);
 
 const MAX_ENCODABLE_VALUE: ValidationError =
     ValidationError("provided value exceeds maximum encodable value");
@@ -289,7 +289,7 @@ macro_rules! transport_parameter {
         impl TransportParameter for $name {
             type CodecValue = $encodable_type;
 
-            const ID: TransportParameterId = TransportParameterId::from_u8($tag);
+            const ID: TransportParameterId = TransportParameterId::from_u32($tag);
 
             fn from_codec_value(value: Self::CodecValue) -> Self {
                 Self(value)
@@ -865,6 +865,63 @@ impl TransportParameterValidator for MaxAckDelay {
     }
 }
 
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-3
+// min_ack_delay (0xff04de1b):  A variable-length integer representing the
+//    minimum amount of time in microseconds by which the endpoint can
+//    delay an acknowledgement.  Values of 2^24 or greater are invalid
+//    and receipt of these values MUST be treated as a connection error
+//    of type TRANSPORT_PARAMETER_ERROR.
+//
+// An endpoint's min_ack_delay MUST NOT be greater than its max_ack_delay.
+
+transport_parameter!(MinAckDelay(VarInt), 0xff04de1b);
+optional_transport_parameter!(MinAckDelay);
+
+impl MinAckDelay {
+    /// Convert min_ack_delay into a `core::time::Duration`
+    pub const fn as_duration(self) -> Duration {
+        Duration::from_micros(self.0.as_u64())
+    }
+
+    /// Returns an error if the `min_ack_delay` exceeds the given `max_ack_delay`
+    pub fn validate_with_max_ack_delay(
+        self,
+        max_ack_delay: MaxAckDelay,
+    ) -> Result<Self, ValidationError> {
+        if self.as_duration() > max_ack_delay.as_duration() {
+            return Err(ValidationError(
+                "min_ack_delay cannot be greater than max_ack_delay",
+            ));
+        }
+        Ok(self)
+    }
+}
+
+impl TransportParameterValidator for MinAckDelay {
+    fn validate(self) -> Result<Self, DecoderError> {
+        decoder_invariant!(
+            *self.0 < 2u64.pow(24),
+            "min_ack_delay must be less than 2^24"
+        );
+        Ok(self)
+    }
+}
+
+impl TryFrom<Duration> for MinAckDelay {
+    type Error = ValidationError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        let value: VarInt = value.as_micros().try_into()?;
+        value.try_into()
+    }
+}
+
+impl From<MinAckDelay> for Duration {
+    fn from(value: MinAckDelay) -> Self {
+        value.as_duration()
+    }
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# disable_active_migration (0x0c): The disable active migration
 //#    transport parameter is included if the endpoint does not support
@@ -1431,6 +1488,7 @@ impl_transport_parameters!(
         max_datagram_frame_size: MaxDatagramFrameSize,
         ack_delay_exponent: AckDelayExponent,
         max_ack_delay: MaxAckDelay,
+        min_ack_delay: Option<MinAckDelay>,
         migration_support: MigrationSupport,
         active_connection_id_limit: ActiveConnectionIdLimit,
         original_destination_connection_id: OriginalDestinationConnectionId,
@@ -1482,6 +1540,7 @@ impl<
             initial_max_streams_uni
         );
         load!(max_ack_delay, max_ack_delay);
+        load!(min_ack_delay, min_ack_delay);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
     }
@@ -1539,6 +1598,7 @@ mod snapshot_tests {
             max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
             ack_delay_exponent: 2u8.try_into().unwrap(),
             max_ack_delay: integer_value.try_into().unwrap(),
+            min_ack_delay: None,
             migration_support: MigrationSupport::Disabled,
             active_connection_id_limit: integer_value.try_into().unwrap(),
             original_destination_connection_id: Some(
@@ -1583,6 +1643,7 @@ mod snapshot_tests {
             max_datagram_frame_size: MaxDatagramFrameSize::new(0u16).unwrap(),
             ack_delay_exponent: 2u8.try_into().unwrap(),
             max_ack_delay: integer_value.try_into().unwrap(),
+            min_ack_delay: None,
             migration_support: MigrationSupport::Disabled,
             active_connection_id_limit: integer_value.try_into().unwrap(),
             original_destination_connection_id: Default::default(),
@@ -1632,4 +1693,24 @@ mod snapshot_tests {
         assert_eq!(value, decoded_params);
         assert_eq!(0, remaining.len());
     }
+
+    #[test]
+    fn min_ack_delay_round_trip() {
+        let mut value = client_transport_parameters();
+        value.min_ack_delay = Some(Duration::from_millis(1).try_into().unwrap());
+        let encoded = assert_codec_round_trip_value!(ClientTransportParameters, value);
+        assert!(!encoded.is_empty());
+
+        assert!(MinAckDelay::try_from(VarInt::from_u32(1 << 24)).is_err());
+
+        let max_ack_delay = MaxAckDelay::try_from(Duration::from_millis(1)).unwrap();
+        assert!(MinAckDelay::try_from(Duration::from_millis(1))
+            .unwrap()
+            .validate_with_max_ack_delay(max_ack_delay)
+            .is_ok());
+        assert!(MinAckDelay::try_from(Duration::from_millis(2))
+            .unwrap()
+            .validate_with_max_ack_delay(max_ack_delay)
+            .is_err());
+    }
 }
@@ -10,6 +10,10 @@ use s2n_codec::{decoder_value, Encoder, EncoderValue};
 #[cfg(any(test, feature = "generator"))]
 use bolero_generator::*;
 
+mod range;
+
+pub use range::VarIntRange;
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-16
 //# QUIC packets and frames commonly use a variable-length encoding for
 //# non-negative integer values.  This encoding ensures that smaller
@@ -0,0 +1,158 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::varint::VarInt;
+
+/// An inclusive range of `VarInt`s
+///
+/// Iteration stops at the end of the range or at `VarInt::MAX`, whichever comes first,
+/// without overflowing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VarIntRange {
+    start: VarInt,
+    end: VarInt,
+    exhausted: bool,
+}
+
+impl VarIntRange {
+    /// Creates a new `VarInt` range.
+    #[inline]
+    pub fn new(start: VarInt, end: VarInt) -> Self {
+        assert!(start <= end, "start must be less than or equal to end");
+        Self {
+            start,
+            end,
+            exhausted: false,
+        }
+    }
+
+    /// Returns true if the range contains the given value
+    #[inline]
+    pub fn contains(&self, value: VarInt) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Returns the lower bound of the range (inclusive).
+    #[inline]
+    pub fn start(&self) -> VarInt {
+        self.start
+    }
+
+    /// Returns the upper bound of the range (inclusive).
+    #[inline]
+    pub fn end(&self) -> VarInt {
+        self.end
+    }
+}
+
+impl Iterator for VarIntRange {
+    type Item = VarInt;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.exhausted && self.start <= self.end {
+            let current = self.start;
+            if let Some(next) = current.checked_add(VarInt::from_u8(1)) {
+                self.start = next;
+            } else {
+                // VarInt range has been exceeded
+                self.exhausted = true;
+            }
+            Some(current)
+        } else {
+            self.exhausted = true;
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for VarIntRange {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if !self.exhausted && self.start <= self.end {
+            let current = self.end;
+            if let Some(prev) = current.checked_sub(VarInt::from_u8(1)) {
+                self.end = prev;
+                self.exhausted = self.start > self.end;
+            } else {
+                // VarInt range has been exceeded
+                self.exhausted = true;
+            }
+            Some(current)
+        } else {
+            self.exhausted = true;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::varint::MAX_VARINT_VALUE;
+
+    #[test]
+    fn iterator() {
+        let range = VarIntRange::new(VarInt::from_u8(1), VarInt::from_u8(10));
+        assert_eq!(VarInt::from_u8(1), range.start());
+        assert_eq!(VarInt::from_u8(10), range.end());
+
+        assert!(range.map(VarInt::as_u64).eq(1..=10));
+        assert!(range.rev().map(VarInt::as_u64).eq((1..=10).rev()));
+    }
+
+    #[test]
+    fn double_ended_iterator_zero() {
+        let mut range = VarIntRange::new(VarInt::ZERO, VarInt::from_u8(2));
+
+        assert_eq!(Some(VarInt::from_u8(2)), range.next_back());
+        assert_eq!(Some(VarInt::ZERO), range.next());
+        assert_eq!(Some(VarInt::from_u8(1)), range.next_back());
+        assert_eq!(None, range.next_back());
+        assert_eq!(None, range.next());
+
+        // Iterating back to zero does not underflow
+        assert_eq!(
+            1,
+            VarIntRange::new(VarInt::ZERO, VarInt::ZERO).rev().count()
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let range = VarIntRange::new(VarInt::from_u8(5), VarInt::from_u8(10));
+
+        assert!(!range.contains(VarInt::from_u8(4)));
+        assert!(range.contains(VarInt::from_u8(5)));
+        assert!(range.contains(VarInt::from_u8(10)));
+        assert!(!range.contains(VarInt::from_u8(11)));
+    }
+
+    #[test]
+    #[should_panic(expected = "start must be less than or equal to end")]
+    fn start_greater_than_end() {
+        VarIntRange::new(VarInt::from_u8(10), VarInt::from_u8(1));
+    }
+
+    #[test]
+    fn end_is_max_varint() {
+        let start = VarInt::new(MAX_VARINT_VALUE - 2).unwrap();
+        let mut range = VarIntRange::new(start, VarInt::MAX);
+
+        assert_eq!(Some(start), range.next());
+        assert_eq!(
+            Some(VarInt::new(MAX_VARINT_VALUE - 1).unwrap()),
+            range.next()
+        );
+        assert_eq!(Some(VarInt::MAX), range.next());
+        // The iterator stops at the maximum value rather than overflowing
+        assert_eq!(None, range.next());
+        assert_eq!(None, range.next());
+
+        assert_eq!(3, VarIntRange::new(start, VarInt::MAX).count());
+        assert_eq!(
+            Some(VarInt::MAX),
+            VarIntRange::new(VarInt::MAX, VarInt::MAX).last()
+        );
+    }
+}
@@ -358,6 +358,12 @@ enum Frame {
     Datagram {
         len: u16,
     },
+    AckFrequency {
+        sequence_number: u64,
+        ack_eliciting_threshold: u64,
+        request_max_ack_delay: u64,
+        reordering_threshold: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -531,6 +537,17 @@ where
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::AckFrequency {
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::AckFrequency {
+            sequence_number: self.sequence_number.as_u64(),
+            ack_eliciting_threshold: self.ack_eliciting_threshold.as_u64(),
+            request_max_ack_delay: self.request_max_ack_delay.as_u64(),
+            reordering_threshold: self.reordering_threshold.as_u64(),
+        }
+    }
+}
+
 enum StreamType {
     Bidirectional,
     Unidirectional,
@@ -545,6 +562,16 @@ impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
     }
 }
 
+impl IntoEvent<builder::KeySpace> for crate::packet::number::PacketNumberSpace {
+    fn into_event(self) -> builder::KeySpace {
+        match self {
+            Self::Initial => builder::KeySpace::Initial {},
+            Self::Handshake => builder::KeySpace::Handshake {},
+            Self::ApplicationData => builder::KeySpace::OneRtt {},
+        }
+    }
+}
+
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#A.2
 //
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#A.4
@@ -796,6 +823,28 @@ enum CongestionSource {
     PacketLoss,
 }
 
+/// The loss detection threshold that caused a packet to be declared lost
+enum PacketLossReason {
+    /// The packet was sent kPacketThreshold packets before an acknowledged packet
+    PacketThreshold,
+    /// The packet was sent long enough in the past, without exceeding the packet threshold
+    TimeThreshold,
+    /// The packet was the oldest tracked packet when the limit on tracked packets was exceeded
+    TrackedPacketLimit,
+}
+
+/// The reason a connection stopped transmitting on a path
+enum TransmissionLimitCause {
+    /// The congestion window does not have space for another packet
+    CongestionLimited,
+    /// The application does not have enough data to fill the congestion window
+    AppLimited,
+    /// The pacer has delayed transmission until a future departure time
+    PacingLimited,
+    /// The transmission queue does not have capacity for another packet
+    QueueFull,
+}
+
 #[allow(non_camel_case_types)] // we prefer to match the standard identifier
 enum CipherSuite {
     TLS_AES_128_GCM_SHA256,
@@ -836,6 +885,14 @@ enum SlowStartExitCause {
     Other,
 }
 
+/// The congestion controller phase responsible for an increase in the congestion window
+enum CongestionWindowIncreaseCause {
+    /// The window was increased by Slow Start
+    SlowStart,
+    /// The window was increased by Congestion Avoidance
+    CongestionAvoidance,
+}
+
 /// The reason the MTU was updated
 enum MtuUpdatedCause {
     /// The MTU was initialized with the default value
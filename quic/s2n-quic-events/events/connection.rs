@@ -78,6 +78,7 @@ struct PacketLost<'a> {
     path: Path<'a>,
     bytes_lost: u16,
     is_mtu_probe: bool,
+    reason: PacketLossReason,
 }
 
 #[event("recovery:metrics_updated")]
@@ -103,6 +104,46 @@ struct Congestion<'a> {
     source: CongestionSource,
 }
 
+#[event("recovery:transmission_limited")]
+/// Transmission on a path has stopped due to a limit
+struct TransmissionLimited<'a> {
+    path: Path<'a>,
+    cause: TransmissionLimitCause,
+}
+
+#[event("recovery:pto_armed")]
+/// The probe timeout (PTO) timer has been armed for a packet number space
+struct PtoArmed<'a> {
+    path: Path<'a>,
+    space: KeySpace,
+    /// The PTO period the timer was armed with
+    ///
+    /// The timer expires once this much time has passed since the last ack-eliciting packet was
+    /// sent, or since the timer was armed if there are no ack-eliciting packets in flight.
+    pto_period: Duration,
+}
+
+#[event("recovery:pto_expired")]
+/// The probe timeout (PTO) timer has expired for a packet number space
+struct PtoExpired<'a> {
+    path: Path<'a>,
+    space: KeySpace,
+    /// The number of ack-eliciting probe packets that will be sent in response
+    probe_count: u8,
+}
+
+#[event("recovery:receive_buffer_advice")]
+/// The congestion window changed significantly, and the receive buffer should be resized
+struct ReceiveBufferAdvice<'a> {
+    path: Path<'a>,
+    /// The congestion window in bytes
+    congestion_window: u32,
+    /// The suggested receive buffer size in bytes
+    ///
+    /// This is twice the estimated bandwidth-delay product of the path.
+    suggested_receive_buffer_size: u64,
+}
+
 #[event("recovery:ack_processed")]
 #[deprecated(note = "use on_rx_ack_range_dropped event instead")]
 /// Events related to ACK processing
@@ -289,3 +330,11 @@ struct SlowStartExited<'a> {
     cause: SlowStartExitCause,
     congestion_window: u32,
 }
+
+#[event("recovery:congestion_window_increased")]
+/// The congestion window has been increased in response to an acknowledgement
+struct CongestionWindowIncreased<'a> {
+    path: Path<'a>,
+    cause: CongestionWindowIncreaseCause,
+    congestion_window: u32,
+}
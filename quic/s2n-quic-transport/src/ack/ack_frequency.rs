@@ -0,0 +1,272 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Requests the peer acknowledge packets at the rate preferred by the recovery manager
+//!
+//! The peer is informed of the preferred ack-eliciting threshold with ACK_FREQUENCY frames,
+//! which are only sent if the peer advertised the `min_ack_delay` transport parameter.
+
+use crate::{contexts::WriteContext, transmission};
+use core::time::Duration;
+use s2n_quic_core::{ack, frame::AckFrequency, packet::number::PacketNumber, varint::VarInt};
+
+//= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.2
+//# A receiver SHOULD send an ACK frame after receiving at least two
+//# ack-eliciting packets.
+const DEFAULT_ACK_ELICITING_THRESHOLD: u64 = 1;
+
+// https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+// A value of 1 will result in immediate acknowledgement upon receiving any
+// out-of-order packet, matching the behavior in [QUIC-TRANSPORT].
+const REORDERING_THRESHOLD: VarInt = VarInt::from_u8(1);
+
+#[derive(Debug)]
+pub struct AckFrequencySender {
+    /// The `min_ack_delay` advertised by the peer, or `None` if the peer does not support
+    /// receiving ACK_FREQUENCY frames
+    peer_min_ack_delay: Option<Duration>,
+
+    /// The `max_ack_delay` advertised by the peer
+    peer_max_ack_delay: Duration,
+
+    /// The ack-eliciting threshold most recently requested from the peer
+    requested_threshold: u64,
+
+    /// The sequence number for the next ACK_FREQUENCY frame
+    next_sequence_number: VarInt,
+
+    /// The most recent ACK_FREQUENCY frame
+    frame: Option<AckFrequency>,
+
+    /// Used to transition through transmission/retransmission states
+    delivery: DeliveryState,
+}
+
+#[derive(Debug, PartialEq)]
+enum DeliveryState {
+    /// The most recent frame has been delivered or no frame was requested
+    Idle,
+
+    /// The frame needs to be transmitted
+    RequiresTransmission,
+
+    /// The frame was lost and needs to be retransmitted
+    RequiresRetransmission,
+
+    /// The frame has been transmitted and is pending acknowledgement
+    InFlight(PacketNumber),
+}
+
+impl AckFrequencySender {
+    pub fn new(peer_min_ack_delay: Option<Duration>, peer_max_ack_delay: Duration) -> Self {
+        Self {
+            peer_min_ack_delay,
+            peer_max_ack_delay,
+            requested_threshold: DEFAULT_ACK_ELICITING_THRESHOLD,
+            next_sequence_number: VarInt::from_u8(0),
+            frame: None,
+            delivery: DeliveryState::Idle,
+        }
+    }
+
+    /// Returns the ack-eliciting threshold most recently requested from the peer
+    #[inline]
+    pub fn requested_threshold(&self) -> u64 {
+        self.requested_threshold
+    }
+
+    /// Updates the ack-eliciting threshold preferred by the recovery manager
+    ///
+    /// A lower threshold is requested immediately, so the peer acknowledges more often as the
+    /// congestion window shrinks. A higher threshold is only requested once the outstanding
+    /// ack-eliciting packets cross it, since acknowledging less often has no benefit until then.
+    pub fn update(&mut self, threshold: u64, is_threshold_exceeded: bool) {
+        // the peer does not support ACK_FREQUENCY frames
+        if self.peer_min_ack_delay.is_none() {
+            return;
+        }
+
+        if threshold == self.requested_threshold
+            || (threshold > self.requested_threshold && !is_threshold_exceeded)
+        {
+            return;
+        }
+
+        let ack_eliciting_threshold = if let Ok(threshold) = VarInt::new(threshold) {
+            threshold
+        } else {
+            return;
+        };
+
+        let sequence_number = self.next_sequence_number;
+        self.next_sequence_number = sequence_number.saturating_add(VarInt::from_u8(1));
+
+        self.frame = Some(AckFrequency {
+            sequence_number,
+            ack_eliciting_threshold,
+            // Only the threshold is tuned; the peer keeps its advertised max_ack_delay, which
+            // was validated to be no less than its min_ack_delay
+            request_max_ack_delay: AckFrequency::encode_max_ack_delay(self.peer_max_ack_delay),
+            reordering_threshold: REORDERING_THRESHOLD,
+        });
+        self.requested_threshold = threshold;
+
+        // any previous transmission is superseded by the new frame
+        self.delivery = DeliveryState::RequiresTransmission;
+    }
+
+    /// Queries the component for any outgoing frames that need to get sent
+    pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
+        let constraint = context.transmission_constraint();
+        let can_send = match self.delivery {
+            DeliveryState::RequiresTransmission => constraint.can_transmit(),
+            DeliveryState::RequiresRetransmission => constraint.can_retransmit(),
+            _ => false,
+        };
+
+        if !can_send {
+            return;
+        }
+
+        if let Some(frame) = self.frame.as_ref() {
+            if let Some(packet_number) = context.write_frame(frame) {
+                self.delivery = DeliveryState::InFlight(packet_number);
+            }
+        }
+    }
+
+    /// This method gets called when a packet delivery got acknowledged
+    pub fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
+        if let DeliveryState::InFlight(packet_number) = self.delivery {
+            if ack_set.contains(packet_number) {
+                self.delivery = DeliveryState::Idle;
+            }
+        }
+    }
+
+    /// This method gets called when a packet loss is reported
+    pub fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
+        if let DeliveryState::InFlight(packet_number) = self.delivery {
+            if ack_set.contains(packet_number) {
+                self.delivery = DeliveryState::RequiresRetransmission;
+            }
+        }
+    }
+}
+
+impl transmission::interest::Provider for AckFrequencySender {
+    #[inline]
+    fn transmission_interest<Q: transmission::interest::Query>(
+        &self,
+        query: &mut Q,
+    ) -> transmission::interest::Result {
+        match self.delivery {
+            DeliveryState::RequiresTransmission => query.on_new_data(),
+            DeliveryState::RequiresRetransmission => query.on_lost_data(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{contexts::testing::*, transmission::interest::Provider};
+    use s2n_quic_core::{endpoint, frame::Frame};
+    use s2n_quic_platform::time;
+
+    fn written_frame(frame_buffer: &mut OutgoingFrameBuffer) -> (AckFrequency, PacketNumber) {
+        let mut written = frame_buffer
+            .pop_front()
+            .expect("an ACK_FREQUENCY frame should be written");
+        let packet_number = written.packet_nr;
+        match written.as_frame() {
+            Frame::AckFrequency(frame) => (frame, packet_number),
+            frame => panic!("unexpected frame {:?}", frame),
+        }
+    }
+
+    #[test]
+    fn peer_without_support_test() {
+        let mut sender = AckFrequencySender::new(None, Duration::from_millis(25));
+
+        sender.update(10, true);
+        assert!(!sender.has_transmission_interest());
+        assert_eq!(
+            sender.requested_threshold(),
+            DEFAULT_ACK_ELICITING_THRESHOLD
+        );
+    }
+
+    #[test]
+    fn threshold_crossed_test() {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+        let mut sender =
+            AckFrequencySender::new(Some(Duration::from_millis(1)), Duration::from_millis(25));
+
+        // The default threshold doesn't need to be requested
+        sender.update(DEFAULT_ACK_ELICITING_THRESHOLD, false);
+        assert!(!sender.has_transmission_interest());
+
+        // A higher threshold is not requested until the outstanding packets cross it
+        sender.update(10, false);
+        assert!(!sender.has_transmission_interest());
+        sender.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+
+        sender.update(10, true);
+        assert_eq!(
+            sender.get_transmission_interest(),
+            transmission::Interest::NewData
+        );
+
+        context.transmission_constraint = transmission::Constraint::CongestionLimited;
+        sender.on_transmit(&mut context);
+        assert!(context.frame_buffer.is_empty());
+        context.transmission_constraint = transmission::Constraint::None;
+
+        sender.on_transmit(&mut context);
+        let (frame, packet_number) = written_frame(context.frame_buffer);
+        assert_eq!(frame.sequence_number, VarInt::from_u8(0));
+        assert_eq!(frame.ack_eliciting_threshold, VarInt::from_u8(10));
+        assert_eq!(frame.request_max_ack_delay(), Duration::from_millis(25));
+        assert_eq!(frame.reordering_threshold, REORDERING_THRESHOLD);
+        assert_eq!(sender.requested_threshold(), 10);
+        assert!(!sender.has_transmission_interest());
+
+        // Once delivered, nothing else is sent
+        sender.on_packet_ack(&packet_number);
+        assert_eq!(sender.delivery, DeliveryState::Idle);
+        sender.update(10, true);
+        assert!(!sender.has_transmission_interest());
+
+        // A lower threshold is requested immediately
+        sender.update(4, false);
+        assert_eq!(
+            sender.get_transmission_interest(),
+            transmission::Interest::NewData
+        );
+        sender.on_transmit(&mut context);
+        let (frame, packet_number) = written_frame(context.frame_buffer);
+        assert_eq!(frame.sequence_number, VarInt::from_u8(1));
+        assert_eq!(frame.ack_eliciting_threshold, VarInt::from_u8(4));
+
+        // Lost frames are retransmitted with the same sequence number
+        sender.on_packet_loss(&packet_number);
+        assert_eq!(
+            sender.get_transmission_interest(),
+            transmission::Interest::LostData
+        );
+        sender.on_transmit(&mut context);
+        let (frame, _) = written_frame(context.frame_buffer);
+        assert_eq!(frame.sequence_number, VarInt::from_u8(1));
+        assert_eq!(frame.ack_eliciting_threshold, VarInt::from_u8(4));
+    }
+}
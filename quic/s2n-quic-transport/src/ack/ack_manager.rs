@@ -19,7 +19,7 @@ use s2n_quic_core::{
         builder::{AckAction, AckProcessed},
         IntoEvent as _,
     },
-    frame::{ack::EcnCounts, Ack, Ping},
+    frame::{ack::EcnCounts, Ack, AckFrequency, Ping},
     packet::number::{PacketNumber, PacketNumberSpace},
     time::{timer, Timer, Timestamp},
     varint::VarInt,
@@ -41,6 +41,11 @@ use s2n_quic_core::{
 //# every ack-eliciting packet.  The guidance offered below seeks to
 //# strike this balance.
 
+// TODO update to draft link after published
+// https://github.com/quicwg/base-drafts/pull/3623
+// An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
+const DEFAULT_PACKET_TOLERANCE: u8 = 10;
+
 #[derive(Clone, Debug)]
 pub struct AckManager {
     /// Time at which the AckManager will wake and transmit an ACK
@@ -72,6 +77,15 @@ pub struct AckManager {
 
     /// Explicit Congestion Notification counts from processed packets
     ecn_counts: EcnCounts,
+
+    /// The number of processed packets after which an ACK is sent immediately
+    packet_tolerance: u8,
+
+    /// If `true`, out-of-order packets don't cause an ACK to be sent immediately
+    ignore_order: bool,
+
+    /// The smallest sequence number of an ACK_FREQUENCY frame that is not obsolete
+    next_ack_frequency_sequence_number: VarInt,
 }
 
 impl AckManager {
@@ -88,9 +102,49 @@ impl AckManager {
             transmissions_since_elicitation: Counter::new(0),
             transmission_state: AckTransmissionState::default(),
             ecn_counts: EcnCounts::default(),
+            packet_tolerance: DEFAULT_PACKET_TOLERANCE,
+            ignore_order: false,
+            next_ack_frequency_sequence_number: VarInt::from_u8(0),
         }
     }
 
+    /// Called when the peer sends an ACK_FREQUENCY frame
+    ///
+    /// The frame is expected to have been validated against the local `min_ack_delay`.
+    pub fn on_ack_frequency(&mut self, frame: &AckFrequency) {
+        // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+        // On a subsequent ACK_FREQUENCY frame, the receiver MUST check if this
+        // frame is more recent than any previous ones, as follows:
+        //
+        // *  If the frame's sequence number is not greater than the largest one
+        //    seen so far, the endpoint MUST ignore this frame.
+        if frame.sequence_number < self.next_ack_frequency_sequence_number {
+            return;
+        }
+        self.next_ack_frequency_sequence_number =
+            frame.sequence_number.saturating_add(VarInt::from_u8(1));
+
+        // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-6.1
+        // An endpoint sends an acknowledgement immediately if the number of
+        // received ack-eliciting packets since the last acknowledgement was
+        // sent exceeds the Ack-Eliciting Threshold.
+        self.packet_tolerance = frame
+            .ack_eliciting_threshold
+            .as_u64()
+            .saturating_add(1)
+            .min(u8::MAX as u64) as u8;
+
+        self.ack_settings.max_ack_delay = frame.request_max_ack_delay();
+
+        // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-6.2
+        // If the Reordering Threshold is 0, the endpoint SHOULD NOT send an
+        // immediate acknowledgement due to reordering.
+        //
+        // Larger reordering thresholds are treated like a threshold of 1, which
+        // acknowledges out-of-order packets more often than requested.
+        self.ignore_order = frame.reordering_threshold == VarInt::from_u8(0);
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -298,14 +352,14 @@ impl AckManager {
             //# *  when the received packet has a packet number less than another
             //#    ack-eliciting packet that has been received, or
 
-            should_activate |= !is_largest;
+            should_activate |= !self.ignore_order && !is_largest;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# *  when the packet has a packet number larger than the highest-
             //#    numbered ack-eliciting packet that has been received and there are
             //#    missing packets between that packet and this packet.
 
-            should_activate |= !is_ordered;
+            should_activate |= !self.ignore_order && !is_ordered;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.1
             //# Similarly, packets marked with the ECN Congestion Experienced (CE)
@@ -313,15 +367,8 @@ impl AckManager {
             //# reduce the peer's response time to congestion events.
             should_activate |= processed_packet.datagram.ecn.congestion_experienced();
 
-            // TODO update to draft link after published
-            // https://github.com/quicwg/base-drafts/pull/3623
-            // An ACK frame SHOULD be generated for at least every 10th ack-eliciting packet
-
-            // TODO support delayed ack proposal
-            // https://tools.ietf.org/html/draft-iyengar-quic-delayed-ack-00
-            let packet_tolerance = 10;
-
-            should_activate |= self.processed_packets_since_transmission >= packet_tolerance;
+            // The tolerance may be adjusted by the peer with ACK_FREQUENCY frames
+            should_activate |= self.processed_packets_since_transmission >= self.packet_tolerance;
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-9.3.3
             //# An endpoint that receives a PATH_CHALLENGE on an active path SHOULD
@@ -522,6 +569,65 @@ mod tests {
         assert_eq!(1, manager.ecn_counts.ce_count.as_u64());
     }
 
+    #[test]
+    fn on_ack_frequency() {
+        let path = helper_path_server();
+        let path_id = path::Id::test_id();
+        let mut publisher = Publisher::no_snapshot();
+        let datagram = helper_datagram_info(ExplicitCongestionNotification::NotEct);
+
+        let mut on_processed_packet = |manager: &mut AckManager, packet_number: u8| {
+            let pn = PacketNumberSpace::ApplicationData
+                .new_packet_number(VarInt::from_u8(packet_number));
+            let mut processed_packet = ProcessedPacket::new(pn, &datagram);
+            processed_packet.ack_elicitation = AckElicitation::Eliciting;
+            manager.on_processed_packet(
+                &processed_packet,
+                path_event!(path, path_id),
+                &mut publisher,
+            );
+            manager.transmission_state.is_active()
+        };
+
+        let frame = |sequence_number: u8, threshold: u8, reordering_threshold: u8| AckFrequency {
+            sequence_number: VarInt::from_u8(sequence_number),
+            ack_eliciting_threshold: VarInt::from_u8(threshold),
+            request_max_ack_delay: AckFrequency::encode_max_ack_delay(Duration::from_millis(10)),
+            reordering_threshold: VarInt::from_u8(reordering_threshold),
+        };
+
+        // The threshold and max ack delay are applied
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        manager.on_ack_frequency(&frame(1, 1, 1));
+        assert_eq!(
+            manager.ack_settings.max_ack_delay,
+            Duration::from_millis(10)
+        );
+        assert!(!on_processed_packet(&mut manager, 1));
+        assert!(on_processed_packet(&mut manager, 2));
+
+        // Obsolete frames are ignored
+        manager.on_ack_frequency(&frame(1, 20, 1));
+        manager.on_ack_frequency(&frame(0, 20, 1));
+        assert_eq!(manager.packet_tolerance, 2);
+        manager.on_ack_frequency(&frame(2, 20, 1));
+        assert_eq!(manager.packet_tolerance, 21);
+
+        // Out-of-order packets are acknowledged immediately by default
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        assert!(!on_processed_packet(&mut manager, 1));
+        assert!(on_processed_packet(&mut manager, 3));
+
+        // A reordering threshold of 0 disables immediate acknowledgements on reordering
+        let mut manager =
+            AckManager::new(PacketNumberSpace::ApplicationData, ack::Settings::default());
+        manager.on_ack_frequency(&frame(0, 9, 0));
+        assert!(!on_processed_packet(&mut manager, 1));
+        assert!(!on_processed_packet(&mut manager, 3));
+    }
+
     /// Helper function to construct `DatagramInfo` with the given `ExplicitCongestionNotification`
     fn helper_datagram_info(ecn: ExplicitCongestionNotification) -> DatagramInfo {
         DatagramInfo {
@@ -57,7 +57,7 @@ use s2n_quic_core::{
     recovery::CongestionController,
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
-    transport,
+    transport::{self, parameters::MaxAckDelay},
 };
 
 /// Possible states for handing over a connection from the endpoint to the
@@ -369,6 +369,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             self.timers.reset_peer_idle_timer_on_send = true;
         }
 
+        self.space_manager.check_bytes_in_flight(&self.path_manager);
+
         let mut publisher = self
             .event_context
             .publisher(packet.datagram.timestamp, subscriber);
@@ -563,7 +565,25 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         // The path manager always starts with a single path containing the known peer and local
         // connection ids.
-        let rtt_estimator = RttEstimator::default();
+        //
+        // The peer's max_ack_delay is not known until its transport parameters are
+        // received, so the default value is assumed until then.
+        let mut rtt_estimator = RttEstimator::new_with_initial_rtt(
+            MaxAckDelay::default().as_duration(),
+            parameters.limits.initial_rtt(),
+        )
+        .with_persistent_congestion_threshold(parameters.limits.persistent_congestion_threshold())
+        .with_timer_granularity(parameters.limits.timer_granularity());
+        if let Some(rttvar) = parameters.limits.initial_rttvar() {
+            rtt_estimator = rtt_estimator.with_initial_rttvar(rttvar);
+        }
+        let mut congestion_controller = parameters.congestion_controller;
+        if let Some(congestion_window) = parameters.limits.remembered_congestion_window() {
+            congestion_controller.on_remembered_congestion_window(congestion_window);
+        }
+        if let Some(slow_start_threshold) = parameters.limits.remembered_slow_start_threshold() {
+            congestion_controller.on_remembered_slow_start_threshold(slow_start_threshold);
+        }
         // Assume clients validate the server's address implicitly.
         let peer_validated = Self::Config::ENDPOINT_TYPE.is_server();
 
@@ -572,7 +592,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             parameters.peer_connection_id,
             parameters.local_connection_id,
             rtt_estimator,
-            parameters.congestion_controller,
+            congestion_controller,
             peer_validated,
             parameters.max_mtu,
         );
@@ -699,6 +719,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.timers.cancel();
         // Let the path manager know we're closing
         self.path_manager.on_closing();
+        // Discard the unacknowledged packets, since they will no longer be acknowledged
+        self.space_manager
+            .on_connection_close(&mut self.path_manager);
         // Update the connection state based on the type of error
         self.state = error.into();
         self.error = Err(error);
@@ -868,29 +891,50 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 }
 
                 // Send all other data for the active path
-                while self.path_manager.active_path().can_transmit(timestamp)
-                    && queue
-                        .push(ConnectionTransmission {
-                            context: transmission_context!(
-                                self,
-                                &mut outcome,
-                                path_id,
-                                timestamp,
-                                transmission::Mode::Normal,
-                                subscriber,
-                                packet_interceptor,
-                            ),
-                            space_manager: &mut self.space_manager,
-                        })
-                        .is_ok()
-                {
-                    count += 1;
+                let mut push_error = None;
+                while self.path_manager.active_path().can_transmit(timestamp) {
+                    match queue.push(ConnectionTransmission {
+                        context: transmission_context!(
+                            self,
+                            &mut outcome,
+                            path_id,
+                            timestamp,
+                            transmission::Mode::Normal,
+                            subscriber,
+                            packet_interceptor,
+                        ),
+                        space_manager: &mut self.space_manager,
+                    }) {
+                        Ok(_) => count += 1,
+                        Err(error) => {
+                            push_error = Some(error);
+                            break;
+                        }
+                    }
                 }
 
+                // Classify why the active path stopped transmitting before any other
+                // transmissions change its state
+                let transmission_limit_cause = match push_error {
+                    // The queue is full, so the path itself may not be limited
+                    Some(tx::Error::AtCapacity) => {
+                        Some(event::builder::TransmissionLimitCause::QueueFull)
+                    }
+                    Some(tx::Error::UndersizedBuffer) => None,
+                    // Either the path is unable to transmit or nothing was written to the
+                    // packet, due to the congestion window or the lack of pending data
+                    Some(tx::Error::EmptyPayload) | None => self
+                        .path_manager
+                        .active_path()
+                        .transmission_limit_cause(timestamp),
+                };
+
                 if outcome.ack_elicitation.is_ack_eliciting() {
                     self.on_ack_eliciting_packet_sent(timestamp);
                 }
 
+                self.space_manager.check_bytes_in_flight(&self.path_manager);
+
                 if let Some(edt) = self
                     .path_manager
                     .active_path()
@@ -941,6 +985,15 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     })
                 }
 
+                if let Some(cause) = transmission_limit_cause {
+                    let path_id = self.path_manager.active_path_id();
+                    let path = self.path_manager.active_path();
+                    publisher.on_transmission_limited(event::builder::TransmissionLimited {
+                        path: path_event!(path, path_id),
+                        cause,
+                    });
+                }
+
                 // check to see if we are flushing and should close
                 if self.poll_flush().is_ready() {
                     // trigger a wake up so we can close
@@ -1015,6 +1068,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             timestamp,
             &mut publisher,
         );
+        self.space_manager.check_bytes_in_flight(&self.path_manager);
 
         if self
             .timers
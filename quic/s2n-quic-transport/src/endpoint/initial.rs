@@ -250,6 +250,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             tls_session,
             initial_key,
             initial_header_key,
+            &limits,
             datagram.timestamp,
             &mut publisher,
         );
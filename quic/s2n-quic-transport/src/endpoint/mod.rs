@@ -1109,6 +1109,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             tls_session,
             initial_key,
             initial_header_key,
+            &limits,
             timestamp,
             &mut publisher,
         );
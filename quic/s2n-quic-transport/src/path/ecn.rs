@@ -244,6 +244,7 @@ impl Controller {
             if Self::ce_remarking(incremental_ecn_counts, newly_acked_ecn_counts)
                 || Self::remarked_to_ect0_or_ect1(incremental_ecn_counts, sent_packet_ecn_counts)
                 || Self::ce_suppression(incremental_ecn_counts, newly_acked_ecn_counts)
+                || Self::marks_exceed_sent(incremental_ecn_counts, sent_packet_ecn_counts)
             {
                 self.fail(now, path, publisher);
                 return ValidationOutcome::Failed;
@@ -324,6 +325,25 @@ impl Controller {
         incremental_ecn_counts.ce_count < newly_acked_ecn_counts.ce_count
     }
 
+    // The network may remark ECT(0) or ECT(1) packets as ECN-CE, but it can't mark packets
+    // that were sent without an ECN codepoint as ECN capable. The increase in the sum of the
+    // counts is therefore limited to the number of outstanding packets sent with an ECN
+    // codepoint, which rules out a peer claiming more ECN-CE marks than could have been applied.
+    #[inline]
+    fn marks_exceed_sent(
+        incremental_ecn_counts: EcnCounts,
+        sent_packet_ecn_counts: EcnCounts,
+    ) -> bool {
+        let total = |ecn_counts: EcnCounts| {
+            ecn_counts
+                .ect_0_count
+                .saturating_add(ecn_counts.ect_1_count)
+                .saturating_add(ecn_counts.ce_count)
+        };
+
+        total(incremental_ecn_counts) > total(sent_packet_ecn_counts)
+    }
+
     /// This method gets called when a packet has been sent
     pub fn on_packet_sent<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -437,6 +437,92 @@ fn validate_ecn_decrease() {
     assert!(matches!(controller.state, State::Failed(_)));
 }
 
+#[test]
+fn validate_ecn_counts_regression() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Default::default()
+    };
+    let now = s2n_quic_platform::time::now();
+    let newly_acked_ecn_counts = helper_ecn_counts(1, 0, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(4, 0, 0);
+    let baseline_ecn_counts = helper_ecn_counts(3, 0, 1);
+    // The CE count decreased from the baseline
+    let ack_frame_ecn_counts = helper_ecn_counts(4, 0, 0);
+    let outcome = controller.validate(
+        newly_acked_ecn_counts,
+        sent_packet_ecn_counts,
+        baseline_ecn_counts,
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Failed, outcome);
+    assert!(matches!(controller.state, State::Failed(_)));
+}
+
+#[test]
+fn validate_ecn_counts_increase() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Default::default()
+    };
+    let now = s2n_quic_platform::time::now();
+    let newly_acked_ecn_counts = helper_ecn_counts(2, 0, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(4, 0, 0);
+    let baseline_ecn_counts = helper_ecn_counts(3, 0, 1);
+    // All 4 outstanding packets are reported, one of them remarked as CE
+    let ack_frame_ecn_counts = helper_ecn_counts(6, 0, 2);
+    let outcome = controller.validate(
+        newly_acked_ecn_counts,
+        sent_packet_ecn_counts,
+        baseline_ecn_counts,
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::from_millis(50),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(
+        ValidationOutcome::CongestionExperienced(1_u8.into()),
+        outcome
+    );
+    assert!(controller.is_capable());
+}
+
+#[test]
+fn validate_ecn_ce_exceeds_sent() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut controller = Controller {
+        state: State::Unknown,
+        ..Default::default()
+    };
+    let now = s2n_quic_platform::time::now();
+    let newly_acked_ecn_counts = helper_ecn_counts(2, 0, 0);
+    let sent_packet_ecn_counts = helper_ecn_counts(4, 0, 0);
+    // 6 marked packets are reported, but only 4 were sent with an ECN codepoint
+    let ack_frame_ecn_counts = helper_ecn_counts(2, 0, 4);
+    let outcome = controller.validate(
+        newly_acked_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(ack_frame_ecn_counts),
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Failed, outcome);
+    assert!(matches!(controller.state, State::Failed(_)));
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-A.4
 //= type=test
 //# From the "unknown" state, successful validation of the ECN counts in an ACK frame
@@ -572,7 +658,7 @@ fn validate_capable_congestion_experienced() {
     let now = s2n_quic_platform::time::now();
     let expected_ecn_counts = helper_ecn_counts(2, 0, 5);
     let ack_frame_ecn_counts = helper_ecn_counts(1, 0, 12);
-    let sent_packet_ecn_counts = helper_ecn_counts(2, 0, 5);
+    let sent_packet_ecn_counts = helper_ecn_counts(8, 0, 5);
     let rtt = Duration::from_millis(50);
     let outcome = controller.validate(
         expected_ecn_counts,
@@ -879,3 +965,75 @@ fn fuzz_validate() {
             },
         );
 }
+
+#[test]
+fn validation_lifecycle_capable() {
+    let mut publisher = Publisher::snapshot();
+    let mut controller = Controller::default();
+    let now = s2n_quic_platform::time::now();
+    let rtt = Duration::from_millis(50);
+    let mut sent_packet_ecn_counts = EcnCounts::default();
+
+    for _ in 0..TESTING_PACKET_THRESHOLD {
+        let ecn = controller.ecn(transmission::Mode::Normal, now);
+        assert_eq!(ExplicitCongestionNotification::Ect0, ecn);
+        sent_packet_ecn_counts.increment(ecn);
+        controller.on_packet_sent(ecn, Path::test(), &mut publisher);
+    }
+
+    assert_eq!(State::Unknown, controller.state);
+
+    // The peer reports all of the ECT(0) marked packets
+    let outcome = controller.validate(
+        sent_packet_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        Some(sent_packet_ecn_counts),
+        now,
+        rtt,
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Passed, outcome);
+    assert!(controller.is_capable());
+    assert_eq!(
+        ExplicitCongestionNotification::Ect0,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+}
+
+#[test]
+fn validation_lifecycle_bleached() {
+    let mut publisher = Publisher::snapshot();
+    let mut controller = Controller::default();
+    let now = s2n_quic_platform::time::now();
+    let mut sent_packet_ecn_counts = EcnCounts::default();
+
+    for _ in 0..TESTING_PACKET_THRESHOLD {
+        let ecn = controller.ecn(transmission::Mode::Normal, now);
+        sent_packet_ecn_counts.increment(ecn);
+        controller.on_packet_sent(ecn, Path::test(), &mut publisher);
+    }
+
+    // A network element cleared the ECN markings, so the peer reports no ECN counts
+    let outcome = controller.validate(
+        sent_packet_ecn_counts,
+        sent_packet_ecn_counts,
+        EcnCounts::default(),
+        None,
+        now,
+        Duration::default(),
+        Path::test(),
+        &mut publisher,
+    );
+
+    assert_eq!(ValidationOutcome::Failed, outcome);
+    assert!(matches!(controller.state, State::Failed(_)));
+    assert!(!controller.is_capable());
+    // Packets are no longer marked once validation has failed
+    assert_eq!(
+        ExplicitCongestionNotification::NotEct,
+        controller.ecn(transmission::Mode::Normal, now)
+    );
+}
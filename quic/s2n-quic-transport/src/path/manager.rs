@@ -386,7 +386,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         // estimator for the new path, and they are initialized with initial values,
         // we do not need to reset congestion controller and round-trip time estimator
         // again on confirming the peer's ownership of its new address.
-        let rtt = RttEstimator::new(self.active_path().rtt_estimator.max_ack_delay());
+        let active_rtt_estimator = &self.active_path().rtt_estimator;
+        let rtt = RttEstimator::new(active_rtt_estimator.max_ack_delay())
+            .with_persistent_congestion_threshold(
+                active_rtt_estimator.persistent_congestion_multiplier(),
+            )
+            .with_timer_granularity(active_rtt_estimator.timer_granularity());
         let path_info = congestion_controller::PathInfo::new(&remote_address);
         let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
 
@@ -15,6 +15,7 @@ use s2n_quic_core::{
     counter::{Counter, Saturating},
     event::{self, IntoEvent},
     frame, packet, random,
+    recovery::resumption::ResumptionState,
     time::{timer, Timestamp},
 };
 
@@ -253,6 +254,34 @@ impl<Config: endpoint::Config> Path<Config> {
                 .map_or(true, |edt| edt.has_elapsed(timestamp))
     }
 
+    /// Returns the reason this path is unable to transmit further packets at the given timestamp
+    ///
+    /// `None` is returned if the path is at the amplification limit, as this is not a
+    /// limit imposed by congestion control.
+    #[inline]
+    pub fn transmission_limit_cause(
+        &self,
+        timestamp: Timestamp,
+    ) -> Option<event::builder::TransmissionLimitCause> {
+        if self.at_amplification_limit() {
+            return None;
+        }
+
+        let cause = if self.congestion_controller.is_congestion_limited() {
+            event::builder::TransmissionLimitCause::CongestionLimited
+        } else if !self.can_transmit(timestamp) {
+            event::builder::TransmissionLimitCause::PacingLimited
+        } else {
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.8
+            //# When bytes in flight is smaller than the congestion window and
+            //# sending is not pacing limited, the congestion window is
+            //# underutilized.
+            event::builder::TransmissionLimitCause::AppLimited
+        };
+
+        Some(cause)
+    }
+
     /// Only PATH_CHALLENGE and PATH_RESPONSE frames should be transmitted here.
     #[inline]
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
@@ -552,6 +581,13 @@ impl<Config: endpoint::Config> Path<Config> {
         cwnd.saturating_sub(bytes_in_flight) < mtu
     }
 
+    /// Exports the congestion and RTT state of the path so a later connection to the
+    /// same peer can resume from it
+    #[inline]
+    pub fn resumption_state(&self) -> ResumptionState {
+        ResumptionState::new(&self.congestion_controller, &self.rtt_estimator)
+    }
+
     // Compare a Path based on its PathHandle.
     //
     // Currently the local_address on the Client connection is unknown and set to
@@ -1218,6 +1254,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn transmission_limit_cause() {
+        let mut path = testing::helper_path_client();
+        let now = NoopClock.get_time();
+        let mtu = path.mtu_controller.mtu() as u32;
+
+        path.congestion_controller.congestion_window = 12000;
+        path.congestion_controller.bytes_in_flight = 12000 - mtu;
+        assert!(matches!(
+            path.transmission_limit_cause(now),
+            Some(event::builder::TransmissionLimitCause::AppLimited)
+        ));
+
+        path.congestion_controller.earliest_departure_time = Some(now + Duration::from_millis(1));
+        assert!(matches!(
+            path.transmission_limit_cause(now),
+            Some(event::builder::TransmissionLimitCause::PacingLimited)
+        ));
+
+        // The congestion window takes precedence over pacing
+        path.congestion_controller.bytes_in_flight = 12000;
+        assert!(matches!(
+            path.transmission_limit_cause(now),
+            Some(event::builder::TransmissionLimitCause::CongestionLimited)
+        ));
+
+        // The amplification limit is not reported
+        let path = testing::helper_path_server();
+        assert!(path.at_amplification_limit());
+        assert!(path.transmission_limit_cause(now).is_none());
+    }
+
     #[test]
     fn is_congestion_limited() {
         let mut path = testing::helper_path_client();
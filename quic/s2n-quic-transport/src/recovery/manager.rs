@@ -12,22 +12,29 @@ use crate::{
 };
 use core::{cmp::max, time::Duration};
 use s2n_quic_core::{
+    ack,
+    connection::limits::Limits,
     event::{
         self,
-        builder::{CongestionSource, SlowStartExitCause},
+        builder::{CongestionSource, CongestionWindowIncreaseCause, SlowStartExitCause},
         IntoEvent,
     },
     frame,
     frame::ack::EcnCounts,
     inet::ExplicitCongestionNotification,
     packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
-    recovery::{congestion_controller, CongestionController, RttEstimator, K_GRANULARITY},
+    recovery::{
+        congestion_controller, ecn_mark_rate::EcnMarkRate, loss_rate::LossRate,
+        loss_timers::LossTimers, CongestionController, RttEstimator,
+    },
     time::{timer, Timer, Timestamp},
-    transport,
+    transport::{self, parameters::AckDelayExponent},
+    varint::VarInt,
 };
 use smallvec::SmallVec;
 
 type PacketDetails<PacketInfo> = (PacketNumber, SentPacketInfo<PacketInfo>);
+type LostPacketDetails<PacketInfo> = (PacketNumber, SentPacketInfo<PacketInfo>, LossReason);
 
 #[derive(Debug)]
 pub struct Manager<Config: endpoint::Config> {
@@ -64,6 +71,41 @@ pub struct Manager<Config: endpoint::Config> {
 
     // The total ecn counts for outstanding (unacknowledged) packets
     sent_packet_ecn_counts: EcnCounts,
+
+    // The smoothed fraction of ECN-marked packets reported as CE in ACK frames
+    ecn_mark_rate: EcnMarkRate,
+
+    // The fraction of recently acknowledged or lost packets that were lost
+    loss_rate: LossRate,
+
+    // The largest packet number sent in the packet number space so far
+    largest_sent_packet: Option<PacketNumber>,
+
+    // The total bytes acknowledged in the packet number space, across all recovery periods
+    total_delivered: u64,
+
+    // The largest packet number sent when the current round trip started. The round trip
+    // ends when a packet sent after this one is acknowledged.
+    round_end_packet: Option<PacketNumber>,
+
+    // The exponent the peer uses to encode the ACK Delay field of its ACK frames
+    ack_delay_exponent: u8,
+
+    // The congestion window when receive buffer advice was last published, or 0 if it
+    // has not been published
+    advised_congestion_window: u32,
+
+    // The maximum number of sent packets to track before the oldest are declared lost
+    max_tracked_packets: Option<usize>,
+
+    // The RTT multiplier used for the time threshold, as a (numerator, denominator) pair
+    time_threshold: (u32, u32),
+
+    // The multiple of the min RTT below which the time threshold is not reduced
+    min_rtt_loss_floor: Option<u32>,
+
+    // The number of ack-eliciting packets that have not been acknowledged or declared lost
+    ack_eliciting_count: usize,
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.1
@@ -74,6 +116,11 @@ pub struct Manager<Config: endpoint::Config> {
 //# [RFC5681].
 const K_PACKET_THRESHOLD: u64 = 3;
 
+//= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
+//# The RECOMMENDED time threshold (kTimeThreshold), expressed as an
+//# RTT multiplier, is 9/8.
+const K_TIME_THRESHOLD: (u32, u32) = (9, 8);
+
 /// Initial capacity of the SmallVec used for keeping track of packets
 /// acked in an ack frame
 // TODO: Determine if there is a more appropriate default
@@ -128,13 +175,86 @@ impl<Config: endpoint::Config> Manager<Config> {
             time_of_last_ack_eliciting_packet: None,
             baseline_ecn_counts: EcnCounts::default(),
             sent_packet_ecn_counts: EcnCounts::default(),
+            ecn_mark_rate: EcnMarkRate::default(),
+            loss_rate: LossRate::default(),
+            largest_sent_packet: None,
+            total_delivered: 0,
+            round_end_packet: None,
+            ack_delay_exponent: 0,
+            advised_congestion_window: 0,
+            max_tracked_packets: None,
+            time_threshold: K_TIME_THRESHOLD,
+            min_rtt_loss_floor: None,
+            ack_eliciting_count: 0,
         }
     }
 
+    /// Sets the `ack_delay_exponent` transport parameter sent by the peer
+    ///
+    /// ACK delays are decoded using this exponent before they are passed to the RTT estimator.
+    /// Managers without a configured exponent use the ACK delay as reported.
+    pub fn with_ack_delay_exponent(mut self, ack_delay_exponent: AckDelayExponent) -> Self {
+        self.ack_delay_exponent = ack_delay_exponent.as_u8();
+        self
+    }
+
+    /// Limits the number of unacknowledged packets tracked in the packet number space
+    ///
+    /// A peer that never acknowledges packets would otherwise cause the sent packets to grow
+    /// without bound. Once more than `max_tracked_packets` are tracked, the oldest packets are
+    /// declared lost so their frames are retransmitted. Managers without a limit track every
+    /// packet until it is acknowledged or declared lost.
+    pub fn with_max_tracked_packets(mut self, max_tracked_packets: usize) -> Self {
+        debug_assert!(
+            max_tracked_packets > 0,
+            "at least one packet must be tracked"
+        );
+        self.max_tracked_packets = Some(max_tracked_packets);
+        self
+    }
+
+    /// Sets the RTT multiplier used for time threshold loss detection to
+    /// `numerator / denominator`
+    ///
+    /// Packets are declared lost once they were sent more than this multiple of the RTT
+    /// before an acknowledged packet. Managers without a configured multiplier use 9/8.
+    pub fn with_time_threshold(mut self, numerator: u32, denominator: u32) -> Self {
+        debug_assert_ne!(denominator, 0, "the denominator must be non-zero");
+        self.time_threshold = (numerator, denominator.max(1));
+        self
+    }
+
+    /// Sets a floor on the time threshold of `multiplier` times the min RTT
+    ///
+    /// A packet delayed by a transient RTT spike may arrive after packets sent later, and
+    /// be declared lost before its acknowledgement is received. The floor gives such packets
+    /// at least `multiplier` min RTTs to be acknowledged. Managers without a floor only use
+    /// the smoothed and latest RTT.
+    pub fn with_min_rtt_loss_floor(mut self, multiplier: u32) -> Self {
+        self.min_rtt_loss_floor = Some(multiplier);
+        self
+    }
+
+    /// Applies the loss detection settings configured in the connection limits
+    pub fn with_limits(mut self, limits: &Limits) -> Self {
+        if let Some(max_tracked_packets) = limits.max_tracked_packets() {
+            self = self.with_max_tracked_packets(max_tracked_packets);
+        }
+        if let Some((numerator, denominator)) = limits.time_threshold() {
+            self = self.with_time_threshold(numerator, denominator);
+        }
+        if let Some(multiplier) = limits.min_rtt_loss_floor() {
+            self = self.with_min_rtt_loss_floor(multiplier);
+        }
+        self
+    }
+
     /// Invoked when the Client processes a Retry packet.
     ///
     /// Reset congestion controller state by discarding sent bytes and replacing recovery
-    /// manager with a new instance of itself.
+    /// manager with a new instance of itself. Any RTT samples are also discarded, since
+    /// they may have been measured against the packets the Retry replaced. The configured
+    /// initial RTT and loss detection settings are retained.
     pub fn on_retry_packet(&mut self, path: &mut Path<Config>) {
         debug_assert!(
             Config::ENDPOINT_TYPE.is_client(),
@@ -148,7 +268,18 @@ impl<Config: endpoint::Config> Manager<Config> {
         path.congestion_controller
             .on_packet_discarded(discarded_bytes);
 
-        *self = Self::new(self.space);
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.3
+        //# Clients that receive a Retry packet reset congestion control and loss
+        //# recovery state, including resetting any pending timers.
+        path.rtt_estimator.reset();
+
+        *self = Self {
+            ack_delay_exponent: self.ack_delay_exponent,
+            max_tracked_packets: self.max_tracked_packets,
+            time_threshold: self.time_threshold,
+            min_rtt_loss_floor: self.min_rtt_loss_floor,
+            ..Self::new(self.space)
+        };
     }
 
     pub fn on_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
@@ -159,14 +290,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         publisher: &mut Pub,
     ) {
         if self.loss_timer.is_armed() {
-            if self.loss_timer.poll_expiration(timestamp).is_ready() {
-                self.detect_and_remove_lost_packets(
-                    timestamp,
-                    random_generator,
-                    context,
-                    publisher,
-                );
-            }
+            self.poll_loss_timer(timestamp, random_generator, context, publisher);
         } else {
             let pto_expired = self
                 .pto
@@ -183,7 +307,8 @@ impl<Config: endpoint::Config> Manager<Config> {
                 // Note: the psuedocode updates the pto timer in OnLossDetectionTimeout
                 // (see section A.9). We don't do that here since it will be rearmed in
                 // `on_packet_sent`, which immediately follows a timeout.
-                context.path_mut().pto_backoff *= 2;
+                let path_id = context.path_id();
+                self.on_pto_expired(context.path_mut(), path_id, publisher);
             }
         }
 
@@ -192,6 +317,57 @@ impl<Config: endpoint::Config> Manager<Config> {
         publisher.on_recovery_metrics(recovery_event!(path_id, path));
     }
 
+    /// Called when the connection timer expired and the PTO timers of all packet number
+    /// spaces are expired together with [`on_pto_timeout`]
+    ///
+    /// Only the loss timer is expired, since expiring the PTO timer of each space on its own
+    /// would expire more than the earliest PTO timer.
+    pub fn on_loss_timeout<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        random_generator: &mut Config::RandomGenerator,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        self.poll_loss_timer(timestamp, random_generator, context, publisher);
+
+        let path_id = context.path_id().as_u8();
+        let path = context.path_mut();
+        publisher.on_recovery_metrics(recovery_event!(path_id, path));
+    }
+
+    /// Detects lost packets if the loss timer has expired
+    fn poll_loss_timer<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        random_generator: &mut Config::RandomGenerator,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        if self.loss_timer.poll_expiration(timestamp).is_ready() {
+            self.detect_and_remove_lost_packets(timestamp, random_generator, context, publisher);
+        }
+    }
+
+    /// Increases the PTO backoff of the path and publishes a `PtoExpired` event after the
+    /// PTO timer expired
+    fn on_pto_expired<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path: &mut Path<Config>,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
+        path.pto_backoff *= 2;
+
+        if let PtoState::RequiresTransmission(probe_count) = self.pto.state {
+            publisher.on_pto_expired(event::builder::PtoExpired {
+                path: path_event!(path, path_id),
+                space: self.space.into_event(),
+                probe_count,
+            });
+        }
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-A.5
     //# After a packet is sent, information about the packet is stored.
     #[allow(clippy::too_many_arguments)]
@@ -221,6 +397,11 @@ impl<Config: endpoint::Config> Manager<Config> {
             0
         };
 
+        self.largest_sent_packet = Some(
+            self.largest_sent_packet
+                .map_or(packet_number, |largest| largest.max(packet_number)),
+        );
+
         let path_id = context.path_id();
         let path = context.path_mut();
         let cc_packet_info = path.congestion_controller.on_packet_sent(
@@ -229,6 +410,8 @@ impl<Config: endpoint::Config> Manager<Config> {
             app_limited,
             &path.rtt_estimator,
         );
+        path.congestion_controller
+            .on_packet_number_sent(packet_number);
 
         self.sent_packets.insert(
             packet_number,
@@ -246,6 +429,10 @@ impl<Config: endpoint::Config> Manager<Config> {
         path.ecn_controller
             .on_packet_sent(ecn, path_event!(path, path_id), publisher);
         self.sent_packet_ecn_counts.increment(ecn);
+        if outcome.ack_elicitation.is_ack_eliciting() {
+            self.ack_eliciting_count += 1;
+        }
+        self.shed_excess_tracked_packets(time_sent, context, publisher);
 
         if outcome.is_congestion_controlled {
             if outcome.ack_elicitation.is_ack_eliciting() {
@@ -255,8 +442,37 @@ impl<Config: endpoint::Config> Manager<Config> {
             //# A sender SHOULD restart its PTO timer every time an ack-eliciting
             //# packet is sent or acknowledged,
             let is_handshake_confirmed = context.is_handshake_confirmed();
-            let path = context.path_mut_by_id(context.path_id());
-            self.update_pto_timer(path, time_sent, is_handshake_confirmed);
+            let path = context.path_mut_by_id(path_id);
+            self.update_pto_timer_and_publish(
+                path,
+                path_id,
+                time_sent,
+                is_handshake_confirmed,
+                publisher,
+            );
+        }
+    }
+
+    /// Updates the PTO timer and publishes a `PtoArmed` event if it was armed with a new expiration
+    fn update_pto_timer_and_publish<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path: &Path<Config>,
+        path_id: path::Id,
+        now: Timestamp,
+        is_handshake_confirmed: bool,
+        publisher: &mut Pub,
+    ) {
+        let previous_expiration = timer::Provider::next_expiration(&self.pto);
+
+        self.update_pto_timer(path, now, is_handshake_confirmed);
+
+        let expiration = timer::Provider::next_expiration(&self.pto);
+        if expiration.is_some() && expiration != previous_expiration {
+            publisher.on_pto_armed(event::builder::PtoArmed {
+                path: path_event!(path, path_id),
+                space: self.space.into_event(),
+                pto_period: path.pto_period(self.space),
+            });
         }
     }
 
@@ -348,7 +564,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                 PacketNumberRange::new(space.new_packet_number(start), space.new_packet_number(end))
             }),
             largest_acked_packet_number,
-            frame.ack_delay(),
+            self.decode_ack_delay(frame.ack_delay),
             frame.ecn_counts,
             packet_number,
             random_generator,
@@ -359,6 +575,20 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(())
     }
 
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-19.3
+    //# It is decoded by
+    //# multiplying the value in the field by 2 to the power of the
+    //# ack_delay_exponent transport parameter sent by the sender of the
+    //# ACK frame; see Section 18.2.
+    #[inline]
+    fn decode_ack_delay(&self, ack_delay: VarInt) -> Duration {
+        ack::Settings {
+            ack_delay_exponent: self.ack_delay_exponent,
+            ..ack::Settings::RECOMMENDED
+        }
+        .decode_ack_delay(ack_delay)
+    }
+
     /// Generic interface for processing ACK ranges.
     #[allow(clippy::too_many_arguments)]
     fn process_acks<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
@@ -412,7 +642,7 @@ impl<Config: endpoint::Config> Manager<Config> {
                 publisher,
             );
 
-            let (_, largest_newly_acked_info) = largest_newly_acked;
+            let (largest_newly_acked_packet_number, largest_newly_acked_info) = largest_newly_acked;
             self.process_new_acked_packets(
                 &newly_acked_packets,
                 largest_newly_acked_info,
@@ -423,15 +653,49 @@ impl<Config: endpoint::Config> Manager<Config> {
                 context,
                 publisher,
             );
+            self.update_round_trip(largest_newly_acked_packet_number, context);
         }
 
-        let path_id = context.path_id().as_u8();
-        let path = context.path_mut();
-        publisher.on_recovery_metrics(recovery_event!(path_id, path));
+        let path_id = context.path_id();
+        let path = context.path();
+        let path_id_idx = path_id.as_u8();
+        publisher.on_recovery_metrics(recovery_event!(path_id_idx, path));
+        self.publish_receive_buffer_advice(path, path_id, publisher);
 
         Ok(())
     }
 
+    /// Publishes a `ReceiveBufferAdvice` event if the congestion window of the path has
+    /// changed significantly since advice was last published
+    fn publish_receive_buffer_advice<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path: &Path<Config>,
+        path_id: path::Id,
+        publisher: &mut Pub,
+    ) {
+        // The congestion window must change by at least a quarter of the previously
+        // advised window for new advice to be published
+        const SIGNIFICANT_CHANGE_DIVISOR: u32 = 4;
+
+        let congestion_window = path.congestion_controller.congestion_window();
+        let change = congestion_window.max(self.advised_congestion_window)
+            - congestion_window.min(self.advised_congestion_window);
+        if self.advised_congestion_window != 0
+            && change < self.advised_congestion_window / SIGNIFICANT_CHANGE_DIVISOR
+        {
+            return;
+        }
+
+        if let Some(bdp_estimate) = self.bdp_estimate(path) {
+            self.advised_congestion_window = congestion_window;
+            publisher.on_receive_buffer_advice(event::builder::ReceiveBufferAdvice {
+                path: path_event!(path, path_id),
+                congestion_window,
+                suggested_receive_buffer_size: bdp_estimate.saturating_mul(2),
+            });
+        }
+    }
+
     // Process ack_range and return largest_newly_acked and if the packet is ack eliciting.
     fn process_ack_range<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
@@ -483,7 +747,10 @@ impl<Config: endpoint::Config> Manager<Config> {
                     newly_acked_range = Some((packet_number, packet_number));
                 };
 
-                includes_ack_eliciting |= acked_packet_info.ack_elicitation.is_ack_eliciting();
+                if acked_packet_info.ack_elicitation.is_ack_eliciting() {
+                    includes_ack_eliciting = true;
+                    self.ack_eliciting_count = self.ack_eliciting_count.saturating_sub(1);
+                }
 
                 let path = context.path_mut_by_id(acked_packet_info.path_id);
                 path.mtu_controller.on_packet_ack(
@@ -595,11 +862,14 @@ impl<Config: endpoint::Config> Manager<Config> {
         let mut current_path_acked_bytes = 0;
         let mut newly_acked_ecn_counts = EcnCounts::default();
 
+        self.loss_rate.on_ack(newly_acked_packets.len() as u32);
+
         for acked_packet_info in newly_acked_packets {
             let path = context.path_mut_by_id(acked_packet_info.path_id);
 
             let sent_bytes = acked_packet_info.sent_bytes as usize;
             newly_acked_ecn_counts.increment(acked_packet_info.ecn);
+            self.total_delivered += sent_bytes as u64;
 
             if acked_packet_info.path_id == current_path_id {
                 current_path_acked_bytes += sent_bytes;
@@ -614,8 +884,17 @@ impl<Config: endpoint::Config> Manager<Config> {
                     random_generator,
                     timestamp,
                 );
+                let path_id = acked_packet_info.path_id;
+                if path.congestion_controller.congestion_window() > congestion_window {
+                    publisher.on_congestion_window_increased(
+                        event::builder::CongestionWindowIncreased {
+                            path: path_event!(path, path_id),
+                            cause: congestion_window_increase_cause(slow_start),
+                            congestion_window: path.congestion_controller.congestion_window(),
+                        },
+                    );
+                }
                 if slow_start && !path.congestion_controller.is_slow_start() {
-                    let path_id = acked_packet_info.path_id;
                     publisher.on_slow_start_exited(event::builder::SlowStartExited {
                         path: path_event!(path, path_id),
                         cause: SlowStartExitCause::Other,
@@ -638,7 +917,13 @@ impl<Config: endpoint::Config> Manager<Config> {
             }
 
             if acked_packet_info.path_id != current_path_id {
-                self.update_pto_timer(path, timestamp, is_handshake_confirmed);
+                self.update_pto_timer_and_publish(
+                    path,
+                    acked_packet_info.path_id,
+                    timestamp,
+                    is_handshake_confirmed,
+                    publisher,
+                );
             }
         }
 
@@ -667,6 +952,8 @@ impl<Config: endpoint::Config> Manager<Config> {
         let path = context.path_mut();
 
         if current_path_acked_bytes > 0 {
+            let slow_start = path.congestion_controller.is_slow_start();
+            let congestion_window = path.congestion_controller.congestion_window();
             path.congestion_controller.on_ack(
                 largest_newly_acked.time_sent,
                 current_path_acked_bytes,
@@ -675,9 +962,42 @@ impl<Config: endpoint::Config> Manager<Config> {
                 random_generator,
                 timestamp,
             );
+            if path.congestion_controller.congestion_window() > congestion_window {
+                publisher.on_congestion_window_increased(
+                    event::builder::CongestionWindowIncreased {
+                        path: path_event!(path, current_path_id),
+                        cause: congestion_window_increase_cause(slow_start),
+                        congestion_window: path.congestion_controller.congestion_window(),
+                    },
+                );
+            }
+
+            self.update_pto_timer_and_publish(
+                path,
+                current_path_id,
+                timestamp,
+                is_handshake_confirmed,
+                publisher,
+            );
+        }
+    }
 
-            self.update_pto_timer(path, timestamp, is_handshake_confirmed);
+    /// Notifies the congestion controller if acknowledging `largest_newly_acked` completes
+    /// the current round trip
+    fn update_round_trip<Ctx: Context<Config>>(
+        &mut self,
+        largest_newly_acked: PacketNumber,
+        context: &mut Ctx,
+    ) {
+        if self.round_end_packet.map_or(false, |round_end_packet| {
+            largest_newly_acked <= round_end_packet
+        }) {
+            return;
         }
+
+        // The next round trip ends once a packet sent after this point is acknowledged
+        self.round_end_packet = self.largest_sent_packet;
+        context.path_mut().congestion_controller.on_round_start();
     }
 
     fn process_ecn<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
@@ -729,6 +1049,14 @@ impl<Config: endpoint::Config> Manager<Config> {
             })
         }
 
+        if let Some(ack_frame_ecn_counts) = ack_frame_ecn_counts {
+            self.ecn_mark_rate.on_ack(ack_frame_ecn_counts);
+            context
+                .path_mut()
+                .congestion_controller
+                .on_ecn_mark_rate(self.ecn_mark_rate.rate());
+        }
+
         self.baseline_ecn_counts = ack_frame_ecn_counts.unwrap_or_default();
         self.sent_packet_ecn_counts -= newly_acked_ecn_counts;
     }
@@ -739,6 +1067,144 @@ impl<Config: endpoint::Config> Manager<Config> {
         matches!(self.pto.state, PtoState::RequiresTransmission(_))
     }
 
+    /// Returns true if `threshold` or fewer packet numbers remain in the packet number space
+    ///
+    /// The endpoint should close the connection gracefully before the space is exhausted.
+    #[inline]
+    pub fn is_space_nearly_exhausted(&self, threshold: u64) -> bool {
+        // Packet numbers are limited to 2^62-1, the largest value a VarInt can hold
+        let largest_sent = self
+            .largest_sent_packet
+            .map_or(0, |packet_number| packet_number.as_u64());
+        VarInt::MAX.as_u64() - largest_sent <= threshold
+    }
+
+    /// Returns how long after a packet is sent it may be declared lost by the time
+    /// threshold, based on the current RTT estimate of the given `path`
+    ///
+    /// The loss timer must be rescheduled whenever this value changes.
+    #[inline]
+    pub fn loss_delay(&self, path: &Path<Config>) -> Duration {
+        self.calculate_loss_time_threshold(&path.rtt_estimator)
+    }
+
+    /// Returns the fraction of recently acknowledged or lost packets that were lost,
+    /// from 0.0 to 1.0
+    pub fn loss_rate(&self) -> f32 {
+        self.loss_rate.rate()
+    }
+
+    /// Returns the number of bytes in flight in the packet number space for packets sent
+    /// on the given path
+    pub fn bytes_in_flight(&self, path_id: path::Id) -> u32 {
+        self.sent_packets
+            .iter()
+            .filter(|(_, sent_info)| sent_info.path_id == path_id)
+            .map(|(_, sent_info)| sent_info.sent_bytes as u32)
+            .sum()
+    }
+
+    /// Returns the smoothed fraction of ECN-marked packets that the peer reported as
+    /// CE, from 0.0 to 1.0
+    pub fn ecn_mark_rate(&self) -> f32 {
+        self.ecn_mark_rate.rate()
+    }
+
+    /// Returns the total number of bytes acknowledged in the packet number space
+    ///
+    /// Unlike the bytes delivered tracked by PRR, this is not reset when a new
+    /// recovery period starts.
+    #[inline]
+    pub fn total_delivered(&self) -> u64 {
+        self.total_delivered
+    }
+
+    /// Returns the time the most recent ack-eliciting packet was sent, if any
+    ///
+    /// This can be used to determine how long a connection has been idle from the
+    /// perspective of the peer, for example when scheduling keep-alive PINGs.
+    #[inline]
+    pub fn time_of_last_ack_eliciting(&self) -> Option<Timestamp> {
+        self.time_of_last_ack_eliciting_packet
+    }
+
+    /// Returns the probes that need to be sent following a PTO expiration, if any
+    #[inline]
+    pub fn probe_request(&self) -> Option<ProbeRequest> {
+        match self.pto.state {
+            PtoState::RequiresTransmission(count) => Some(ProbeRequest {
+                space: self.space,
+                count,
+            }),
+            PtoState::Idle => None,
+        }
+    }
+
+    /// Returns the number of bytes that may be transmitted on the given path at `now`
+    ///
+    /// This is the available congestion window, further limited by pacing and
+    /// Proportional Rate Reduction as applied by the congestion controller.
+    ///
+    /// This is a diagnostic value and is not consulted by the send loop. The send loop
+    /// continues while the path can transmit, as packets containing only ACK frames are
+    /// not limited by the congestion controller and must be sent even when the budget is
+    /// exhausted. Each packet space instead applies the transmission constraint of the
+    /// path to the frames it writes.
+    #[inline]
+    pub fn send_budget(&self, path: &Path<Config>, now: Timestamp) -> usize {
+        let budget = path.congestion_controller.send_budget(now);
+
+        if self.requires_probe() {
+            //= https://www.rfc-editor.org/rfc/rfc9002#section-7.5
+            //# Probe packets MUST NOT be blocked by the congestion controller.
+            return budget.max(path::MINIMUM_MTU as usize);
+        }
+
+        budget
+    }
+
+    /// Returns an estimate of the bandwidth-delay product of the given path in bytes
+    ///
+    /// The estimate is the delivery rate reported by the congestion controller multiplied
+    /// by the minimum RTT, and may be used as a hint for sizing receive windows. `None` is
+    /// returned until both an RTT sample and a delivery rate sample are available.
+    #[inline]
+    pub fn bdp_estimate(&self, path: &Path<Config>) -> Option<u64> {
+        path.rtt_estimator.first_rtt_sample()?;
+        let delivery_rate = path.congestion_controller.delivery_rate()?;
+
+        Some(delivery_rate * path.rtt_estimator.min_rtt())
+    }
+
+    /// Returns the number of ack-eliciting packets the peer may receive before it
+    /// should immediately send an acknowledgement
+    ///
+    /// The threshold allows roughly four acknowledgements per congestion window, which
+    /// keeps the congestion controller responsive while reducing acknowledgement
+    /// overhead on high-throughput paths. The threshold is requested from peers that support
+    /// ACK_FREQUENCY frames.
+    #[inline]
+    pub fn ack_eliciting_threshold(&self, path: &Path<Config>) -> u64 {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-13.2.2
+        //# A receiver SHOULD send an ACK frame after receiving at least two
+        //# ack-eliciting packets.
+        const DEFAULT_ACK_ELICITING_THRESHOLD: u64 = 1;
+        const ACKS_PER_CONGESTION_WINDOW: u64 = 4;
+
+        let congestion_window = path.congestion_controller.congestion_window() as u64;
+        let mtu = path.mtu_controller.mtu().max(1) as u64;
+
+        (congestion_window / (mtu * ACKS_PER_CONGESTION_WINDOW))
+            .max(DEFAULT_ACK_ELICITING_THRESHOLD)
+    }
+
+    /// Returns `true` if the number of outstanding ack-eliciting packets exceeds the
+    /// threshold the peer is expected to be using
+    #[inline]
+    pub fn is_ack_eliciting_threshold_exceeded(&self, path: &Path<Config>) -> bool {
+        self.ack_eliciting_count as u64 > self.ack_eliciting_threshold(path)
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#section-B.9
     //# When Initial or Handshake keys are discarded, packets sent in that
     //# space no longer count toward bytes in flight.
@@ -766,6 +1232,44 @@ impl<Config: endpoint::Config> Manager<Config> {
         }
         path.congestion_controller
             .on_packet_discarded(discarded_bytes);
+
+        // The discarded bytes are not returned to the path's anti-amplification allowance,
+        // since they were still transmitted to the peer
+    }
+
+    /// Invoked when the connection is closed, such as after the idle timeout
+    ///
+    /// Every unacknowledged packet is discarded and removed from the bytes in flight of the
+    /// path it was sent on, and the loss and PTO timers are cancelled. This keeps any
+    /// accounting from leaking into a congestion controller that is reused.
+    pub fn on_connection_close(&mut self, path_manager: &mut path::Manager<Config>) {
+        let mut path_ids: SmallVec<[path::Id; 2]> = SmallVec::new();
+        path_ids.push(path_manager.active_path_id());
+
+        for (_, unacked_sent_info) in self
+            .sent_packets
+            .remove_range(self.sent_packets.get_range())
+        {
+            let path_id = unacked_sent_info.path_id;
+            path_manager[path_id]
+                .congestion_controller
+                .on_packet_discarded(unacked_sent_info.sent_bytes as usize);
+
+            if !path_ids.contains(&path_id) {
+                path_ids.push(path_id);
+            }
+        }
+
+        for path_id in path_ids {
+            path_manager[path_id]
+                .congestion_controller
+                .on_connection_close();
+        }
+
+        self.loss_timer.cancel();
+        self.pto = Pto::default();
+        self.time_of_last_ack_eliciting_packet = None;
+        self.ack_eliciting_count = 0;
     }
 
     //= https://www.rfc-editor.org/rfc/rfc9002#section-A.10
@@ -790,10 +1294,79 @@ impl<Config: endpoint::Config> Manager<Config> {
             now,
             persistent_congestion_duration,
             sent_packets_to_remove,
-            random_generator,
+            Some(random_generator),
+            context,
+            publisher,
+        );
+    }
+
+    /// Declares the oldest sent packets lost while more than `max_tracked_packets` are tracked
+    ///
+    /// There is no evidence these packets were lost in the network, so their bytes are
+    /// discarded from the congestion controller without a congestion response.
+    fn shed_excess_tracked_packets<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
+        &mut self,
+        now: Timestamp,
+        context: &mut Ctx,
+        publisher: &mut Pub,
+    ) {
+        let excess = match self.max_tracked_packets {
+            Some(max_tracked_packets) => {
+                self.sent_packets.len().saturating_sub(max_tracked_packets)
+            }
+            None => return,
+        };
+
+        if excess == 0 {
+            return;
+        }
+
+        let mut sent_packets_to_remove = Vec::with_capacity(excess);
+        for (packet_number, sent_info) in self.sent_packets.iter().take(excess) {
+            sent_packets_to_remove.push((
+                packet_number,
+                *sent_info,
+                LossReason::TrackedPacketLimit,
+            ));
+
+            if sent_info.congestion_controlled {
+                let range = PacketNumberRange::new(packet_number, packet_number);
+                context.on_packet_loss(&range, LossReason::TrackedPacketLimit, publisher);
+            }
+        }
+
+        // As with packets declared lost, the ECN counts of the shed packets are retained, since
+        // the peer may still report them in the ECN counts of a later ACK frame
+        self.remove_lost_packets(
+            now,
+            Duration::ZERO,
+            sent_packets_to_remove,
+            None,
             context,
             publisher,
         );
+
+        // The loss timer may have been armed for one of the shed packets, so it is armed
+        // again for the oldest remaining packet sent prior to the largest acknowledged packet
+        if self.loss_timer.is_armed() {
+            self.loss_timer.cancel();
+
+            if let (Some(largest_acked_packet), Some((packet_number, sent_info))) =
+                (self.largest_acked_packet, self.sent_packets.iter().next())
+            {
+                if packet_number < largest_acked_packet {
+                    let path = context.path_by_id(sent_info.path_id);
+                    let packet_lost_time = sent_info.time_sent + self.loss_delay(path);
+                    self.loss_timer.set(packet_lost_time);
+                }
+            }
+        }
+
+        // The shed packets may have been the only ack-eliciting packets in flight
+        let path_id = context.path_id();
+        let is_handshake_confirmed = context.is_handshake_confirmed();
+        let path = context.path_by_id(path_id);
+        self.update_pto_timer_and_publish(path, path_id, now, is_handshake_confirmed, publisher);
     }
 
     fn detect_lost_packets<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
@@ -801,7 +1374,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         now: Timestamp,
         context: &mut Ctx,
         publisher: &mut Pub,
-    ) -> (Duration, Vec<PacketDetails<packet_info_type!()>>) {
+    ) -> (Duration, Vec<LostPacketDetails<packet_info_type!()>>) {
         let largest_acked_packet = self
             .largest_acked_packet
             .expect("This function is only called after an ack has been received");
@@ -823,7 +1396,7 @@ impl<Config: endpoint::Config> Manager<Config> {
             let unacked_path_id = unacked_sent_info.path_id;
             let path = &context.path_by_id(unacked_path_id);
             // Calculate how long we wait until a packet is declared lost
-            let time_threshold = Self::calculate_loss_time_threshold(&path.rtt_estimator);
+            let time_threshold = self.loss_delay(path);
             // Calculate at what time this particular packet is considered lost based on the
             // current path `time_threshold`
             let packet_lost_time = unacked_sent_info.time_sent + time_threshold;
@@ -847,14 +1420,19 @@ impl<Config: endpoint::Config> Manager<Config> {
             //#        acknowledged packet (Section 6.1.1), or it was sent long enough in
             //#        the past (Section 6.1.2).
             if time_threshold_exceeded || packet_number_threshold_exceeded {
-                sent_packets_to_remove.push((unacked_packet_number, *unacked_sent_info));
+                let reason = if packet_number_threshold_exceeded {
+                    LossReason::PacketThreshold
+                } else {
+                    LossReason::TimeThreshold
+                };
+                sent_packets_to_remove.push((unacked_packet_number, *unacked_sent_info, reason));
 
                 if unacked_sent_info.congestion_controlled {
                     // The packet is "in-flight", ie congestion controlled
                     // TODO merge contiguous packet numbers
                     let range =
                         PacketNumberRange::new(unacked_packet_number, unacked_packet_number);
-                    context.on_packet_loss(&range, publisher);
+                    context.on_packet_loss(&range, reason, publisher);
                 }
 
                 persistent_congestion_calculator
@@ -891,23 +1469,31 @@ impl<Config: endpoint::Config> Manager<Config> {
         )
     }
 
+    /// Removes the given lost packets from the sent packets
+    ///
+    /// When no `random_generator` is provided, the packets were not lost in the network. Their
+    /// bytes are discarded from the congestion controller without a congestion response.
     fn remove_lost_packets<Ctx: Context<Config>, Pub: event::ConnectionPublisher>(
         &mut self,
         now: Timestamp,
         persistent_congestion_duration: Duration,
-        sent_packets_to_remove: Vec<PacketDetails<packet_info_type!()>>,
-        random_generator: &mut Config::RandomGenerator,
+        sent_packets_to_remove: Vec<LostPacketDetails<packet_info_type!()>>,
+        mut random_generator: Option<&mut Config::RandomGenerator>,
         context: &mut Ctx,
         publisher: &mut Pub,
     ) {
         let current_path_id = context.path_id();
         let mut is_congestion_event = false;
         let mut prev_lost_packet_number = None;
+        let mut lost_packet_count = 0;
 
         // Remove the lost packets and account for the bytes on the proper congestion controller
-        for (packet_number, sent_info) in sent_packets_to_remove {
+        for (packet_number, sent_info, reason) in sent_packets_to_remove {
             let path = context.path_mut_by_id(sent_info.path_id);
             self.sent_packets.remove(packet_number);
+            if sent_info.ack_elicitation.is_ack_eliciting() {
+                self.ack_eliciting_count = self.ack_eliciting_count.saturating_sub(1);
+            }
 
             //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
             //# A sender that does not have state for all packet
@@ -923,7 +1509,13 @@ impl<Config: endpoint::Config> Manager<Config> {
                 packet_number.checked_distance(prev) != Some(1)
             });
 
-            if sent_info.transmission_mode.is_mtu_probing() {
+            // MTU probes are expected to be lost when the probed size exceeds the path
+            // MTU, so they are excluded from the loss rate
+            if !sent_info.is_mtu_probe() {
+                lost_packet_count += 1;
+            }
+
+            if sent_info.is_mtu_probe() {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-14.4
                 //# Loss of a QUIC packet that is carried in a PMTU probe is therefore not a
                 //# reliable indication of congestion and SHOULD NOT trigger a congestion
@@ -937,25 +1529,33 @@ impl<Config: endpoint::Config> Manager<Config> {
                 path.congestion_controller
                     .on_packet_discarded(sent_info.sent_bytes as usize);
             } else if sent_info.sent_bytes > 0 {
-                let slow_start = path.congestion_controller.is_slow_start();
-                let congestion_window = path.congestion_controller.congestion_window();
-                path.congestion_controller.on_packet_lost(
-                    sent_info.sent_bytes as u32,
-                    sent_info.cc_packet_info,
-                    persistent_congestion,
-                    new_loss_burst,
-                    random_generator,
-                    now,
-                );
-                if slow_start && !path.congestion_controller.is_slow_start() {
-                    let path_id = sent_info.path_id;
-                    publisher.on_slow_start_exited(event::builder::SlowStartExited {
-                        path: path_event!(path, path_id),
-                        cause: SlowStartExitCause::PacketLoss,
-                        congestion_window,
-                    });
+                match random_generator.as_deref_mut() {
+                    Some(random_generator) => {
+                        let slow_start = path.congestion_controller.is_slow_start();
+                        let congestion_window = path.congestion_controller.congestion_window();
+                        path.congestion_controller.on_packet_lost(
+                            sent_info.sent_bytes as u32,
+                            sent_info.cc_packet_info,
+                            persistent_congestion,
+                            new_loss_burst,
+                            random_generator,
+                            now,
+                        );
+                        if slow_start && !path.congestion_controller.is_slow_start() {
+                            let path_id = sent_info.path_id;
+                            publisher.on_slow_start_exited(event::builder::SlowStartExited {
+                                path: path_event!(path, path_id),
+                                cause: SlowStartExitCause::PacketLoss,
+                                congestion_window,
+                            });
+                        }
+                        is_congestion_event = true;
+                    }
+                    // The packet was not lost in the network, so there is no congestion response
+                    None => path
+                        .congestion_controller
+                        .on_packet_discarded(sent_info.sent_bytes as usize),
                 }
-                is_congestion_event = true;
             }
 
             publisher.on_packet_lost(event::builder::PacketLost {
@@ -965,30 +1565,34 @@ impl<Config: endpoint::Config> Manager<Config> {
                 ),
                 path: path_event!(path, current_path_id),
                 bytes_lost: sent_info.sent_bytes,
-                is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
+                is_mtu_probe: sent_info.is_mtu_probe(),
+                reason: reason.into_event(),
             });
 
-            // Notify the MTU controller of packet loss even if it wasn't a probe since it uses
-            // that information for blackhole detection.
-            path.mtu_controller.on_packet_loss(
-                packet_number,
-                sent_info.sent_bytes,
-                now,
-                &mut path.congestion_controller,
-                sent_info.path_id,
-                publisher,
-            );
+            // Packets that were not lost in the network are no indication of a blackhole
+            if random_generator.is_some() {
+                // Notify the MTU controller of packet loss even if it wasn't a probe since it uses
+                // that information for blackhole detection.
+                path.mtu_controller.on_packet_loss(
+                    packet_number,
+                    sent_info.sent_bytes,
+                    now,
+                    &mut path.congestion_controller,
+                    sent_info.path_id,
+                    publisher,
+                );
 
-            let path_id = sent_info.path_id;
+                let path_id = sent_info.path_id;
 
-            // Notify the ECN controller of packet loss for blackhole detection.
-            path.ecn_controller.on_packet_loss(
-                sent_info.time_sent,
-                sent_info.ecn,
-                now,
-                path_event!(path, path_id),
-                publisher,
-            );
+                // Notify the ECN controller of packet loss for blackhole detection.
+                path.ecn_controller.on_packet_loss(
+                    sent_info.time_sent,
+                    sent_info.ecn,
+                    now,
+                    path_event!(path, path_id),
+                    publisher,
+                );
+            }
 
             if persistent_congestion {
                 //= https://www.rfc-editor.org/rfc/rfc9002#section-5.2
@@ -1000,6 +1604,10 @@ impl<Config: endpoint::Config> Manager<Config> {
             prev_lost_packet_number = Some(packet_number);
         }
 
+        if lost_packet_count > 0 {
+            self.loss_rate.on_loss(lost_packet_count);
+        }
+
         if is_congestion_event {
             let path = context.path();
             publisher.on_congestion(event::builder::Congestion {
@@ -1009,24 +1617,81 @@ impl<Config: endpoint::Config> Manager<Config> {
         }
     }
 
-    fn calculate_loss_time_threshold(rtt_estimator: &RttEstimator) -> Duration {
+    fn calculate_loss_time_threshold(&self, rtt_estimator: &RttEstimator) -> Duration {
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
         //# The time threshold is:
         //#
         //# max(kTimeThreshold * max(smoothed_rtt, latest_rtt), kGranularity)
         let mut time_threshold = max(rtt_estimator.smoothed_rtt(), rtt_estimator.latest_rtt());
 
-        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
-        //# The RECOMMENDED time threshold (kTimeThreshold), expressed as an
-        //# RTT multiplier, is 9/8.
-        time_threshold = (time_threshold * 9) / 8;
+        let (numerator, denominator) = self.time_threshold;
+        time_threshold = (time_threshold * numerator) / denominator;
+
+        if let Some(multiplier) = self.min_rtt_loss_floor {
+            time_threshold = max(time_threshold, rtt_estimator.min_rtt() * multiplier);
+        }
 
         //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
         //# To avoid declaring
         //# packets as lost too early, this time threshold MUST be set to at
         //# least the local timer granularity, as indicated by the kGranularity
         //# constant.
-        max(time_threshold, K_GRANULARITY)
+        max(time_threshold, rtt_estimator.timer_granularity())
+    }
+}
+
+/// Invoked on a connection timeout with the recovery managers of each packet number space
+///
+/// Rather than each packet number space expiring its own PTO timer, only the space with the
+/// earliest PTO timer is expired. The PTO backoff of the path is increased and probes are
+/// requested in that space, even if nothing ack-eliciting is outstanding in it, such as when
+/// a client arms the PTO timer to unblock a server at its anti-amplification limit. Spaces
+/// with an armed loss timer are skipped, since their PTO timer must not be set. `None` is
+/// returned if no PTO timer has expired.
+pub fn on_pto_timeout<Config: endpoint::Config, Pub: event::ConnectionPublisher>(
+    managers: &mut [&mut Manager<Config>],
+    timestamp: Timestamp,
+    path: &mut Path<Config>,
+    path_id: path::Id,
+    publisher: &mut Pub,
+) -> Option<ProbeRequest> {
+    let mut pto_timers = LossTimers::default();
+    for manager in managers.iter() {
+        if manager.loss_timer.is_armed() {
+            continue;
+        }
+        if let Some(expiration) = timer::Provider::next_expiration(&manager.pto.timer) {
+            pto_timers.set(manager.space, expiration);
+        }
+    }
+
+    let (space, expiration) = pto_timers.earliest()?;
+
+    if !expiration.has_elapsed(timestamp) {
+        return None;
+    }
+
+    let manager = managers.iter_mut().find(|manager| manager.space == space)?;
+    manager.pto.timer.cancel();
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2
+    //# A PTO timer expiration event does not indicate packet loss and MUST
+    //# NOT cause prior unacknowledged packets to be marked as lost.
+    let packets_in_flight = !manager.sent_packets.is_empty();
+    manager.pto.on_expiration(packets_in_flight);
+    manager.on_pto_expired(path, path_id, publisher);
+
+    manager.probe_request()
+}
+
+/// Attributes an increase in the congestion window to the phase the congestion
+/// controller was in when the acknowledgement was received
+#[inline]
+fn congestion_window_increase_cause(slow_start: bool) -> CongestionWindowIncreaseCause {
+    if slow_start {
+        CongestionWindowIncreaseCause::SlowStart
+    } else {
+        CongestionWindowIncreaseCause::CongestionAvoidance
     }
 }
 
@@ -1079,11 +1744,36 @@ pub trait Context<Config: endpoint::Config> {
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number_range: &PacketNumberRange,
+        reason: LossReason,
         publisher: &mut Pub,
     );
     fn on_rtt_update(&mut self);
 }
 
+/// The loss detection threshold that caused a packet to be declared lost
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LossReason {
+    /// The packet was sent kPacketThreshold packets before an acknowledged packet
+    ///
+    /// This is also the reason given if the time threshold was exceeded as well.
+    PacketThreshold,
+    /// The packet was sent long enough in the past, without exceeding the packet threshold
+    TimeThreshold,
+    /// The packet was the oldest tracked packet when the limit on tracked packets was exceeded
+    TrackedPacketLimit,
+}
+
+impl IntoEvent<event::builder::PacketLossReason> for LossReason {
+    #[inline]
+    fn into_event(self) -> event::builder::PacketLossReason {
+        match self {
+            Self::PacketThreshold => event::builder::PacketLossReason::PacketThreshold,
+            Self::TimeThreshold => event::builder::PacketLossReason::TimeThreshold,
+            Self::TrackedPacketLimit => event::builder::PacketLossReason::TrackedPacketLimit,
+        }
+    }
+}
+
 impl<Config: endpoint::Config> transmission::interest::Provider for Manager<Config> {
     fn transmission_interest<Q: transmission::interest::Query>(
         &self,
@@ -1100,6 +1790,15 @@ struct Pto {
     state: PtoState,
 }
 
+/// The probe packets required by a recovery manager after its PTO timer expired
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProbeRequest {
+    /// The packet number space the probes should be sent in
+    pub space: PacketNumberSpace,
+    /// The number of ack-eliciting probe packets remaining to be sent
+    pub count: u8,
+}
+
 #[derive(Debug, PartialEq)]
 enum PtoState {
     Idle,
@@ -1116,36 +1815,41 @@ impl Pto {
     /// Called when a timeout has occurred. Returns true if the PTO timer had expired.
     pub fn on_timeout(&mut self, packets_in_flight: bool, timestamp: Timestamp) -> bool {
         if self.timer.poll_expiration(timestamp).is_ready() {
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-            //# When a PTO timer expires, a sender MUST send at least one ack-
-            //# eliciting packet in the packet number space as a probe.
-
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2.1
-            //# Since the server could be blocked until more datagrams are received
-            //# from the client, it is the client's responsibility to send packets to
-            //# unblock the server until it is certain that the server has finished
-            //# its address validation
-
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-            //# An endpoint
-            //# MAY send up to two full-sized datagrams containing ack-eliciting
-            //# packets to avoid an expensive consecutive PTO expiration due to a
-            //# single lost datagram or to transmit data from multiple packet number
-            //# spaces.
-
-            //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-            //# Sending two packets on PTO
-            //# expiration increases resilience to packet drops, thus reducing the
-            //# probability of consecutive PTO events.
-            let transmission_count = if packets_in_flight { 2 } else { 1 };
-
-            self.state = PtoState::RequiresTransmission(transmission_count);
+            self.on_expiration(packets_in_flight);
             true
         } else {
             false
         }
     }
 
+    /// Requests probe packets after the PTO timer expired
+    pub fn on_expiration(&mut self, packets_in_flight: bool) {
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+        //# When a PTO timer expires, a sender MUST send at least one ack-
+        //# eliciting packet in the packet number space as a probe.
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.2.1
+        //# Since the server could be blocked until more datagrams are received
+        //# from the client, it is the client's responsibility to send packets to
+        //# unblock the server until it is certain that the server has finished
+        //# its address validation
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+        //# An endpoint
+        //# MAY send up to two full-sized datagrams containing ack-eliciting
+        //# packets to avoid an expensive consecutive PTO expiration due to a
+        //# single lost datagram or to transmit data from multiple packet number
+        //# spaces.
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+        //# Sending two packets on PTO
+        //# expiration increases resilience to packet drops, thus reducing the
+        //# probability of consecutive PTO events.
+        let transmission_count = if packets_in_flight { 2 } else { 1 };
+
+        self.state = PtoState::RequiresTransmission(transmission_count);
+    }
+
     /// Queries the component for any outgoing frames that need to get sent
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) {
         if !context.transmission_mode().is_loss_recovery_probing() {
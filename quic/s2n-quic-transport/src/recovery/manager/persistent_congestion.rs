@@ -62,7 +62,7 @@ impl PersistentCongestionCalculator {
         }
 
         // Check if this lost packet was an MTU probe
-        if packet_info.transmission_mode.is_mtu_probing() {
+        if packet_info.is_mtu_probe() {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-14.4
             //# Loss of a QUIC packet that is carried in a PMTU probe is therefore not a
             //# reliable indication of congestion and SHOULD NOT trigger a congestion
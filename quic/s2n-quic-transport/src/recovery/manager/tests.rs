@@ -10,6 +10,8 @@ use crate::{
     path::MINIMUM_MTU,
     recovery,
     recovery::manager::PtoState::RequiresTransmission,
+    sync,
+    transmission::interest::Provider as _,
 };
 use core::{ops::RangeInclusive, time::Duration};
 use s2n_quic_core::{
@@ -21,15 +23,19 @@ use s2n_quic_core::{
     path::{migration, RemoteAddress, DEFAULT_MAX_MTU, INITIAL_PTO_BACKOFF},
     random,
     recovery::{
+        bandwidth::Bandwidth,
         congestion_controller::testing::mock::{
             CongestionController as MockCongestionController, Endpoint,
         },
-        DEFAULT_INITIAL_RTT,
+        DEFAULT_INITIAL_RTT, K_GRANULARITY,
     },
     time::{timer::Provider as _, Clock, NoopClock},
     varint::VarInt,
 };
-use std::{collections::HashSet, net::SocketAddr};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+};
 
 // alias the manager and paths over the config so we don't have to annotate it everywhere
 type Manager = super::Manager<Config>;
@@ -179,6 +185,10 @@ fn on_packet_sent() {
             .congestion_controller
             .bytes_in_flight
     );
+    assert_eq!(
+        expected_bytes_in_flight as u32,
+        manager.bytes_in_flight(first_path_id)
+    );
 }
 
 #[test]
@@ -525,6 +535,297 @@ fn on_ack_frame() {
     assert_eq!(3, context.on_rtt_update_count);
 }
 
+// Packets that only carry frames that don't need to be acknowledged or retransmitted,
+// such as ACK frames, are removed from the sent packets without generating an RTT sample
+// or notifying the congestion controller
+#[test]
+fn ack_only_packet() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    for (packet_number, ack_elicitation, bytes_sent) in [
+        (0, AckElicitation::Eliciting, 128),
+        (1, AckElicitation::NonEliciting, 0),
+    ] {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation,
+                is_congestion_controlled: bytes_sent > 0,
+                bytes_sent,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    ack_packets(
+        0..=0,
+        time_sent + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(context.path().congestion_controller.on_rtt_update, 1);
+    assert_eq!(context.path().congestion_controller.on_packet_ack, 1);
+    assert_eq!(
+        context.path().rtt_estimator.latest_rtt(),
+        Duration::from_millis(100)
+    );
+
+    ack_packets(
+        1..=1,
+        time_sent + Duration::from_millis(500),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert!(manager.sent_packets.is_empty());
+    assert_eq!(context.on_new_packet_ack_count, 2);
+    // Neither the RTT estimator nor the congestion controller are updated
+    assert_eq!(context.path().congestion_controller.on_rtt_update, 1);
+    assert_eq!(context.path().congestion_controller.on_packet_ack, 1);
+    assert_eq!(
+        context.path().rtt_estimator.latest_rtt(),
+        Duration::from_millis(100)
+    );
+    assert_eq!(1, context.on_rtt_update_count);
+}
+
+#[test]
+fn max_tracked_packets() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space).with_max_tracked_packets(3);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    // An ACK-only packet followed by ack-eliciting packets the peer never acknowledges
+    for (packet_number, bytes_sent) in [(1, 0), (2, 100), (3, 100), (4, 100), (5, 100)] {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: if bytes_sent > 0 {
+                    AckElicitation::Eliciting
+                } else {
+                    AckElicitation::NonEliciting
+                },
+                is_congestion_controlled: bytes_sent > 0,
+                bytes_sent,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::Ect0,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+        assert!(manager.sent_packets.len() <= 3);
+    }
+
+    // The two oldest packets are no longer tracked
+    assert_eq!(manager.sent_packets.len(), 3);
+    for packet_number in 1..=2 {
+        assert!(manager
+            .sent_packets
+            .get(space.new_packet_number(VarInt::from_u8(packet_number)))
+            .is_none());
+    }
+
+    // Only the ack-eliciting packet is reported lost, so its frames are retransmitted
+    assert_eq!(context.on_packet_loss_count, 1);
+    assert_eq!(
+        context
+            .lost_packets
+            .get(&space.new_packet_number(VarInt::from_u8(2))),
+        Some(&LossReason::TrackedPacketLimit)
+    );
+
+    // The shed packet is removed from flight without a congestion response
+    assert_eq!(context.path().congestion_controller.bytes_in_flight, 300);
+    assert_eq!(context.path().congestion_controller.congestion_events, 0);
+    assert_eq!(context.path().congestion_controller.lost_bytes, 0);
+
+    // The shed packets are still counted as sent for ECN validation
+    assert_eq!(
+        manager.sent_packet_ecn_counts.ect_0_count,
+        VarInt::from_u8(5)
+    );
+
+    // The shed packets are accounted for in the loss rate
+    assert!(manager.loss_rate() > 0.0);
+
+    // The PTO timer remains armed for the packets still in flight
+    assert!(manager.pto.timer.is_armed());
+}
+
+#[test]
+fn max_tracked_packets_rearms_loss_timer() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space).with_max_tracked_packets(2);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let loss_delay = manager.loss_delay(context.path());
+
+    manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(10)));
+
+    for packet_number in 1..=3 {
+        // The loss timer was armed for the oldest packet sent prior to the largest acknowledged
+        if packet_number == 3 {
+            manager
+                .loss_timer
+                .set(time_sent + Duration::from_millis(10) + loss_delay);
+        }
+
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 100,
+                bytes_progressed: 0,
+            },
+            time_sent + Duration::from_millis(packet_number as u64 * 10),
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // The first packet was shed, so the loss timer is armed for the second packet instead
+    assert!(manager
+        .sent_packets
+        .get(space.new_packet_number(VarInt::from_u8(1)))
+        .is_none());
+    let expected_expiration = Some(time_sent + Duration::from_millis(20) + loss_delay);
+    assert_eq!(manager.loss_timer.next_expiration(), expected_expiration);
+    assert_eq!(
+        timer::Provider::next_expiration(&manager),
+        expected_expiration
+    );
+}
+
+#[test]
+fn max_tracked_packets_limits() {
+    let space = PacketNumberSpace::ApplicationData;
+
+    // Every packet is tracked by default
+    let manager = Manager::new(space).with_limits(&Limits::default());
+    assert_eq!(manager.max_tracked_packets, None);
+
+    let limits = Limits::default().with_max_tracked_packets(100).unwrap();
+    let manager = Manager::new(space).with_limits(&limits);
+    assert_eq!(manager.max_tracked_packets, Some(100));
+}
+
+#[test]
+fn max_tracked_packets_ecn_validation() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space).with_max_tracked_packets(5);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    // Send 10 ECT0 marked packets, shedding the five oldest
+    for packet_number in 1..=10 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 100,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::Ect0,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+    assert_eq!(manager.sent_packets.len(), 5);
+
+    // The peer received every packet, so its ECN counts include the shed packets
+    let ack_ecn_counts = EcnCounts {
+        ect_0_count: VarInt::from_u8(10),
+        ect_1_count: Default::default(),
+        ce_count: Default::default(),
+    };
+    ack_packets(
+        1..=10,
+        time_sent + Duration::from_millis(500),
+        &mut context,
+        &mut manager,
+        Some(ack_ecn_counts),
+        &mut publisher,
+    );
+
+    // The ECN counts do not exceed the packets sent, so validation passes
+    assert!(context.path().ecn_controller.is_capable());
+    assert_eq!(ack_ecn_counts, manager.baseline_ecn_counts);
+}
+
+// A flight of packets that are not ack-eliciting will not be acknowledged until an
+// ack-eliciting packet is sent, so it must not arm the PTO timer
+#[test]
+fn ack_only_flight_does_not_arm_pto() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    // Validate the path so it is not amplification limited
+    context.path_mut().on_handshake_packet();
+
+    // ACK-only packets, along with non-ack-eliciting packets that count towards
+    // bytes in flight, such as those containing only PADDING
+    for (packet_number, bytes_sent) in [(1, 0), (2, 128), (3, 0), (4, 128)] {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::NonEliciting,
+                is_congestion_controlled: bytes_sent > 0,
+                bytes_sent,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    assert_eq!(manager.sent_packets.earliest_ack_eliciting(), None);
+    assert_eq!(manager.time_of_last_ack_eliciting(), None);
+    assert!(!manager.pto.timer.is_armed());
+
+    manager.update_pto_timer(context.path(), time_sent, true);
+    assert!(!manager.pto.timer.is_armed());
+}
+
 #[test]
 // pto_backoff reset should happen for the path the packet was sent on
 //
@@ -1000,6 +1301,77 @@ fn process_new_acked_packets_process_ecn() {
     assert!(context.path().ecn_controller.is_capable());
 }
 
+#[test]
+#[allow(clippy::float_cmp)]
+fn process_ecn_updates_ecn_mark_rate() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut publisher = Publisher::no_snapshot();
+
+    for i in 1..=10 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(i)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::Ect0,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+    assert_eq!(manager.ecn_mark_rate(), 0.0);
+
+    // One of the four acknowledged packets was marked CE
+    let ack_receive_time = time_sent + Duration::from_millis(500);
+    let ack_ecn_counts = EcnCounts {
+        ect_0_count: VarInt::from_u8(3),
+        ect_1_count: Default::default(),
+        ce_count: VarInt::from_u8(1),
+    };
+    ack_packets(
+        1..=4,
+        ack_receive_time,
+        &mut context,
+        &mut manager,
+        Some(ack_ecn_counts),
+        &mut publisher,
+    );
+    assert_eq!(manager.ecn_mark_rate(), 0.25 / 16.0);
+    assert_eq!(
+        context.path().congestion_controller.ecn_mark_rate,
+        Some(manager.ecn_mark_rate())
+    );
+
+    // None of the newly acknowledged packets were marked CE, so the rate decays
+    let ack_ecn_counts = EcnCounts {
+        ect_0_count: VarInt::from_u8(9),
+        ect_1_count: Default::default(),
+        ce_count: VarInt::from_u8(1),
+    };
+    ack_packets(
+        5..=10,
+        ack_receive_time,
+        &mut context,
+        &mut manager,
+        Some(ack_ecn_counts),
+        &mut publisher,
+    );
+    assert_eq!(manager.ecn_mark_rate(), 0.25 / 16.0 * 15.0 / 16.0);
+    assert_eq!(
+        context.path().congestion_controller.ecn_mark_rate,
+        Some(manager.ecn_mark_rate())
+    );
+}
+
 #[test]
 // Increase in ECN CE count should not cause congestion event if ECN validation fails
 //
@@ -1063,27 +1435,110 @@ fn process_new_acked_packets_failed_ecn_validation_does_not_cause_congestion_eve
     assert!(!context.path().ecn_controller.is_capable());
 }
 
-//= https://www.rfc-editor.org/rfc/rfc9002#section-5.1
-//= type=test
-//# To avoid generating multiple RTT samples for a single packet, an ACK
-//# frame SHOULD NOT be used to update RTT estimates if it does not newly
-//# acknowledge the largest acknowledged packet.
+// The ACK delay is decoded with the peer's exponent and then capped at the peer's
+// max_ack_delay, since the mock context reports the handshake as confirmed
 #[test]
-fn no_rtt_update_when_not_acknowledging_the_largest_acknowledged_packet() {
+fn ack_delay_exponent() {
     let space = PacketNumberSpace::ApplicationData;
-    let mut manager = Manager::new(space);
-    let packet_bytes = 128;
+    let mut manager =
+        Manager::new(space).with_ack_delay_exponent(AckDelayExponent::new(3u8).unwrap());
     let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
-    let ecn = ExplicitCongestionNotification::default();
     let mut context = MockContext::new(&mut path_manager);
-    let mut publisher = Publisher::snapshot();
-
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
     let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
 
-    // Send 2 packets
-    manager.on_packet_sent(
-        space.new_packet_number(VarInt::from_u8(0)),
-        transmission::Outcome {
+    for packet_number in packet_numbers(space).take(3) {
+        manager.on_packet_sent(
+            packet_number,
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // (packet number, receive time, encoded ack delay, expected smoothed rtt)
+    let expectations = [
+        // The first sample initializes the smoothed RTT
+        (0, 100, 0, Duration::from_millis(100)),
+        // 1000 * 2^3 = 8ms, which is below max_ack_delay
+        (
+            1,
+            200,
+            1000,
+            7 * Duration::from_millis(100) / 8 + Duration::from_millis(200 - 8) / 8,
+        ),
+        // 5000 * 2^3 = 40ms, which is capped at the 10ms max_ack_delay
+        (
+            2,
+            300,
+            5000,
+            7 * (7 * Duration::from_millis(100) / 8 + Duration::from_millis(200 - 8) / 8) / 8
+                + Duration::from_millis(300 - 10) / 8,
+        ),
+    ];
+
+    for (packet_number, receive_time, ack_delay, expected_smoothed_rtt) in expectations {
+        let packet_number = space.new_packet_number(VarInt::from_u8(packet_number));
+        let mut ack_ranges = AckRanges::new(1);
+        assert!(ack_ranges.insert_packet_number(packet_number).is_ok());
+
+        let frame = frame::Ack {
+            ack_delay: VarInt::from_u16(ack_delay),
+            ack_ranges: (&ack_ranges),
+            ecn_counts: None,
+        };
+
+        manager
+            .on_ack_frame(
+                time_sent + Duration::from_millis(receive_time),
+                frame,
+                packet_number,
+                random,
+                &mut context,
+                &mut publisher,
+            )
+            .unwrap();
+
+        assert_eq!(
+            context.path().rtt_estimator.smoothed_rtt(),
+            expected_smoothed_rtt
+        );
+    }
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-5.1
+//= type=test
+//# To avoid generating multiple RTT samples for a single packet, an ACK
+//# frame SHOULD NOT be used to update RTT estimates if it does not newly
+//# acknowledge the largest acknowledged packet.
+#[test]
+fn no_rtt_update_when_not_acknowledging_the_largest_acknowledged_packet() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let packet_bytes = 128;
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::snapshot();
+    let mut packet_numbers = packet_numbers(space);
+
+    let time_sent = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    // Send 2 packets
+    let packet_0 = packet_numbers.next().unwrap();
+    manager.on_packet_sent(
+        packet_0,
+        transmission::Outcome {
             ack_elicitation: AckElicitation::Eliciting,
             is_congestion_controlled: true,
             bytes_sent: packet_bytes,
@@ -1096,8 +1551,9 @@ fn no_rtt_update_when_not_acknowledging_the_largest_acknowledged_packet() {
         &mut context,
         &mut publisher,
     );
+    let packet_1 = packet_numbers.next().unwrap();
     manager.on_packet_sent(
-        space.new_packet_number(VarInt::from_u8(1)),
+        packet_1,
         transmission::Outcome {
             ack_elicitation: AckElicitation::Eliciting,
             is_congestion_controlled: true,
@@ -1127,10 +1583,7 @@ fn no_rtt_update_when_not_acknowledging_the_largest_acknowledged_packet() {
 
     // New rtt estimate because the largest packet was newly acked
     assert_eq!(context.path().congestion_controller.on_rtt_update, 1);
-    assert_eq!(
-        manager.largest_acked_packet,
-        Some(space.new_packet_number(VarInt::from_u8(1)))
-    );
+    assert_eq!(manager.largest_acked_packet, Some(packet_1));
     assert_eq!(
         context.path().rtt_estimator.latest_rtt(),
         Duration::from_millis(500)
@@ -1413,7 +1866,7 @@ fn detect_and_remove_lost_packets() {
     let expected_time_threshold = Duration::from_secs(9);
     assert_eq!(
         expected_time_threshold,
-        Manager::calculate_loss_time_threshold(&context.path().rtt_estimator)
+        manager.calculate_loss_time_threshold(&context.path().rtt_estimator)
     );
 
     time_sent += Duration::from_secs(10);
@@ -1487,16 +1940,16 @@ fn detect_and_remove_lost_packets() {
     assert_eq!(bytes_in_flight, 2);
 
     let sent_packets = &manager.sent_packets;
-    assert!(context.lost_packets.contains(&old_packet_time_sent));
+    assert!(context.lost_packets.contains_key(&old_packet_time_sent));
     assert!(sent_packets.get(old_packet_time_sent).is_none());
 
-    assert!(context.lost_packets.contains(&old_packet_packet_number));
+    assert!(context.lost_packets.contains_key(&old_packet_packet_number));
     assert!(sent_packets.get(old_packet_packet_number).is_none());
 
-    assert!(!context.lost_packets.contains(&larger_than_largest));
+    assert!(!context.lost_packets.contains_key(&larger_than_largest));
     assert!(sent_packets.get(larger_than_largest).is_some());
 
-    assert!(!context.lost_packets.contains(&not_lost));
+    assert!(!context.lost_packets.contains_key(&not_lost));
     assert!(sent_packets.get(not_lost).is_some());
 
     let expected_loss_time =
@@ -1512,6 +1965,136 @@ fn detect_and_remove_lost_packets() {
     );
 }
 
+#[test]
+fn loss_reason() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
+    let outcome = transmission::Outcome {
+        ack_elicitation: AckElicitation::Eliciting,
+        is_congestion_controlled: true,
+        bytes_sent: 1,
+        bytes_progressed: 0,
+    };
+
+    manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(10)));
+
+    // time threshold = 9/8 * 8 = 9
+    let time_sent = s2n_quic_platform::time::now();
+    context.path_mut().rtt_estimator.update_rtt(
+        Duration::from_secs(0),
+        Duration::from_secs(8),
+        time_sent,
+        true,
+        space,
+    );
+
+    // Packet 7 is K_PACKET_THRESHOLD away from the largest acked packet
+    let packet_threshold_packet = space.new_packet_number(VarInt::from_u8(7));
+    // Packet 8 is within the packet threshold
+    let time_threshold_packet = space.new_packet_number(VarInt::from_u8(8));
+
+    for packet_number in [packet_threshold_packet, time_threshold_packet] {
+        manager.on_packet_sent(
+            packet_number,
+            outcome,
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Only the packet threshold has been exceeded
+    manager.detect_and_remove_lost_packets(time_sent, random, &mut context, &mut publisher);
+    assert_eq!(context.lost_packets.len(), 1);
+    assert_eq!(
+        context.lost_packets.get(&packet_threshold_packet),
+        Some(&LossReason::PacketThreshold)
+    );
+
+    // Packet 8 was sent more than 9 seconds ago
+    let now = time_sent + Duration::from_secs(10);
+    manager.detect_and_remove_lost_packets(now, random, &mut context, &mut publisher);
+    assert_eq!(context.lost_packets.len(), 2);
+    assert_eq!(
+        context.lost_packets.get(&time_threshold_packet),
+        Some(&LossReason::TimeThreshold)
+    );
+}
+
+// Frames are not stored in the sent packet info. Each component records the packet number
+// its frames were written in and looks them up when that packet number is reported lost.
+#[test]
+fn lost_packet_frames_are_retransmitted() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
+    let now = s2n_quic_platform::time::now();
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        now,
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    let mut ping = sync::flag::Ping::default();
+    ping.send();
+    ping.on_transmit(&mut write_context).unwrap();
+    let packet_number = write_context
+        .frame_buffer
+        .pop_front()
+        .expect("a PING frame should be written")
+        .packet_nr;
+    assert_eq!(
+        ping.get_transmission_interest(),
+        transmission::Interest::None
+    );
+
+    manager.on_packet_sent(
+        packet_number,
+        transmission::Outcome {
+            ack_elicitation: AckElicitation::Eliciting,
+            is_congestion_controlled: true,
+            bytes_sent: 1,
+            bytes_progressed: 0,
+        },
+        now,
+        ExplicitCongestionNotification::default(),
+        transmission::Mode::Normal,
+        None,
+        &mut context,
+        &mut publisher,
+    );
+
+    // The packet is K_PACKET_THRESHOLD away from the largest acked packet
+    manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(3)));
+    manager.detect_and_remove_lost_packets(now, random, &mut context, &mut publisher);
+    assert!(manager.sent_packets.get(packet_number).is_none());
+    assert!(context.lost_packets.contains_key(&packet_number));
+
+    for lost_packet_number in context.lost_packets.keys() {
+        ping.on_packet_loss(lost_packet_number);
+    }
+
+    // The PING frame carried in the lost packet is queued for retransmission
+    assert_eq!(
+        ping.get_transmission_interest(),
+        transmission::Interest::LostData
+    );
+}
+
 #[test]
 // persistent_congestion should only be calculated for the specified path
 //
@@ -1621,7 +2204,7 @@ fn detect_lost_packets_persistent_congestion_path_aware() {
     let expected_time_threshold = Duration::from_secs(9);
     assert_eq!(
         expected_time_threshold,
-        Manager::calculate_loss_time_threshold(&context.path_by_id(first_path_id).rtt_estimator)
+        manager.calculate_loss_time_threshold(&context.path_by_id(first_path_id).rtt_estimator)
     );
 
     // 1-9 packets packets sent, each size 1 byte
@@ -1728,6 +2311,7 @@ fn remove_lost_packets_persistent_congestion_path_aware() {
                 transmission::Mode::Normal,
                 Default::default(),
             ),
+            LossReason::TimeThreshold,
         ),
         (
             space.new_packet_number(VarInt::from_u8(9)),
@@ -1741,6 +2325,7 @@ fn remove_lost_packets_persistent_congestion_path_aware() {
                 transmission::Mode::Normal,
                 Default::default(),
             ),
+            LossReason::TimeThreshold,
         ),
     ];
 
@@ -1870,6 +2455,7 @@ fn detect_and_remove_lost_packets_mtu_probe() {
         context.path().congestion_controller.bytes_in_flight,
         MINIMUM_MTU as u32 + 1
     );
+    let congestion_window = context.path().congestion_controller.congestion_window;
 
     manager.detect_and_remove_lost_packets(time_sent, random, &mut context, &mut publisher);
 
@@ -1879,6 +2465,12 @@ fn detect_and_remove_lost_packets_mtu_probe() {
     assert_eq!(context.path().congestion_controller.lost_bytes, 0);
     assert_eq!(context.path().congestion_controller.on_packets_lost, 0);
     assert_eq!(context.path().congestion_controller.bytes_in_flight, 0);
+    // The congestion window is not reduced
+    assert_eq!(context.path().congestion_controller.congestion_events, 0);
+    assert_eq!(
+        context.path().congestion_controller.congestion_window,
+        congestion_window
+    );
 }
 
 #[test]
@@ -2759,82 +3351,357 @@ fn on_timeout() {
 }
 
 #[test]
-fn timers() {
+fn pto_events() {
     let space = PacketNumberSpace::ApplicationData;
     let mut manager = Manager::new(space);
-    let loss_time = s2n_quic_platform::time::now() + Duration::from_secs(5);
-    let pto_time = s2n_quic_platform::time::now() + Duration::from_secs(10);
-
-    // No timer is set
-    assert_eq!(manager.armed_timer_count(), 0);
+    let now = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
 
-    // Loss timer is armed
-    manager.loss_timer.set(loss_time);
-    assert_eq!(manager.armed_timer_count(), 1);
-    assert_eq!(manager.next_expiration(), Some(loss_time));
+    // Validate the path so it is not amplification limited and the PTO can be armed
+    //
+    // simulate receiving a handshake packet to force path validation
+    context.path_mut().on_handshake_packet();
 
-    // PTO timer is armed
-    manager.loss_timer.cancel();
-    manager.pto.timer.set(pto_time);
-    assert_eq!(manager.armed_timer_count(), 1);
-    assert_eq!(manager.next_expiration(), Some(pto_time));
+    let outcome = transmission::Outcome {
+        ack_elicitation: AckElicitation::Eliciting,
+        is_congestion_controlled: true,
+        bytes_sent: 1,
+        bytes_progressed: 0,
+    };
+    let mut packet_numbers = packet_numbers(space);
 
-    // Both timers are armed, only loss time is returned
-    manager.loss_timer.set(loss_time);
-    manager.pto.timer.set(pto_time);
-    assert_eq!(manager.armed_timer_count(), 1);
-    assert_eq!(manager.next_expiration(), Some(loss_time));
-}
+    manager.on_packet_sent(
+        packet_numbers.next().unwrap(),
+        outcome,
+        now,
+        ecn,
+        transmission::Mode::Normal,
+        None,
+        &mut context,
+        &mut publisher,
+    );
+    assert!(manager.pto.timer.is_armed());
+    assert_eq!(publisher.pto_armed, 1);
 
-#[test]
-fn on_transmit() {
-    let space = PacketNumberSpace::ApplicationData;
-    let mut manager = Manager::new(space);
-    let mut frame_buffer = OutgoingFrameBuffer::new();
-    let mut context = MockWriteContext::new(
-        s2n_quic_platform::time::now(),
-        &mut frame_buffer,
-        transmission::Constraint::CongestionLimited, // Recovery manager ignores constraints
-        transmission::Mode::LossRecoveryProbing,
-        endpoint::Type::Client,
+    // Sending another packet at the same time does not move the PTO expiration
+    manager.on_packet_sent(
+        packet_numbers.next().unwrap(),
+        outcome,
+        now,
+        ecn,
+        transmission::Mode::Normal,
+        None,
+        &mut context,
+        &mut publisher,
     );
+    assert_eq!(publisher.pto_armed, 1);
 
-    // Already idle
-    manager.pto.state = PtoState::Idle;
-    manager.on_transmit(&mut context);
-    assert_eq!(manager.pto.state, PtoState::Idle);
+    // A later packet re-arms the PTO with a new expiration
+    let time_sent = now + Duration::from_millis(10);
+    manager.on_packet_sent(
+        packet_numbers.next().unwrap(),
+        outcome,
+        time_sent,
+        ecn,
+        transmission::Mode::Normal,
+        None,
+        &mut context,
+        &mut publisher,
+    );
+    assert_eq!(publisher.pto_armed, 2);
+    assert_eq!(publisher.pto_expired, 0);
 
-    // No transmissions required
-    manager.pto.state = RequiresTransmission(0);
-    manager.on_transmit(&mut context);
-    assert_eq!(manager.pto.state, PtoState::Idle);
+    // The PTO has not expired yet
+    manager.on_timeout(time_sent, random, &mut context, &mut publisher);
+    assert_eq!(publisher.pto_expired, 0);
 
-    // One transmission required, not ack eliciting
-    manager.pto.state = RequiresTransmission(1);
-    context.write_frame_forced(&frame::Padding { length: 1 });
-    assert!(!context.ack_elicitation().is_ack_eliciting());
-    manager.on_transmit(&mut context);
-    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-    //= type=test
-    //# All probe packets sent on a PTO MUST be ack-eliciting.
+    // Expire the PTO with packets in flight, so two probes are requested
+    let expiration = manager.pto.timer.next_expiration().unwrap();
+    manager.on_timeout(expiration, random, &mut context, &mut publisher);
+    assert_eq!(manager.pto.state, RequiresTransmission(2));
+    assert_eq!(publisher.pto_expired, 1);
+    assert_eq!(publisher.pto_armed, 2);
+}
 
-    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-    //= type=test
-    //# When a PTO timer expires, a sender MUST send at least one ack-
-    //# eliciting packet in the packet number space as a probe.
+#[test]
+fn probe_request() {
+    let now = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let ecn = ExplicitCongestionNotification::default();
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let random = &mut random::testing::Generator::default();
 
-    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
-    //= type=test
-    //# When there is no data to send, the sender SHOULD send
-    //# a PING or other ack-eliciting frame in a single packet, re-arming the
-    //# PTO timer.
-    assert!(context.ack_elicitation().is_ack_eliciting());
-    assert_eq!(manager.pto.state, PtoState::Idle);
+    let mut initial = Manager::new(PacketNumberSpace::Initial);
+    let mut handshake = Manager::new(PacketNumberSpace::Handshake);
+    assert_eq!(initial.probe_request(), None);
+    assert_eq!(handshake.probe_request(), None);
 
-    // One transmission required, ack eliciting
-    manager.pto.state = RequiresTransmission(1);
-    context.write_frame_forced(&frame::Ping);
-    manager.on_transmit(&mut context);
+    // No ack-eliciting packets are outstanding, but a single probe is still sent
+    // to elicit an acknowledgement
+    initial.pto.timer.set(now - Duration::from_secs(1));
+    initial.on_timeout(now, random, &mut context, &mut publisher);
+    assert_eq!(
+        initial.probe_request(),
+        Some(ProbeRequest {
+            space: PacketNumberSpace::Initial,
+            count: 1
+        })
+    );
+
+    // Packets are outstanding in both spaces, but only the Handshake PTO has expired
+    for manager in [&mut initial, &mut handshake] {
+        manager.pto.state = PtoState::Idle;
+        manager.sent_packets.insert(
+            manager.space.new_packet_number(VarInt::from_u8(1)),
+            SentPacketInfo::new(
+                true,
+                1,
+                now,
+                AckElicitation::Eliciting,
+                unsafe { path::Id::new(0) },
+                ecn,
+                transmission::Mode::Normal,
+                Default::default(),
+            ),
+        );
+    }
+    let pto_backoff = context.path().pto_backoff;
+    initial.pto.timer.set(now + Duration::from_secs(1));
+    handshake.pto.timer.set(now - Duration::from_secs(1));
+    initial.on_timeout(now, random, &mut context, &mut publisher);
+    handshake.on_timeout(now, random, &mut context, &mut publisher);
+
+    assert_eq!(initial.probe_request(), None);
+    assert_eq!(
+        handshake.probe_request(),
+        Some(ProbeRequest {
+            space: PacketNumberSpace::Handshake,
+            count: 2
+        })
+    );
+    assert_eq!(context.path().pto_backoff, pto_backoff * 2);
+}
+
+#[test]
+fn on_pto_timeout_single_space() {
+    let space = PacketNumberSpace::ApplicationData;
+    let now = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let path_id = context.path_id();
+    let pto_backoff = context.path().pto_backoff;
+
+    let mut manager = Manager::new(space);
+    manager.sent_packets.insert(
+        space.new_packet_number(VarInt::from_u8(1)),
+        SentPacketInfo::new(
+            true,
+            1,
+            now,
+            AckElicitation::Eliciting,
+            path_id,
+            ExplicitCongestionNotification::default(),
+            transmission::Mode::Normal,
+            Default::default(),
+        ),
+    );
+
+    // The PTO timer has not expired yet
+    manager.pto.timer.set(now + Duration::from_secs(1));
+    assert_eq!(
+        on_pto_timeout(
+            &mut [&mut manager],
+            now,
+            context.path_mut(),
+            path_id,
+            &mut publisher
+        ),
+        None
+    );
+    assert_eq!(context.path().pto_backoff, pto_backoff);
+
+    // Two probes are requested since packets are outstanding
+    assert_eq!(
+        on_pto_timeout(
+            &mut [&mut manager],
+            now + Duration::from_secs(1),
+            context.path_mut(),
+            path_id,
+            &mut publisher
+        ),
+        Some(ProbeRequest { space, count: 2 })
+    );
+    assert_eq!(context.path().pto_backoff, pto_backoff * 2);
+    assert!(!manager.pto.timer.is_armed());
+    assert_eq!(publisher.pto_expired, 1);
+}
+
+#[test]
+fn on_pto_timeout_multi_space() {
+    let now = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let path_id = context.path_id();
+    let pto_backoff = context.path().pto_backoff;
+
+    let mut initial = Manager::new(PacketNumberSpace::Initial);
+    let mut handshake = Manager::new(PacketNumberSpace::Handshake);
+    let mut application = Manager::new(PacketNumberSpace::ApplicationData);
+
+    // Both the Initial and Handshake PTO timers have expired, but the Handshake timer
+    // expired first
+    initial.pto.timer.set(now - Duration::from_millis(10));
+    handshake.pto.timer.set(now - Duration::from_millis(20));
+    // The Application space has an earlier PTO timer, but a loss timer is armed
+    application.pto.timer.set(now - Duration::from_millis(30));
+    application.loss_timer.set(now + Duration::from_millis(30));
+
+    assert_eq!(
+        on_pto_timeout(
+            &mut [&mut initial, &mut handshake, &mut application],
+            now,
+            context.path_mut(),
+            path_id,
+            &mut publisher
+        ),
+        Some(ProbeRequest {
+            space: PacketNumberSpace::Handshake,
+            count: 1
+        })
+    );
+
+    // Only the earliest PTO timer is expired
+    assert_eq!(handshake.probe_request().map(|probe| probe.count), Some(1));
+    assert_eq!(initial.probe_request(), None);
+    assert!(initial.pto.timer.is_armed());
+    assert_eq!(application.probe_request(), None);
+    assert_eq!(context.path().pto_backoff, pto_backoff * 2);
+    assert_eq!(publisher.pto_expired, 1);
+}
+
+#[test]
+fn on_pto_timeout_no_outstanding_packets() {
+    let space = PacketNumberSpace::Initial;
+    let now = s2n_quic_platform::time::now() + Duration::from_secs(10);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let path_id = context.path_id();
+
+    // No PTO timers are armed, so no probes are requested
+    let mut manager = Manager::new(space);
+    assert_eq!(
+        on_pto_timeout(
+            &mut [&mut manager],
+            now,
+            context.path_mut(),
+            path_id,
+            &mut publisher
+        ),
+        None
+    );
+
+    // A client arms the PTO timer without any packets outstanding to unblock a server
+    // at its anti-amplification limit, so a single probe is requested
+    manager.pto.timer.set(now);
+    assert!(manager.sent_packets.is_empty());
+    assert_eq!(
+        on_pto_timeout(
+            &mut [&mut manager],
+            now,
+            context.path_mut(),
+            path_id,
+            &mut publisher
+        ),
+        Some(ProbeRequest { space, count: 1 })
+    );
+    assert!(manager.requires_probe());
+}
+
+#[test]
+fn timers() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let loss_time = s2n_quic_platform::time::now() + Duration::from_secs(5);
+    let pto_time = s2n_quic_platform::time::now() + Duration::from_secs(10);
+
+    // No timer is set
+    assert_eq!(manager.armed_timer_count(), 0);
+
+    // Loss timer is armed
+    manager.loss_timer.set(loss_time);
+    assert_eq!(manager.armed_timer_count(), 1);
+    assert_eq!(manager.next_expiration(), Some(loss_time));
+
+    // PTO timer is armed
+    manager.loss_timer.cancel();
+    manager.pto.timer.set(pto_time);
+    assert_eq!(manager.armed_timer_count(), 1);
+    assert_eq!(manager.next_expiration(), Some(pto_time));
+
+    // Both timers are armed, only loss time is returned
+    manager.loss_timer.set(loss_time);
+    manager.pto.timer.set(pto_time);
+    assert_eq!(manager.armed_timer_count(), 1);
+    assert_eq!(manager.next_expiration(), Some(loss_time));
+}
+
+#[test]
+fn on_transmit() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        s2n_quic_platform::time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::CongestionLimited, // Recovery manager ignores constraints
+        transmission::Mode::LossRecoveryProbing,
+        endpoint::Type::Client,
+    );
+
+    // Already idle
+    manager.pto.state = PtoState::Idle;
+    manager.on_transmit(&mut context);
+    assert_eq!(manager.pto.state, PtoState::Idle);
+
+    // No transmissions required
+    manager.pto.state = RequiresTransmission(0);
+    manager.on_transmit(&mut context);
+    assert_eq!(manager.pto.state, PtoState::Idle);
+
+    // One transmission required, not ack eliciting
+    manager.pto.state = RequiresTransmission(1);
+    context.write_frame_forced(&frame::Padding { length: 1 });
+    assert!(!context.ack_elicitation().is_ack_eliciting());
+    manager.on_transmit(&mut context);
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+    //= type=test
+    //# All probe packets sent on a PTO MUST be ack-eliciting.
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+    //= type=test
+    //# When a PTO timer expires, a sender MUST send at least one ack-
+    //# eliciting packet in the packet number space as a probe.
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
+    //= type=test
+    //# When there is no data to send, the sender SHOULD send
+    //# a PING or other ack-eliciting frame in a single packet, re-arming the
+    //# PTO timer.
+    assert!(context.ack_elicitation().is_ack_eliciting());
+    assert_eq!(manager.pto.state, PtoState::Idle);
+
+    // One transmission required, ack eliciting
+    manager.pto.state = RequiresTransmission(1);
+    context.write_frame_forced(&frame::Ping);
+    manager.on_transmit(&mut context);
     //= https://www.rfc-editor.org/rfc/rfc9002#section-6.2.4
     //= type=test
     //# All probe packets sent on a PTO MUST be ack-eliciting.
@@ -2953,15 +3820,629 @@ fn ack_packets(
 }
 
 #[test]
-fn requires_probe() {
+fn requires_probe() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+
+    manager.pto.state = PtoState::RequiresTransmission(2);
+    assert!(manager.requires_probe());
+
+    manager.pto.state = PtoState::Idle;
+    assert!(!manager.requires_probe());
+}
+
+#[test]
+fn time_of_last_ack_eliciting() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+
+    assert_eq!(manager.time_of_last_ack_eliciting(), None);
+
+    let mut send = |packet_number: u8, ack_elicitation: AckElicitation, time_sent: Timestamp| {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    };
+
+    send(1, AckElicitation::NonEliciting, now);
+    send(
+        2,
+        AckElicitation::Eliciting,
+        now + Duration::from_millis(10),
+    );
+    send(
+        3,
+        AckElicitation::NonEliciting,
+        now + Duration::from_millis(20),
+    );
+    send(
+        4,
+        AckElicitation::Eliciting,
+        now + Duration::from_millis(30),
+    );
+    send(
+        5,
+        AckElicitation::NonEliciting,
+        now + Duration::from_millis(40),
+    );
+
+    assert_eq!(
+        manager.time_of_last_ack_eliciting(),
+        Some(now + Duration::from_millis(30))
+    );
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn loss_rate() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+
+    assert_eq!(manager.loss_rate(), 0.0);
+
+    for packet_number in 1..=10 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Acknowledging packets 5-10 declares packets 1-4 lost due to the packet threshold
+    ack_packets(
+        5..=10,
+        now + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+
+    assert!((manager.loss_rate() - 0.4).abs() < 0.001);
+}
+
+#[test]
+fn on_round_start() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let mut packet_numbers = packet_numbers(space);
+    let now = s2n_quic_platform::time::now();
+
+    let outcome = transmission::Outcome {
+        ack_elicitation: AckElicitation::Eliciting,
+        is_congestion_controlled: true,
+        bytes_sent: 128,
+        bytes_progressed: 0,
+    };
+
+    // Send packets 0-3
+    for packet_number in packet_numbers.by_ref().take(4) {
+        manager.on_packet_sent(
+            packet_number,
+            outcome,
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // The first acknowledgement starts the first round, which ends once a packet sent
+    // after packet 3 is acknowledged
+    ack_packets(0..=0, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(context.path().congestion_controller.on_round_start, 1);
+
+    // Send packets 4-5
+    for packet_number in packet_numbers.by_ref().take(2) {
+        manager.on_packet_sent(
+            packet_number,
+            outcome,
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Packets sent before the round started don't end the round
+    ack_packets(1..=3, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(context.path().congestion_controller.on_round_start, 1);
+
+    // Acknowledging the first packet sent after the round started ends the round,
+    // and the next round ends after packet 5
+    ack_packets(4..=4, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(context.path().congestion_controller.on_round_start, 2);
+
+    ack_packets(5..=5, now, &mut context, &mut manager, None, &mut publisher);
+    assert_eq!(context.path().congestion_controller.on_round_start, 2);
+}
+
+#[test]
+fn total_delivered() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+
+    assert_eq!(manager.total_delivered(), 0);
+
+    for packet_number in 1..=20 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Acknowledging packets 5-10 declares packets 1-4 lost, starting a recovery period
+    ack_packets(
+        5..=10,
+        now + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(manager.total_delivered(), 6 * 128);
+
+    // Acknowledging packets 15-20 declares packets 11-14 lost, starting another recovery period
+    ack_packets(
+        15..=20,
+        now + Duration::from_millis(200),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(context.path().congestion_controller.loss_bursts, 2);
+
+    // Lost packets are not counted as delivered, but packets from both periods are
+    assert_eq!(manager.total_delivered(), 12 * 128);
+}
+
+#[test]
+fn congestion_window_increased() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+
+    for packet_number in 1..=3 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    context
+        .path_mut()
+        .congestion_controller
+        .congestion_window_increase_on_ack = 1000;
+
+    // The window grows during slow start
+    assert!(context.path().congestion_controller.is_slow_start());
+    ack_packets(
+        1..=1,
+        now + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(publisher.congestion_window_increased, 1);
+    assert!(matches!(
+        congestion_window_increase_cause(true),
+        CongestionWindowIncreaseCause::SlowStart
+    ));
+
+    // The window grows during congestion avoidance
+    context.path_mut().congestion_controller.slow_start = false;
+    ack_packets(
+        2..=2,
+        now + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(publisher.congestion_window_increased, 2);
+    assert!(matches!(
+        congestion_window_increase_cause(false),
+        CongestionWindowIncreaseCause::CongestionAvoidance
+    ));
+
+    // No event is published when the window doesn't grow
+    context
+        .path_mut()
+        .congestion_controller
+        .congestion_window_increase_on_ack = 0;
+    ack_packets(
+        3..=3,
+        now + Duration::from_millis(100),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert_eq!(publisher.congestion_window_increased, 2);
+}
+
+//= https://www.rfc-editor.org/rfc/rfc9002#section-6.3
+//= type=test
+//# Clients that receive a Retry packet reset congestion control and loss
+//# recovery state, including resetting any pending timers.
+#[test]
+fn on_retry_packet() {
+    let space = PacketNumberSpace::Initial;
+    let mut manager = super::Manager::<endpoint::testing::Client>::new(space)
+        .with_max_tracked_packets(10)
+        .with_time_threshold(5, 4)
+        .with_min_rtt_loss_floor(2);
+    let now = s2n_quic_platform::time::now();
+    let ecn = ExplicitCongestionNotification::default();
+    let max_ack_delay = Duration::from_millis(25);
+    let initial_rtt = Duration::from_millis(50);
+    let rtt_estimator = RttEstimator::new_with_initial_rtt(max_ack_delay, initial_rtt)
+        .with_timer_granularity(Duration::from_millis(5));
+
+    let mut path = super::Path::<endpoint::testing::Client>::new(
+        Default::default(),
+        connection::PeerId::TEST_ID,
+        connection::LocalId::TEST_ID,
+        rtt_estimator,
+        Default::default(),
+        true,
+        DEFAULT_MAX_MTU,
+    );
+
+    for packet_number in 1..=2 {
+        manager.sent_packets.insert(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            SentPacketInfo::new(
+                true,
+                1000,
+                now,
+                AckElicitation::Eliciting,
+                unsafe { path::Id::new(0) },
+                ecn,
+                transmission::Mode::Normal,
+                Default::default(),
+            ),
+        );
+    }
+    path.congestion_controller.bytes_in_flight = 2000;
+    path.rtt_estimator.update_rtt(
+        Duration::from_millis(0),
+        Duration::from_millis(100),
+        now,
+        false,
+        space,
+    );
+    assert!(path.rtt_estimator.first_rtt_sample().is_some());
+
+    manager.on_retry_packet(&mut path);
+
+    assert!(manager.sent_packets.is_empty());
+    assert_eq!(path.congestion_controller.bytes_in_flight, 0);
+
+    // The RTT samples are discarded, but the configured initial RTT is retained
+    assert_eq!(path.rtt_estimator, rtt_estimator);
+    assert_eq!(path.rtt_estimator.smoothed_rtt(), initial_rtt);
+
+    // The loss detection configuration is retained
+    assert_eq!(manager.max_tracked_packets, Some(10));
+    assert_eq!(manager.time_threshold, (5, 4));
+    assert_eq!(manager.min_rtt_loss_floor, Some(2));
+}
+
+#[test]
+fn bdp_estimate() {
+    let space = PacketNumberSpace::ApplicationData;
+    let manager = Manager::new(space);
+    let now = s2n_quic_platform::time::now();
+
+    let mut path = Path::new(
+        Default::default(),
+        connection::PeerId::TEST_ID,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(10)),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+    );
+
+    // No samples have been taken yet
+    assert_eq!(manager.bdp_estimate(&path), None);
+
+    // 1,000,000 bytes per second
+    path.congestion_controller.delivery_rate =
+        Some(Bandwidth::new(1_000_000, Duration::from_secs(1)));
+    assert_eq!(manager.bdp_estimate(&path), None);
+
+    path.rtt_estimator
+        .update_rtt(Duration::ZERO, Duration::from_millis(50), now, true, space);
+    // 1,000,000 bytes/s * 50ms = 50,000 bytes
+    assert_eq!(manager.bdp_estimate(&path), Some(50_000));
+
+    // The minimum RTT is used rather than the latest sample
+    path.rtt_estimator
+        .update_rtt(Duration::ZERO, Duration::from_millis(200), now, true, space);
+    assert_eq!(manager.bdp_estimate(&path), Some(50_000));
+
+    path.congestion_controller.delivery_rate = None;
+    assert_eq!(manager.bdp_estimate(&path), None);
+}
+
+#[test]
+fn receive_buffer_advice() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+    let path_id = unsafe { path::Id::new(0) };
+
+    let mut path = Path::new(
+        Default::default(),
+        connection::PeerId::TEST_ID,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(10)),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+    );
+    path.congestion_controller.congestion_window = 100_000;
+
+    // No advice is published without a bandwidth-delay product estimate
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 0);
+
+    // 1,000,000 bytes/s * 50ms = 50,000 bytes
+    path.congestion_controller.delivery_rate =
+        Some(Bandwidth::new(1_000_000, Duration::from_secs(1)));
+    path.rtt_estimator
+        .update_rtt(Duration::ZERO, Duration::from_millis(50), now, true, space);
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 1);
+
+    // Small changes to the congestion window don't publish new advice
+    path.congestion_controller.congestion_window = 110_000;
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    path.congestion_controller.congestion_window = 80_000;
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 1);
+
+    // A large change publishes advice proportional to the new delivery rate
+    path.congestion_controller.congestion_window = 200_000;
+    path.congestion_controller.delivery_rate =
+        Some(Bandwidth::new(2_000_000, Duration::from_secs(1)));
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 2);
+    assert_eq!(manager.bdp_estimate(&path), Some(100_000));
+    assert_eq!(manager.advised_congestion_window, 200_000);
+
+    // Further changes are measured against the most recent advice
+    path.congestion_controller.congestion_window = 160_000;
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 2);
+    path.congestion_controller.congestion_window = 150_000;
+    manager.publish_receive_buffer_advice(&path, path_id, &mut publisher);
+    assert_eq!(publisher.receive_buffer_advice, 3);
+}
+
+#[test]
+fn on_connection_close() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(100));
+    let now = s2n_quic_platform::time::now();
+    let ecn = ExplicitCongestionNotification::default();
+
+    for packet_number in 1..=3 {
+        manager.sent_packets.insert(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            SentPacketInfo::new(
+                true,
+                1000,
+                now,
+                AckElicitation::Eliciting,
+                unsafe { path::Id::new(0) },
+                ecn,
+                transmission::Mode::Normal,
+                Default::default(),
+            ),
+        );
+    }
+    path_manager[unsafe { path::Id::new(0) }]
+        .congestion_controller
+        .bytes_in_flight = 3000;
+    manager.time_of_last_ack_eliciting_packet = Some(now);
+    manager.loss_timer.set(now + Duration::from_millis(10));
+    manager.pto.timer.set(now + Duration::from_millis(200));
+    assert!(manager.is_armed());
+
+    manager.on_connection_close(&mut path_manager);
+
+    let congestion_controller = &path_manager[unsafe { path::Id::new(0) }].congestion_controller;
+    assert!(manager.sent_packets.is_empty());
+    assert_eq!(congestion_controller.bytes_in_flight, 0);
+    assert_eq!(congestion_controller.on_connection_close, 1);
+    assert_eq!(manager.time_of_last_ack_eliciting(), None);
+    assert!(!manager.loss_timer.is_armed());
+    assert!(!manager.pto.timer.is_armed());
+    assert!(!manager.is_armed());
+}
+
+#[test]
+fn send_budget() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let now = s2n_quic_platform::time::now();
+
+    let mut path = Path::new(
+        Default::default(),
+        connection::PeerId::TEST_ID,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(10)),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+    );
+    path.congestion_controller.congestion_window = 5000;
+    path.congestion_controller.bytes_in_flight = 2000;
+
+    assert_eq!(manager.send_budget(&path, now), 3000);
+
+    // The budget is limited by the congestion controller's pacing
+    path.congestion_controller.earliest_departure_time = Some(now + Duration::from_millis(5));
+    assert_eq!(manager.send_budget(&path, now), 0);
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.5
+    //= type=test
+    //# Probe packets MUST NOT be blocked by the congestion controller.
+    manager.pto.state = PtoState::RequiresTransmission(1);
+    assert_eq!(manager.send_budget(&path, now), MINIMUM_MTU as usize);
+}
+
+#[test]
+fn ack_eliciting_threshold() {
     let space = PacketNumberSpace::ApplicationData;
     let mut manager = Manager::new(space);
+    let now = s2n_quic_platform::time::now();
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let mtu = context.path().mtu_controller.mtu() as u32;
 
-    manager.pto.state = PtoState::RequiresTransmission(2);
-    assert!(manager.requires_probe());
+    // Small congestion windows use the default threshold
+    context.path_mut().congestion_controller.congestion_window = 2 * mtu;
+    assert_eq!(manager.ack_eliciting_threshold(context.path()), 1);
 
-    manager.pto.state = PtoState::Idle;
-    assert!(!manager.requires_probe());
+    // Larger congestion windows allow the peer to acknowledge less frequently
+    context.path_mut().congestion_controller.congestion_window = 40 * mtu;
+    assert_eq!(manager.ack_eliciting_threshold(context.path()), 10);
+
+    for packet_number in 1..=11 {
+        assert!(!manager.is_ack_eliciting_threshold_exceeded(context.path()));
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: mtu as usize,
+                bytes_progressed: 0,
+            },
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Packets that are not ack-eliciting are not counted
+    manager.on_packet_sent(
+        space.new_packet_number(VarInt::from_u8(12)),
+        transmission::Outcome {
+            ack_elicitation: AckElicitation::NonEliciting,
+            is_congestion_controlled: false,
+            bytes_sent: 0,
+            bytes_progressed: 0,
+        },
+        now,
+        ecn,
+        transmission::Mode::Normal,
+        None,
+        &mut context,
+        &mut publisher,
+    );
+
+    // 11 outstanding ack-eliciting packets crosses the threshold of 10
+    assert!(manager.is_ack_eliciting_threshold_exceeded(context.path()));
+
+    // A larger congestion window raises the threshold again
+    context.path_mut().congestion_controller.congestion_window = 80 * mtu;
+    assert!(!manager.is_ack_eliciting_threshold_exceeded(context.path()));
+    context.path_mut().congestion_controller.congestion_window = 40 * mtu;
+
+    // Acknowledging packets brings the count back below the threshold
+    ack_packets(
+        1..=1,
+        now + Duration::from_millis(10),
+        &mut context,
+        &mut manager,
+        None,
+        &mut publisher,
+    );
+    assert!(!manager.is_ack_eliciting_threshold_exceeded(context.path()));
 }
 
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.5
@@ -3000,6 +4481,54 @@ fn probe_packets_count_towards_bytes_in_flight() {
     assert_eq!(context.path().congestion_controller.bytes_in_flight, 100);
 }
 
+#[test]
+fn space_discarded_does_not_refund_amplification_allowance() {
+    let space = PacketNumberSpace::Handshake;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let path_id = context.path_id;
+
+    // Allow the server to transmit 3 * 1200 bytes
+    context.path_mut().on_bytes_received(1200);
+    assert!(!context.path().at_amplification_limit());
+
+    for packet_number in 1..=3 {
+        let outcome = transmission::Outcome {
+            ack_elicitation: AckElicitation::Eliciting,
+            is_congestion_controlled: true,
+            bytes_sent: 1200,
+            bytes_progressed: 0,
+        };
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(packet_number)),
+            outcome,
+            s2n_quic_platform::time::now(),
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+        context.path_mut().on_bytes_transmitted(outcome.bytes_sent);
+    }
+
+    assert!(context.path().at_amplification_limit());
+    assert_eq!(context.path().congestion_controller.bytes_in_flight, 3600);
+
+    manager.on_packet_number_space_discarded(context.path_mut(), path_id, &mut publisher);
+
+    // The discarded packets are removed from flight, but were still sent to the peer
+    assert_eq!(context.path().congestion_controller.bytes_in_flight, 0);
+    assert!(context.path().at_amplification_limit());
+    assert_eq!(
+        context.path().transmission_constraint(),
+        transmission::Constraint::AmplificationLimited
+    );
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.1
 //= type=test
 //# The RECOMMENDED initial value for the packet reordering threshold
@@ -3033,10 +4562,134 @@ fn time_threshold_multiplier_equals_nine_eighths() {
     );
     assert_eq!(
         Duration::from_millis(1125), // 9/8 seconds = 1.125 seconds
-        Manager::calculate_loss_time_threshold(&rtt_estimator)
+        Manager::new(PacketNumberSpace::Initial).calculate_loss_time_threshold(&rtt_estimator)
     );
 }
 
+#[test]
+fn custom_time_threshold() {
+    let space = PacketNumberSpace::ApplicationData;
+    let ecn = ExplicitCongestionNotification::default();
+    let random = &mut random::testing::Generator::default();
+
+    // time threshold = max(kTimeThreshold * max(smoothed_rtt, latest_rtt), kGranularity)
+    for (mut manager, expected_time_threshold) in [
+        // 9/8 * 8 = 9
+        (Manager::new(space), Duration::from_secs(9)),
+        // 3/2 * 8 = 12
+        (
+            Manager::new(space).with_time_threshold(3, 2),
+            Duration::from_secs(12),
+        ),
+        // The time threshold is configured through the connection limits
+        (
+            Manager::new(space).with_limits(&Limits::default().with_time_threshold(3, 2).unwrap()),
+            Duration::from_secs(12),
+        ),
+    ] {
+        let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+        let mut context = MockContext::new(&mut path_manager);
+        let mut publisher = Publisher::no_snapshot();
+        let time_sent = s2n_quic_platform::time::now();
+
+        context.path_mut().rtt_estimator.update_rtt(
+            Duration::from_secs(0),
+            Duration::from_secs(8),
+            time_sent,
+            true,
+            space,
+        );
+        assert_eq!(manager.loss_delay(context.path()), expected_time_threshold);
+
+        let outcome = transmission::Outcome {
+            ack_elicitation: AckElicitation::Eliciting,
+            is_congestion_controlled: true,
+            bytes_sent: 1,
+            bytes_progressed: 0,
+        };
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(1)),
+            outcome,
+            time_sent,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+        manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(2)));
+
+        // The packet is within the time threshold, so the loss timer is armed for the
+        // time the packet would be declared lost
+        manager.detect_and_remove_lost_packets(time_sent, random, &mut context, &mut publisher);
+        assert!(context.lost_packets.is_empty());
+        assert_eq!(
+            manager.loss_timer.next_expiration(),
+            Some(time_sent + expected_time_threshold)
+        );
+    }
+}
+
+#[test]
+fn min_rtt_loss_floor() {
+    let space = PacketNumberSpace::ApplicationData;
+    let ecn = ExplicitCongestionNotification::default();
+    let random = &mut random::testing::Generator::default();
+
+    for (mut manager, is_lost) in [
+        (Manager::new(space), true),
+        (Manager::new(space).with_min_rtt_loss_floor(2), false),
+        // The floor is configured through the connection limits
+        (
+            Manager::new(space).with_limits(&Limits::default().with_min_rtt_loss_floor(2).unwrap()),
+            false,
+        ),
+    ] {
+        let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+        let mut context = MockContext::new(&mut path_manager);
+        let mut publisher = Publisher::no_snapshot();
+        let now = s2n_quic_platform::time::now();
+
+        // min_rtt = smoothed_rtt = latest_rtt = 100ms
+        context.path_mut().rtt_estimator.update_rtt(
+            Duration::from_secs(0),
+            Duration::from_millis(100),
+            now,
+            true,
+            space,
+        );
+
+        let outcome = transmission::Outcome {
+            ack_elicitation: AckElicitation::Eliciting,
+            is_congestion_controlled: true,
+            bytes_sent: 1,
+            bytes_progressed: 0,
+        };
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(1)),
+            outcome,
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+        manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(2)));
+
+        // The packet is delayed by an RTT spike, arriving after 150ms. Without the floor the
+        // time threshold is 9/8 * 100ms = 112.5ms, while the floor raises it to 200ms.
+        manager.detect_and_remove_lost_packets(
+            now + Duration::from_millis(150),
+            random,
+            &mut context,
+            &mut publisher,
+        );
+        assert_eq!(context.lost_packets.len(), is_lost as usize);
+        assert_eq!(manager.loss_timer.is_armed(), !is_lost);
+    }
+}
+
 #[test]
 fn timer_granularity() {
     //= https://www.rfc-editor.org/rfc/rfc9002#section-6.1.2
@@ -3060,7 +4713,166 @@ fn timer_granularity() {
     //# packets as lost too early, this time threshold MUST be set to at
     //# least the local timer granularity, as indicated by the kGranularity
     //# constant.
-    assert!(Manager::calculate_loss_time_threshold(&rtt_estimator) >= K_GRANULARITY);
+    assert!(
+        Manager::new(PacketNumberSpace::Initial).calculate_loss_time_threshold(&rtt_estimator)
+            >= K_GRANULARITY
+    );
+}
+
+#[test]
+fn custom_timer_granularity() {
+    let space = PacketNumberSpace::ApplicationData;
+    let ecn = ExplicitCongestionNotification::default();
+    let now = s2n_quic_platform::time::now();
+
+    // Returns when the loss timer is armed for packet 1, after packet 2 is acknowledged
+    // with an RTT of 1ms
+    let loss_timer_expiration = |rtt_estimator: RttEstimator| {
+        let mut manager = Manager::new(space);
+        let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+        let mut context = MockContext::new(&mut path_manager);
+        let mut publisher = Publisher::no_snapshot();
+        context.path_mut().rtt_estimator = rtt_estimator;
+
+        for packet_number in 1..=2 {
+            manager.on_packet_sent(
+                space.new_packet_number(VarInt::from_u8(packet_number)),
+                transmission::Outcome {
+                    ack_elicitation: AckElicitation::Eliciting,
+                    is_congestion_controlled: true,
+                    bytes_sent: 128,
+                    bytes_progressed: 0,
+                },
+                now,
+                ecn,
+                transmission::Mode::Normal,
+                None,
+                &mut context,
+                &mut publisher,
+            );
+        }
+
+        ack_packets(
+            2..=2,
+            now + Duration::from_millis(1),
+            &mut context,
+            &mut manager,
+            None,
+            &mut publisher,
+        );
+
+        manager
+            .loss_timer
+            .next_expiration()
+            .expect("packet 1 is not yet lost")
+    };
+
+    // = 9/8 * 1ms
+    let rtt_estimator = RttEstimator::new(Duration::from_millis(10));
+    assert_eq!(
+        loss_timer_expiration(rtt_estimator),
+        now + Duration::from_micros(1125)
+    );
+
+    // The coarser granularity is used as the floor for the loss delay
+    let rtt_estimator = rtt_estimator.with_timer_granularity(Duration::from_millis(15));
+    assert_eq!(
+        loss_timer_expiration(rtt_estimator),
+        now + Duration::from_millis(15)
+    );
+}
+
+#[test]
+fn is_space_nearly_exhausted() {
+    let space = PacketNumberSpace::ApplicationData;
+    let mut manager = Manager::new(space);
+    let ecn = ExplicitCongestionNotification::default();
+    let mut path_manager = helper_generate_path_manager(Duration::from_millis(10));
+    let mut context = MockContext::new(&mut path_manager);
+    let mut publisher = Publisher::no_snapshot();
+    let now = s2n_quic_platform::time::now();
+
+    assert!(!manager.is_space_nearly_exhausted(1000));
+
+    let mut send = |manager: &mut Manager, packet_number: u64| {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::new(packet_number).unwrap()),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            now,
+            ecn,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    };
+
+    send(&mut manager, VarInt::MAX.as_u64() - 1001);
+    assert!(!manager.is_space_nearly_exhausted(1000));
+
+    send(&mut manager, VarInt::MAX.as_u64() - 1000);
+    assert!(manager.is_space_nearly_exhausted(1000));
+    assert!(!manager.is_space_nearly_exhausted(999));
+
+    send(&mut manager, VarInt::MAX.as_u64());
+    assert!(manager.is_space_nearly_exhausted(0));
+}
+
+#[test]
+fn loss_delay() {
+    let manager = Manager::new(PacketNumberSpace::ApplicationData);
+    let now = s2n_quic_platform::time::now();
+    let mut path = Path::new(
+        Default::default(),
+        connection::PeerId::TEST_ID,
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(10)),
+        Default::default(),
+        false,
+        DEFAULT_MAX_MTU,
+    );
+
+    // Before any samples, the initial RTT is used
+    assert_eq!(manager.loss_delay(&path), DEFAULT_INITIAL_RTT * 9 / 8);
+
+    // The larger of smoothed_rtt and latest_rtt is used
+    path.rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(800),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    path.rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_millis(400),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    assert_eq!(
+        path.rtt_estimator.smoothed_rtt(),
+        Duration::from_millis(750)
+    );
+    assert_eq!(manager.loss_delay(&path), Duration::from_micros(843_750));
+
+    // Tiny RTT samples are raised to 1ms by the RTT estimator, which keeps the loss
+    // delay at or above kGranularity
+    path.rtt_estimator = RttEstimator::new(Duration::ZERO);
+    path.rtt_estimator.update_rtt(
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        now,
+        true,
+        PacketNumberSpace::ApplicationData,
+    );
+    assert_eq!(manager.loss_delay(&path), Duration::from_micros(1125));
+    assert!(manager.loss_delay(&path) >= K_GRANULARITY);
 }
 
 #[test]
@@ -3091,7 +4903,7 @@ fn packet_declared_lost_less_than_1_ms_from_loss_threshold() {
     );
     manager.largest_acked_packet = Some(space.new_packet_number(VarInt::from_u8(2)));
 
-    let loss_time_threshold = Manager::calculate_loss_time_threshold(&context.path().rtt_estimator);
+    let loss_time_threshold = manager.calculate_loss_time_threshold(&context.path().rtt_estimator);
 
     manager.detect_and_remove_lost_packets(
         sent_time + loss_time_threshold - Duration::from_micros(999),
@@ -3191,6 +5003,11 @@ fn helper_generate_multi_path_manager(
     )
 }
 
+/// Returns an iterator of successive packet numbers in the given `space`, starting at 0
+fn packet_numbers(space: PacketNumberSpace) -> impl Iterator<Item = PacketNumber> {
+    (0u32..).map(move |packet_number| space.new_packet_number(VarInt::from_u32(packet_number)))
+}
+
 fn helper_generate_path_manager(max_ack_delay: Duration) -> path::Manager<Config> {
     helper_generate_path_manager_with_first_addr(max_ack_delay, Default::default())
 }
@@ -3226,7 +5043,7 @@ struct MockContext<'a> {
     on_packet_loss_count: u8,
     on_rtt_update_count: u8,
     path_id: path::Id,
-    lost_packets: HashSet<PacketNumber>,
+    lost_packets: HashMap<PacketNumber, LossReason>,
     path_manager: &'a mut path::Manager<Config>,
 }
 
@@ -3239,7 +5056,7 @@ impl<'a> MockContext<'a> {
             on_packet_loss_count: 0,
             on_rtt_update_count: 0,
             path_id: path_manager.active_path_id(),
-            lost_packets: HashSet::default(),
+            lost_packets: HashMap::default(),
             path_manager,
         }
     }
@@ -3300,10 +5117,12 @@ impl<'a> recovery::Context<Config> for MockContext<'a> {
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number_range: &PacketNumberRange,
+        reason: LossReason,
         _publisher: &mut Pub,
     ) {
         self.on_packet_loss_count += 1;
-        self.lost_packets.insert(packet_number_range.start());
+        self.lost_packets
+            .insert(packet_number_range.start(), reason);
     }
 
     fn on_rtt_update(&mut self) {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencySender, AckManager},
     connection::{self, ConnectionTransmissionContext, ProcessingError},
     endpoint, path,
     path::{path_event, Path},
@@ -14,7 +14,7 @@ use crate::{
     transmission,
     transmission::interest::Provider,
 };
-use core::{convert::TryInto, fmt, marker::PhantomData};
+use core::{convert::TryInto, fmt, marker::PhantomData, time::Duration};
 use once_cell::sync::OnceCell;
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
@@ -22,9 +22,9 @@ use s2n_quic_core::{
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData,
+        MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
@@ -61,6 +61,10 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
 
     ping: flag::Ping,
     keep_alive: KeepAlive,
+    /// Requests the peer acknowledge packets at the rate preferred by the recovery manager
+    ack_frequency: AckFrequencySender,
+    /// The `min_ack_delay` advertised to the peer, if any
+    local_min_ack_delay: Option<Duration>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
     pub datagram_manager: datagram::Manager<Config>,
@@ -70,6 +74,7 @@ impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ApplicationSpace")
             .field("ack_manager", &self.ack_manager)
+            .field("ack_frequency", &self.ack_frequency)
             .field("ping", &self.ping)
             .field("processed_packet_numbers", &self.processed_packet_numbers)
             .field("recovery_manager", &self.recovery_manager)
@@ -90,6 +95,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         keep_alive: KeepAlive,
         max_mtu: MaxMtu,
         datagram_manager: datagram::Manager<Config>,
+        recovery_manager: recovery::Manager<Config>,
+        ack_frequency: AckFrequencySender,
+        local_min_ack_delay: Option<Duration>,
     ) -> Self {
         let key_set = KeySet::new(key, Self::key_limits(max_mtu));
 
@@ -102,8 +110,10 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             header_key,
             ping: flag::Ping::default(),
             keep_alive,
+            ack_frequency,
+            local_min_ack_delay,
             processed_packet_numbers: SlidingWindow::default(),
-            recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
+            recovery_manager,
             datagram_manager,
         }
     }
@@ -162,6 +172,14 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let min_packet_len = context.min_packet_len;
         let bytes_progressed = self.stream_manager.outgoing_bytes_progressed();
 
+        // Request a new ack-eliciting threshold from the peer if the preferred one has changed
+        let path = context.path();
+        self.ack_frequency.update(
+            self.recovery_manager.ack_eliciting_threshold(path),
+            self.recovery_manager
+                .is_ack_eliciting_threshold_exceeded(path),
+        );
+
         let payload = transmission::Transmission {
             config: <PhantomData<Config>>::default(),
             outcome: &mut outcome,
@@ -172,6 +190,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 context.local_id_registry,
                 context.transmission_mode,
                 &mut self.ack_manager,
+                &mut self.ack_frequency,
                 handshake_status,
                 &mut self.ping,
                 &mut self.stream_manager,
@@ -380,7 +399,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             path_manager,
         );
 
-        recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
+        recovery_manager.on_loss_timeout(timestamp, random_generator, &mut context, publisher);
 
         self.stream_manager.on_timeout(timestamp);
 
@@ -400,6 +419,16 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    /// Returns the recovery manager for this packet space
+    pub fn recovery_manager(&self) -> &recovery::Manager<Config> {
+        &self.recovery_manager
+    }
+
+    /// Returns a mutable reference to the recovery manager for this packet space
+    pub fn recovery_manager_mut(&mut self) -> &mut recovery::Manager<Config> {
+        &mut self.recovery_manager
+    }
+
     pub fn ping(&mut self) {
         self.ping.send()
     }
@@ -427,6 +456,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             &mut self.recovery_manager,
             RecoveryContext {
                 ack_manager: &mut self.ack_manager,
+                ack_frequency: &mut self.ack_frequency,
                 handshake_status,
                 ping: &mut self.ping,
                 stream_manager: &mut self.stream_manager,
@@ -562,8 +592,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        // The recovery manager timers are queried across the packet number spaces by the
+        // `PacketSpaceManager`
         self.ack_manager.timers(query)?;
-        self.recovery_manager.timers(query)?;
         self.key_set.timers(query)?;
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
@@ -579,6 +610,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        self.ack_frequency.transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
@@ -595,6 +627,7 @@ impl<Config: endpoint::Config> connection::finalization::Provider for Applicatio
 
 struct RecoveryContext<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    ack_frequency: &'a mut AckFrequencySender,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut flag::Ping,
     stream_manager: &'a mut AbstractStreamManager<Config::Stream>,
@@ -648,6 +681,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
         self.handshake_status
             .on_packet_ack(packet_number_range, publisher);
         self.ping.on_packet_ack(packet_number_range);
+        self.ack_frequency.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
@@ -661,12 +695,14 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number_range: &PacketNumberRange,
+        _reason: recovery::LossReason,
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_packet_loss(packet_number_range);
         self.handshake_status
             .on_packet_loss(packet_number_range, publisher);
         self.ping.on_packet_loss(packet_number_range);
+        self.ack_frequency.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
@@ -757,6 +793,31 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_ack_frequency_frame(&mut self, frame: AckFrequency) -> Result<(), transport::Error> {
+        // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-3
+        // Endpoints MUST NOT send ACK_FREQUENCY frames to a peer that has not
+        // advertised the min_ack_delay transport parameter. An endpoint that
+        // receives an ACK_FREQUENCY frame without having advertised the
+        // min_ack_delay transport parameter MUST treat this as a connection
+        // error of type PROTOCOL_VIOLATION.
+        let min_ack_delay = self.local_min_ack_delay.ok_or_else(|| {
+            transport::Error::PROTOCOL_VIOLATION
+                .with_reason("ACK_FREQUENCY frame received without advertising min_ack_delay")
+        })?;
+
+        // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-4
+        // Receipt of a value that is less than the min_ack_delay transport
+        // parameter the endpoint sent MUST be treated as a connection error of
+        // type PROTOCOL_VIOLATION.
+        if frame.request_max_ack_delay() < min_ack_delay {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("requested max_ack_delay is less than min_ack_delay"));
+        }
+
+        self.ack_manager.on_ack_frequency(&frame);
+        Ok(())
+    }
+
     fn handle_data_blocked_frame(&mut self, frame: DataBlocked) -> Result<(), transport::Error> {
         self.stream_manager.on_data_blocked(frame)
     }
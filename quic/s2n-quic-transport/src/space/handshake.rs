@@ -62,6 +62,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeHeaderKey,
         now: Timestamp,
         ack_manager: AckManager,
+        recovery_manager: recovery::Manager<Config>,
     ) -> Self {
         Self {
             ack_manager,
@@ -70,7 +71,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             crypto_stream: CryptoStream::new(),
             tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Handshake, now),
             processed_packet_numbers: SlidingWindow::default(),
-            recovery_manager: recovery::Manager::new(PacketNumberSpace::Handshake),
+            recovery_manager,
         }
     }
 
@@ -277,7 +278,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
 
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, path_id, path_manager);
-        recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
+        recovery_manager.on_loss_timeout(timestamp, random_generator, &mut context, publisher);
     }
 
     /// Called before the Handshake packet space is discarded
@@ -298,6 +299,14 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    pub fn recovery_manager(&self) -> &recovery::Manager<Config> {
+        &self.recovery_manager
+    }
+
+    pub fn recovery_manager_mut(&mut self) -> &mut recovery::Manager<Config> {
+        &mut self.recovery_manager
+    }
+
     /// Returns the Packet Number to be used when decoding incoming packets
     pub fn packet_number_decoder(&self) -> PacketNumber {
         self.ack_manager.largest_received_packet_number_acked()
@@ -375,8 +384,9 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
 impl<Config: endpoint::Config> timer::Provider for HandshakeSpace<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        // The recovery manager timers are queried across the packet number spaces by the
+        // `PacketSpaceManager`
         self.ack_manager.timers(query)?;
-        self.recovery_manager.timers(query)?;
 
         Ok(())
     }
@@ -464,6 +474,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number_range: &PacketNumberRange,
+        _reason: recovery::LossReason,
         _publisher: &mut Pub,
     ) {
         self.crypto_stream.on_packet_loss(packet_number_range);
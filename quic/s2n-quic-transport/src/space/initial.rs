@@ -69,6 +69,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
         now: Timestamp,
         ack_manager: AckManager,
+        recovery_manager: recovery::Manager<Config>,
     ) -> Self {
         Self {
             ack_manager,
@@ -79,7 +80,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             received_hello_message: false,
             retry_token: Vec::new(),
             processed_packet_numbers: SlidingWindow::default(),
-            recovery_manager: recovery::Manager::new(PacketNumberSpace::Initial),
+            recovery_manager,
         }
     }
 
@@ -324,7 +325,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
 
         let (recovery_manager, mut context) =
             self.recovery(handshake_status, path_id, path_manager);
-        recovery_manager.on_timeout(timestamp, random_generator, &mut context, publisher);
+        recovery_manager.on_loss_timeout(timestamp, random_generator, &mut context, publisher);
     }
 
     /// Called before the Initial packet space is discarded
@@ -345,6 +346,14 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         self.recovery_manager.requires_probe()
     }
 
+    pub fn recovery_manager(&self) -> &recovery::Manager<Config> {
+        &self.recovery_manager
+    }
+
+    pub fn recovery_manager_mut(&mut self) -> &mut recovery::Manager<Config> {
+        &mut self.recovery_manager
+    }
+
     /// Returns the Packet Number to be used when decoding incoming packets
     pub fn packet_number_decoder(&self) -> PacketNumber {
         self.ack_manager.largest_received_packet_number_acked()
@@ -515,8 +524,9 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
 impl<Config: endpoint::Config> timer::Provider for InitialSpace<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        // The recovery manager timers are queried across the packet number spaces by the
+        // `PacketSpaceManager`
         self.ack_manager.timers(query)?;
-        self.recovery_manager.timers(query)?;
 
         Ok(())
     }
@@ -604,6 +614,7 @@ impl<'a, Config: endpoint::Config> recovery::Context<Config> for RecoveryContext
     fn on_packet_loss<Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number_range: &PacketNumberRange,
+        _reason: recovery::LossReason,
         _publisher: &mut Pub,
     ) {
         self.crypto_stream.on_packet_loss(packet_number_range);
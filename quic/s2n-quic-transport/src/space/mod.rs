@@ -7,7 +7,7 @@ use crate::{
     connection, endpoint, path,
     path::{path_event, Path},
     processed_packet::ProcessedPacket,
-    transmission,
+    recovery, transmission,
 };
 use bytes::Bytes;
 use core::{
@@ -22,15 +22,17 @@ use s2n_quic_core::{
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData, MaxStreams,
-        NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream, RetireConnectionId,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        AckFrequency, ConnectionClose, DataBlocked, HandshakeDone, MaxData, MaxStreamData,
+        MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse, ResetStream,
+        RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
-    time::{timer, Timestamp},
+    recovery::{loss_timers::LossTimers, CongestionController as _},
+    time::{timer, timer::Provider as _, Timestamp},
     transport,
 };
+use smallvec::SmallVec;
 
 mod application;
 mod crypto_stream;
@@ -64,6 +66,10 @@ pub struct PacketSpaceManager<Config: endpoint::Config> {
     zero_rtt_crypto:
         Option<Box<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::ZeroRttKey>>,
     handshake_status: HandshakeStatus,
+    /// The bytes delivered in packet number spaces that have been discarded
+    discarded_total_delivered: u64,
+    /// If true, the bytes in flight are checked against the sent packets in debug builds
+    invariant_checks: bool,
     /// Server Name Indication
     pub server_name: Option<ServerName>,
     //= https://www.rfc-editor.org/rfc/rfc9000#section-7
@@ -117,6 +123,7 @@ macro_rules! packet_space_api {
                 //# a now discarded packet number space.
                 path.reset_pto_backoff();
                 if let Some(mut space) = self.$field.take() {
+                    self.discarded_total_delivered += space.recovery_manager().total_delivered();
                     space.on_discard(path, path_id, publisher);
                 }
 
@@ -141,10 +148,13 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         session: <Config::TLSEndpoint as tls::Endpoint>::Session,
         initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        limits: &Limits,
         now: Timestamp,
         publisher: &mut Pub,
     ) -> Self {
         let ack_manager = AckManager::new(PacketNumberSpace::Initial, ack::Settings::EARLY);
+        let recovery_manager =
+            recovery::Manager::new(PacketNumberSpace::Initial).with_limits(limits);
 
         publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Initial,
@@ -161,11 +171,14 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 header_key,
                 now,
                 ack_manager,
+                recovery_manager,
             ))),
             handshake: None,
             application: None,
             zero_rtt_crypto: None,
             handshake_status: HandshakeStatus::default(),
+            discarded_total_delivered: 0,
+            invariant_checks: false,
             server_name: None,
             application_protocol: Bytes::new(),
         }
@@ -253,6 +266,22 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         // ensure the backoff doesn't grow too quickly
         let max_backoff = path.pto_backoff * 2;
 
+        // Only the earliest PTO timer across the packet number spaces is expired, so the PTO
+        // backoff is increased once. The requested probes are sent through the transmission
+        // interest of the probed space's recovery manager.
+        let mut managers: SmallVec<[&mut recovery::Manager<Config>; 3]> = SmallVec::new();
+        if let Some(space) = self.initial.as_mut() {
+            managers.push(space.recovery_manager_mut());
+        }
+        if let Some(space) = self.handshake.as_mut() {
+            managers.push(space.recovery_manager_mut());
+        }
+        if let Some(space) = self.application.as_mut() {
+            managers.push(space.recovery_manager_mut());
+        }
+        recovery::on_pto_timeout(&mut managers, timestamp, path, path_id, publisher);
+
+        // The PTO timers were expired above, so each space only expires its loss timer
         if let Some((space, handshake_status)) = self.initial_mut() {
             space.on_timeout(
                 handshake_status,
@@ -288,6 +317,114 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         path.pto_backoff = path.pto_backoff.min(max_backoff);
     }
 
+    /// Called when the connection is closed, such as after the idle timeout
+    ///
+    /// The sent packets of every packet number space are discarded, so no accounting leaks
+    /// into a congestion controller that is reused.
+    pub fn on_connection_close(&mut self, path_manager: &mut path::Manager<Config>) {
+        if let Some(space) = self.initial.as_mut() {
+            space
+                .recovery_manager_mut()
+                .on_connection_close(path_manager);
+        }
+        if let Some(space) = self.handshake.as_mut() {
+            space
+                .recovery_manager_mut()
+                .on_connection_close(path_manager);
+        }
+        if let Some(space) = self.application.as_mut() {
+            space
+                .recovery_manager_mut()
+                .on_connection_close(path_manager);
+        }
+    }
+
+    /// Returns the total bytes delivered over the lifetime of the connection
+    ///
+    /// Bytes delivered in discarded packet number spaces remain counted.
+    #[allow(dead_code)]
+    pub fn total_delivered(&self) -> u64 {
+        self.discarded_total_delivered
+            + core::iter::empty()
+                .chain(self.initial.iter().map(|space| space.recovery_manager()))
+                .chain(self.handshake.iter().map(|space| space.recovery_manager()))
+                .chain(
+                    self.application
+                        .iter()
+                        .map(|space| space.recovery_manager()),
+                )
+                .map(|recovery_manager| recovery_manager.total_delivered())
+                .sum::<u64>()
+    }
+
+    /// Returns the armed loss detection timer of each packet number space's recovery manager
+    fn loss_timers(&self) -> LossTimers {
+        let mut loss_timers = LossTimers::default();
+        let mut set = |space, recovery_manager: &recovery::Manager<Config>| {
+            if let Some(expiration) = recovery_manager.next_expiration() {
+                loss_timers.set(space, expiration);
+            }
+        };
+
+        if let Some(space) = self.initial.as_ref() {
+            set(PacketNumberSpace::Initial, space.recovery_manager());
+        }
+        if let Some(space) = self.handshake.as_ref() {
+            set(PacketNumberSpace::Handshake, space.recovery_manager());
+        }
+        if let Some(space) = self.application.as_ref() {
+            set(PacketNumberSpace::ApplicationData, space.recovery_manager());
+        }
+
+        loss_timers
+    }
+
+    /// Checks the bytes in flight against the sent packets after every operation
+    ///
+    /// This is intended for fuzzing and other tests that exercise the connection with
+    /// arbitrary inputs. Violations fail a `debug_assert!`, so they only panic in debug
+    /// builds.
+    #[allow(dead_code)]
+    pub fn with_invariant_checks(mut self, enabled: bool) -> Self {
+        self.invariant_checks = enabled;
+        self
+    }
+
+    /// Asserts the bytes in flight of the active path's congestion controller do not exceed
+    /// the unacknowledged packets sent on the active path across the packet number spaces,
+    /// if `invariant_checks` are enabled
+    ///
+    /// The congestion controller may count fewer bytes, since it saturates when more bytes
+    /// are acknowledged or discarded than it has in flight.
+    pub fn check_bytes_in_flight(&self, path_manager: &path::Manager<Config>) {
+        if !self.invariant_checks {
+            return;
+        }
+
+        let path_id = path_manager.active_path_id();
+        let bytes_in_flight = path_manager
+            .active_path()
+            .congestion_controller
+            .bytes_in_flight();
+        let sent_bytes_in_flight = core::iter::empty()
+            .chain(self.initial.iter().map(|space| space.recovery_manager()))
+            .chain(self.handshake.iter().map(|space| space.recovery_manager()))
+            .chain(
+                self.application
+                    .iter()
+                    .map(|space| space.recovery_manager()),
+            )
+            .map(|recovery_manager| recovery_manager.bytes_in_flight(path_id))
+            .sum::<u32>();
+
+        debug_assert!(
+            bytes_in_flight <= sent_bytes_in_flight,
+            "congestion controller bytes in flight {} exceed the {} bytes of sent packets",
+            bytes_in_flight,
+            sent_bytes_in_flight
+        );
+    }
+
     /// Signals the connection was previously blocked by anti-amplification limits
     /// but is now no longer limited.
     pub fn on_amplification_unblocked(&mut self, path: &Path<Config>, timestamp: Timestamp) {
@@ -455,6 +592,7 @@ impl<Config: endpoint::Config> timer::Provider for PacketSpaceManager<Config> {
         //# When ack-eliciting packets in multiple packet number spaces are in
         //# flight, the timer MUST be set to the earlier value of the Initial and
         //# Handshake packet number spaces.
+        self.loss_timers().timers(query)?;
 
         if let Some(space) = self.application.as_ref() {
             space.timers(query)?;
@@ -638,6 +776,7 @@ pub trait PacketSpace<Config: endpoint::Config> {
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
     default_frame_handler!(handle_new_token_frame, NewToken);
+    default_frame_handler!(handle_ack_frequency_frame, AckFrequency);
 
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -774,6 +913,10 @@ pub trait PacketSpace<Config: endpoint::Config> {
                     let on_error = on_frame_processed!(frame);
                     self.handle_datagram_frame(frame.into()).map_err(on_error)?;
                 }
+                Frame::AckFrequency(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_ack_frequency_frame(frame).map_err(on_error)?;
+                }
                 Frame::DataBlocked(frame) => {
                     let on_error = on_frame_processed!(frame);
                     self.handle_data_blocked_frame(frame).map_err(on_error)?;
@@ -896,3 +1039,142 @@ pub trait PacketSpace<Config: endpoint::Config> {
         Ok(processed_packet)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        connection::{ConnectionIdMapper, InternalConnectionIdGenerator},
+        endpoint::testing::Client as Config,
+        path::testing::helper_path_client,
+    };
+    use core::time::Duration;
+    use s2n_quic_core::{
+        crypto::key::testing::{HeaderKey, Key},
+        event::testing::Publisher,
+        random,
+        recovery::CongestionController as _,
+        stateless_reset::token::testing::TEST_TOKEN_1,
+        time::{Clock, NoopClock},
+    };
+
+    #[test]
+    fn on_timeout_expires_earliest_pto() {
+        let now = NoopClock.get_time() + Duration::from_secs(10);
+        let mut publisher = Publisher::no_snapshot();
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let internal_id = InternalConnectionIdGenerator::new().generate_id();
+        let mut local_id_registry = mapper.create_local_id_registry(
+            internal_id,
+            &connection::LocalId::TEST_ID,
+            None,
+            TEST_TOKEN_1,
+        );
+        let mut path_manager = path::Manager::<Config>::new(
+            helper_path_client(),
+            mapper.create_client_peer_id_registry(internal_id),
+        );
+
+        let mut manager = PacketSpaceManager::<Config>::new(
+            InitialId::TEST_ID,
+            tls::testing::Session,
+            Key::new(),
+            HeaderKey::new(),
+            &Limits::default(),
+            now,
+            &mut publisher,
+        );
+        manager.handshake = Some(Box::new(HandshakeSpace::new(
+            Key::new(),
+            HeaderKey::new(),
+            now,
+            AckManager::new(PacketNumberSpace::Handshake, ack::Settings::EARLY),
+            recovery::Manager::new(PacketNumberSpace::Handshake),
+        )));
+
+        // The client arms the Initial and Handshake PTO timers without packets in flight,
+        // with the Initial timer armed first
+        let path = path_manager.active_path();
+        let pto_period = path.pto_period(PacketNumberSpace::Initial);
+        let pto_backoff = path.pto_backoff;
+        manager
+            .initial
+            .as_mut()
+            .unwrap()
+            .recovery_manager_mut()
+            .update_pto_timer(path, now, false);
+        manager
+            .handshake
+            .as_mut()
+            .unwrap()
+            .recovery_manager_mut()
+            .update_pto_timer(path, now + Duration::from_millis(1), false);
+
+        // Both PTO timers have expired
+        manager.on_timeout(
+            &mut local_id_registry,
+            &mut path_manager,
+            &mut random_generator,
+            now + pto_period + Duration::from_millis(2),
+            &mut publisher,
+        );
+
+        // Only the earliest PTO timer is expired and the backoff is only increased once
+        let initial = manager.initial.as_ref().unwrap().recovery_manager();
+        assert_eq!(
+            initial.probe_request(),
+            Some(recovery::ProbeRequest {
+                space: PacketNumberSpace::Initial,
+                count: 1
+            })
+        );
+        let handshake = manager.handshake.as_ref().unwrap().recovery_manager();
+        assert_eq!(handshake.probe_request(), None);
+        assert_eq!(path_manager.active_path().pto_backoff, pto_backoff * 2);
+        assert_eq!(publisher.pto_expired, 1);
+    }
+
+    fn helper_bytes_in_flight_violation(invariant_checks: bool) {
+        let now = NoopClock.get_time();
+        let mut publisher = Publisher::no_snapshot();
+        let mut random_generator = random::testing::Generator(123);
+        let mut mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let internal_id = InternalConnectionIdGenerator::new().generate_id();
+        let mut path_manager = path::Manager::<Config>::new(
+            helper_path_client(),
+            mapper.create_client_peer_id_registry(internal_id),
+        );
+
+        let manager = PacketSpaceManager::<Config>::new(
+            InitialId::TEST_ID,
+            tls::testing::Session,
+            Key::new(),
+            HeaderKey::new(),
+            &Limits::default(),
+            now,
+            &mut publisher,
+        )
+        .with_invariant_checks(invariant_checks);
+        manager.check_bytes_in_flight(&path_manager);
+
+        // Bytes sent without a recovery manager tracking the packet are never removed from
+        // the congestion controller's bytes in flight
+        let path = path_manager.active_path_mut();
+        path.congestion_controller
+            .on_packet_sent(now, 1000, None, &path.rtt_estimator);
+        manager.check_bytes_in_flight(&path_manager);
+    }
+
+    #[test]
+    fn bytes_in_flight_unchecked() {
+        helper_bytes_in_flight_violation(false);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "exceed the 0 bytes of sent packets")]
+    fn bytes_in_flight_violation() {
+        helper_bytes_in_flight_violation(true);
+    }
+}
@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencySender, AckManager},
     connection::{self, limits::Limits},
-    endpoint, path,
+    endpoint, path, recovery,
     space::{
         datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
         InitialSpace,
@@ -29,8 +29,8 @@ use s2n_quic_core::{
     transport::{
         self,
         parameters::{
-            ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
-            InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay,
+            AckDelayExponent, ActiveConnectionIdLimit, ClientTransportParameters, DatagramLimits,
+            InitialFlowControlLimits, InitialSourceConnectionId, MaxAckDelay, MinAckDelay,
             ServerTransportParameters,
         },
     },
@@ -70,6 +70,8 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
+            AckDelayExponent,
         ),
         transport::Error,
     > {
@@ -176,6 +178,11 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .register_initial_stateless_reset_token(stateless_reset_token);
         }
 
+        let min_ack_delay = Self::validate_min_ack_delay(
+            peer_parameters.min_ack_delay,
+            peer_parameters.max_ack_delay,
+        )?;
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -188,6 +195,8 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            min_ack_delay,
+            peer_parameters.ack_delay_exponent,
         ))
     }
 
@@ -201,6 +210,8 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             ActiveConnectionIdLimit,
             DatagramLimits,
             MaxAckDelay,
+            Option<MinAckDelay>,
+            AckDelayExponent,
         ),
         transport::Error,
     > {
@@ -234,6 +245,11 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
                 .as_bytes(),
         )?;
 
+        let min_ack_delay = Self::validate_min_ack_delay(
+            peer_parameters.min_ack_delay,
+            peer_parameters.max_ack_delay,
+        )?;
+
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
 
@@ -246,6 +262,8 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             active_connection_id_limit,
             datagram_limits,
             peer_parameters.max_ack_delay,
+            min_ack_delay,
+            peer_parameters.ack_delay_exponent,
         ))
     }
 
@@ -291,6 +309,23 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
 
         Ok(())
     }
+
+    // https://datatracker.ietf.org/doc/html/draft-ietf-quic-ack-frequency-07#section-3
+    // If an endpoint receives a min_ack_delay transport parameter with a value
+    // greater than its max_ack_delay value, the endpoint MUST treat this as a
+    // connection error of type TRANSPORT_PARAMETER_ERROR.
+    fn validate_min_ack_delay(
+        min_ack_delay: Option<MinAckDelay>,
+        max_ack_delay: MaxAckDelay,
+    ) -> Result<Option<MinAckDelay>, transport::Error> {
+        min_ack_delay
+            .map(|value| value.validate_with_max_ack_delay(max_ack_delay))
+            .transpose()
+            .map_err(|_| {
+                transport::Error::TRANSPORT_PARAMETER_ERROR
+                    .with_reason("min_ack_delay exceeds max_ack_delay")
+            })
+    }
 }
 
 impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
@@ -314,6 +349,8 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
         }
 
         let ack_manager = AckManager::new(PacketNumberSpace::Handshake, ack::Settings::EARLY);
+        let recovery_manager =
+            recovery::Manager::new(PacketNumberSpace::Handshake).with_limits(self.limits);
 
         let cipher_suite = key.cipher_suite().into_event();
         *self.handshake = Some(Box::new(HandshakeSpace::new(
@@ -321,6 +358,7 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             header_key,
             self.now,
             ack_manager,
+            recovery_manager,
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::Handshake,
@@ -373,11 +411,17 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
 
         // Parse transport parameters
         let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
-        let (peer_flow_control_limits, active_connection_id_limit, datagram_limits, max_ack_delay) =
-            match Config::ENDPOINT_TYPE {
-                endpoint::Type::Client => self.on_server_params(param_decoder)?,
-                endpoint::Type::Server => self.on_client_params(param_decoder)?,
-            };
+        let (
+            peer_flow_control_limits,
+            active_connection_id_limit,
+            datagram_limits,
+            max_ack_delay,
+            min_ack_delay,
+            ack_delay_exponent,
+        ) = match Config::ENDPOINT_TYPE {
+            endpoint::Type::Client => self.on_server_params(param_decoder)?,
+            endpoint::Type::Server => self.on_client_params(param_decoder)?,
+        };
 
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
@@ -394,6 +438,11 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             self.limits.ack_settings(),
         );
 
+        let ack_frequency = AckFrequencySender::new(
+            min_ack_delay.map(MinAckDelay::as_duration),
+            max_ack_delay.as_duration(),
+        );
+
         let keep_alive = KeepAlive::new(
             self.limits.max_idle_timeout(),
             self.limits.max_keep_alive_period(),
@@ -412,6 +461,10 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             .rtt_estimator
             .on_max_ack_delay(max_ack_delay);
 
+        let recovery_manager = recovery::Manager::new(PacketNumberSpace::ApplicationData)
+            .with_ack_delay_exponent(ack_delay_exponent)
+            .with_limits(self.limits);
+
         let cipher_suite = key.cipher_suite().into_event();
         let max_mtu = self.path_manager.max_mtu();
         *self.application = Some(Box::new(ApplicationSpace::new(
@@ -423,6 +476,9 @@ impl<'a, Config: endpoint::Config, Pub: event::ConnectionPublisher>
             keep_alive,
             max_mtu,
             datagram_manager,
+            recovery_manager,
+            ack_frequency,
+            self.limits.min_ack_delay(),
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
             key_type: event::builder::KeyType::OneRtt { generation: 0 },
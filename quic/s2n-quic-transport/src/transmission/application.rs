@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ack::AckManager,
+    ack::{AckFrequencySender, AckManager},
     connection,
     contexts::WriteContext,
     endpoint, path,
@@ -33,6 +33,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         local_id_registry: &'a mut connection::LocalIdRegistry,
         transmission_mode: transmission::Mode,
         ack_manager: &'a mut AckManager,
+        ack_frequency: &'a mut AckFrequencySender,
         handshake_status: &'a mut HandshakeStatus,
         ping: &'a mut flag::Ping,
         stream_manager: &'a mut AbstractStreamManager<Config::Stream>,
@@ -47,6 +48,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
             Mode::LossRecoveryProbing | Mode::Normal => {
                 transmission::application::Payload::Normal(Normal {
                     ack_manager,
+                    ack_frequency,
                     handshake_status,
                     ping,
                     stream_manager,
@@ -103,6 +105,7 @@ impl<'a, Config: endpoint::Config> transmission::interest::Provider for Payload<
 
 pub struct Normal<'a, S: Stream, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
+    ack_frequency: &'a mut AckFrequencySender,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut Ping,
     stream_manager: &'a mut AbstractStreamManager<S>,
@@ -174,6 +177,10 @@ impl<'a, S: Stream, Config: endpoint::Config> Normal<'a, S, Config> {
         // soon as possible
         self.handshake_status.on_transmit(context);
 
+        // send ACK_FREQUENCY frames early so the peer adjusts its acknowledgements before
+        // receiving the rest of the payload
+        self.ack_frequency.on_transmit(context);
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2
         //# An endpoint MAY include other frames with the PATH_CHALLENGE and
         //# PATH_RESPONSE frames used for path validation.
@@ -194,6 +201,7 @@ impl<'a, S: Stream, Config: endpoint::Config> transmission::interest::Provider
         query: &mut Q,
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
+        self.ack_frequency.transmission_interest(query)?;
         self.handshake_status.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
         self.datagram_manager.transmission_interest(query)?;
@@ -8,12 +8,19 @@ use crate::{
         io::testing::{rand, spawn, test, time::delay, Model},
         packet_interceptor::Loss,
     },
-    Server,
+    Client, Server,
+};
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
-use std::time::Duration;
 
 mod setup;
 use bytes::Bytes;
+use s2n_quic_core::{crypto::tls::testing::certificates, stream::testing::Data};
 use s2n_quic_platform::io::testing::primary;
 use setup::*;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -235,3 +242,118 @@ fn tokio_read_exact_test() {
     })
     .unwrap();
 }
+
+/// Counts the transmission limited events published for each cause
+#[derive(Clone, Default)]
+struct TransmissionLimits {
+    congestion_limited: Arc<AtomicU32>,
+    app_limited: Arc<AtomicU32>,
+    pacing_limited: Arc<AtomicU32>,
+    queue_full: Arc<AtomicU32>,
+}
+
+impl provider::event::Subscriber for TransmissionLimits {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &provider::event::ConnectionMeta,
+        _info: &provider::event::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_transmission_limited(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        _meta: &provider::event::ConnectionMeta,
+        event: &provider::event::events::TransmissionLimited,
+    ) {
+        use provider::event::events::TransmissionLimitCause::*;
+
+        let counter = match event.cause {
+            CongestionLimited { .. } => &self.congestion_limited,
+            AppLimited { .. } => &self.app_limited,
+            PacingLimited { .. } => &self.pacing_limited,
+            QueueFull { .. } => &self.queue_full,
+            _ => return,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Ensures the reason a connection stopped transmitting is published
+#[test]
+fn transmission_limited_test() {
+    let model = Model::default();
+    let limits = TransmissionLimits::default();
+    let subscriber = limits.clone();
+
+    test(model, |handle| {
+        let server = server_with(handle, |io| {
+            Ok(Server::builder()
+                .with_io(io)?
+                .with_tls(SERVER_CERTS)?
+                .with_event(subscriber)?
+                .start()?)
+        })?;
+
+        client(handle, server)
+    })
+    .unwrap();
+
+    // The server eventually runs out of data to echo back to the client
+    assert!(limits.app_limited.load(Ordering::Relaxed) > 0);
+    // The test IO provider never fills its transmission queue for a single connection
+    assert_eq!(limits.queue_full.load(Ordering::Relaxed), 0);
+}
+
+/// Ensures a bulk transfer reports being limited by the congestion window and the pacer
+#[test]
+fn transmission_limited_bulk_test() {
+    let model = Model::default();
+    // A round trip long enough for the slow start window to be exhausted before acks arrive
+    model.set_delay(Duration::from_millis(50));
+    let limits = TransmissionLimits::default();
+    let subscriber = limits.clone();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(subscriber)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(5_000_000);
+
+            let mut recv_data = send_data;
+            primary::spawn(async move {
+                while let Some(chunk) = recv.receive().await.unwrap() {
+                    recv_data.receive(&[chunk]);
+                }
+                assert!(recv_data.is_finished());
+            });
+
+            while let Some(chunk) = send_data.send_one(usize::MAX) {
+                send.send(chunk).await.unwrap();
+            }
+            send.finish().unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    // The client sends more than the congestion window allows in a round trip
+    assert!(limits.congestion_limited.load(Ordering::Relaxed) > 0);
+    // The pacer spreads each window over the round trip rather than sending it in a burst
+    assert!(limits.pacing_limited.load(Ordering::Relaxed) > 0);
+}